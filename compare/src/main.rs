@@ -43,14 +43,7 @@ fn rawzip_impl<P: AsRef<std::path::Path>>(fp: P) -> anyhow::Result<u64> {
     let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
     let file = std::fs::File::open(fp)?;
     let archive = rawzip::ZipArchive::from_file(file, &mut buffer)?;
-    let mut entries = archive.entries(&mut buffer);
-
-    let mut result = 0;
-    while let Some(entry) = entries.next_entry()? {
-        result += entry.uncompressed_size_hint();
-    }
-
-    Ok(result)
+    Ok(archive.total_uncompressed_size(&mut buffer)?)
 }
 
 fn rc_zip<P: AsRef<std::path::Path>>(fp: P) -> anyhow::Result<u64> {
@@ -55,9 +55,18 @@ fn extract_zip_archive<P: AsRef<std::path::Path>>(
         )
     })?;
 
-    // Maintain sorted list of compressed data ranges to detect overlaps:
-    // https://www.bamsoftware.com/hacks/zipbomb/
-    let mut compressed_ranges = Vec::new();
+    // Reject archives whose entries share compressed data, a hallmark of
+    // zip bombs: https://www.bamsoftware.com/hacks/zipbomb/
+    let overlap = archive
+        .check_overlapping_entries(&mut buffer)
+        .map_err(|e| ExtractionError::zip_context(e, "Failed to read ZIP entry".to_string()))?;
+    if let Some((a, b)) = overlap {
+        return Err(ExtractionError::Rejected(format!(
+            "entries at offsets {} and {} have overlapping compressed data",
+            a.local_header_offset(),
+            b.local_header_offset()
+        )));
+    }
 
     let mut entries = archive.entries(&mut buffer);
     while let Some(entry) = entries
@@ -76,7 +85,7 @@ fn extract_zip_archive<P: AsRef<std::path::Path>>(
             }
         };
 
-        let out_path = target_dir.join(file_path.as_ref());
+        let out_path = file_path.join_onto(target_dir);
 
         if entry.is_dir() {
             std::fs::create_dir_all(&out_path).map_err(|e| {
@@ -105,38 +114,6 @@ fn extract_zip_archive<P: AsRef<std::path::Path>>(
         })?;
         let reader = zip_entry.reader();
 
-        // Check for overlapping compressed data ranges
-        let current_range = zip_entry.compressed_data_range();
-        let (current_start, current_end) = current_range;
-
-        // Find insertion point to maintain sorted order by start offset
-        let insert_pos = compressed_ranges
-            .binary_search_by_key(&current_start, |&(start, _)| start)
-            .unwrap_or_else(|pos| pos);
-
-        // Check overlap with previous range (if exists)
-        if insert_pos > 0 {
-            let (_, prev_end) = compressed_ranges[insert_pos - 1];
-            if prev_end > current_start {
-                eprintln!("Skipped file with overlapping compressed data: {file_path:?} (range {}..{} overlaps with previous range ending at {})", 
-                             current_start, current_end, prev_end);
-                continue;
-            }
-        }
-
-        // Check overlap with next range (if exists)
-        if insert_pos < compressed_ranges.len() {
-            let (next_start, _) = compressed_ranges[insert_pos];
-            if current_end > next_start {
-                eprintln!("Skipped file with overlapping compressed data: {file_path:?} (range {}..{} overlaps with next range starting at {})", 
-                             current_start, current_end, next_start);
-                continue;
-            }
-        }
-
-        // Insert the range at the correct position to maintain sorted order
-        compressed_ranges.insert(insert_pos, current_range);
-
         // "DEFLATE, the compression algorithm most commonly supported by zip
         // parsers, cannot achieve a compression ratio greater than 1032"
         // https://www.bamsoftware.com/hacks/zipbomb/
@@ -246,7 +223,7 @@ fn extract_zip_archive<P: AsRef<std::path::Path>>(
             let mode = entry.mode();
             std::fs::set_permissions(
                 &out_path,
-                std::fs::Permissions::from_mode(mode.permissions()),
+                std::fs::Permissions::from_mode(mode.permissions() & 0o777),
             )
             .map_err(|e| {
                 ExtractionError::io_context(
@@ -295,6 +272,7 @@ enum ExtractionError {
         error: std::io::Error,
         context: String,
     },
+    Rejected(String),
 }
 
 impl std::fmt::Display for ExtractionError {
@@ -306,6 +284,7 @@ impl std::fmt::Display for ExtractionError {
             ExtractionError::IoError { error, context } => {
                 write!(f, "{}: {}", context, error)
             }
+            ExtractionError::Rejected(reason) => write!(f, "Rejected ZIP archive: {}", reason),
         }
     }
 }
@@ -315,6 +294,7 @@ impl std::error::Error for ExtractionError {
         match self {
             ExtractionError::ZipError { error, .. } => Some(error),
             ExtractionError::IoError { error, .. } => Some(error),
+            ExtractionError::Rejected(_) => None,
         }
     }
 }
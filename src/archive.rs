@@ -1,19 +1,37 @@
 use crate::crc::crc32_chunk;
 use crate::errors::{Error, ErrorKind};
 use crate::mode::{
-    msdos_mode_to_file_mode, unix_mode_to_file_mode, EntryMode, CREATOR_FAT, CREATOR_MACOS,
-    CREATOR_NTFS, CREATOR_UNIX, CREATOR_VFAT,
+    msdos_mode_to_file_mode, parse_unix_owner, parse_unix_timestamp_owner, unix_mode_to_file_mode,
+    EntryMode, CREATOR_FAT, CREATOR_MACOS, CREATOR_NTFS, CREATOR_UNIX, CREATOR_VFAT, UNIX_OWNER_ID,
+    UNIX_TIMESTAMP_OWNER_ID,
+};
+use crate::path::{NormalizedPathBuf, RawPath, ZipFilePath};
+use crate::reader_at::{FileReader, MutexReader, ReaderAtExt, SeekingReader, SpannedReaderAt};
+use crate::time::{
+    extract_best_timestamp, extract_best_timestamp_with_source, find_extended_timestamps,
+    ExtendedTimestamps, TimestampSource, UtcDateTime, ZipDateTimeKind,
 };
-use crate::path::{RawPath, ZipFilePath};
-use crate::reader_at::{FileReader, MutexReader, ReaderAtExt};
-use crate::time::{extract_best_timestamp, ZipDateTimeKind};
 use crate::utils::{le_u16, le_u32, le_u64};
-use crate::{EndOfCentralDirectoryRecordFixed, ReaderAt, ZipLocator};
+#[cfg(feature = "aes")]
+use crate::winzip_aes::AesReader;
+use crate::winzip_aes::{AesExtraField, AesStrength};
+use crate::zipcrypto::ZipCryptoReader;
+use crate::{EndOfCentralDirectoryRecordFixed, ReaderAt, ReaderLen, ZipLocator};
 use std::io::{Read, Seek, Write};
 
 pub(crate) const END_OF_CENTRAL_DIR_SIGNATURE64: u32 = 0x06064b50;
 pub(crate) const END_OF_CENTRAL_DIR_LOCATOR_SIGNATURE: u32 = 0x07064b50;
 pub(crate) const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const DIGITAL_SIGNATURE_SIGNATURE: u32 = 0x05054b50;
+
+// General purpose bit flags
+const FLAG_ENCRYPTED: u16 = 0x01; // bit 0: entry is encrypted
+const FLAG_STRONG_ENCRYPTION: u16 = 0x40; // bit 6: strong encryption
+
+// Upper bound on allocations sized from an entry's declared uncompressed size,
+// as the declared size is untrusted
+pub(crate) const MAX_PREALLOCATION: u64 = 16 * 1024 * 1024;
+
 /// The recommended buffer size to use when reading from a zip file.
 ///
 /// This buffer size was chosen as it can hold an entire central directory
@@ -47,17 +65,20 @@ pub const RECOMMENDED_BUFFER_SIZE: usize = 1 << 16;
 pub struct ZipSliceArchive<T: AsRef<[u8]>> {
     pub(crate) data: T,
     pub(crate) eocd: EndOfCentralDirectory,
+    pub(crate) max_entries: u64,
 }
 
 impl<T: AsRef<[u8]>> ZipSliceArchive<T> {
     /// Returns an iterator over the entries in the central directory of the archive.
-    pub fn entries(&self) -> ZipSliceEntries {
+    pub fn entries(&self) -> ZipSliceEntries<'_> {
         let data = self.data.as_ref();
         let entry_data =
             &data[(self.eocd.offset() as usize).min(data.len())..self.eocd.end_position() as usize];
         ZipSliceEntries {
             entry_data,
             base_offset: self.eocd.base_offset(),
+            central_dir_end_pos: self.eocd.end_position(),
+            remaining_entries: self.max_entries,
         }
     }
 
@@ -83,8 +104,26 @@ impl<T: AsRef<[u8]>> ZipSliceArchive<T> {
         self.eocd.base_offset()
     }
 
+    /// Returns the byte range of the central directory as its start offset
+    /// and end position.
+    ///
+    /// The end position is where the (zip64) end of central directory record
+    /// begins. Useful for hashing or copying the directory region directly.
+    pub fn central_directory_range(&self) -> (u64, u64) {
+        (self.eocd.offset(), self.eocd.end_position())
+    }
+
+    /// Returns true if the archive has a ZIP64 end of central directory
+    /// record.
+    ///
+    /// Readers without ZIP64 support may fail to open such archives. See
+    /// [`ZipFileHeaderRecord::is_zip64`] for individual entries.
+    pub fn is_zip64(&self) -> bool {
+        self.eocd.zip64.is_some()
+    }
+
     /// The comment of the zip file.
-    pub fn comment(&self) -> ZipStr {
+    pub fn comment(&self) -> ZipStr<'_> {
         let data = self.data.as_ref();
         let comment_start = self.eocd.stream_pos as usize + EndOfCentralDirectoryRecordFixed::SIZE;
         let remaining = &data[comment_start..];
@@ -102,7 +141,176 @@ impl<T: AsRef<[u8]>> ZipSliceArchive<T> {
             reader: self.data,
             comment,
             eocd: self.eocd,
+            max_entries: self.max_entries,
+        }
+    }
+
+    /// Returns a [`ZipArchive`] view of this archive that borrows its bytes.
+    ///
+    /// Unlike [`ZipSliceArchive::into_reader`], the slice archive remains
+    /// usable, which helps when code written against [`ZipArchive`] is only
+    /// occasionally needed. The archive data is not copied, only the comment.
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// let reader_archive = archive.reader_archive();
+    ///
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let mut entries = reader_archive.entries(&mut buffer);
+    /// let entry = entries.next_entry()?.unwrap();
+    /// assert_eq!(entry.file_path().as_ref(), b"test.txt");
+    /// assert_eq!(archive.entries_hint(), reader_archive.entries_hint());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reader_archive(&self) -> ZipArchive<&[u8]> {
+        ZipArchive {
+            reader: self.data.as_ref(),
+            comment: self.comment().into_owned(),
+            eocd: self.eocd.clone(),
+            max_entries: self.max_entries,
+        }
+    }
+
+    /// Retrieves the first entry whose normalized path matches `name`.
+    ///
+    /// The central directory is scanned from the start, so prefer iterating
+    /// [`ZipSliceArchive::entries`] when looking up many entries. Entries
+    /// whose paths can't be normalized are skipped.
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/zip64-2.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// let entry = archive.get_entry_by_name("README")?.unwrap();
+    /// assert!(!entry.data().is_empty());
+    /// assert!(archive.get_entry_by_name("missing")?.is_none());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if the central directory or the matching entry is
+    /// malformed.
+    pub fn get_entry_by_name(&self, name: &str) -> Result<Option<ZipSliceEntry<'_>>, Error> {
+        let name = ZipFilePath::from_str(name);
+        for entry in self.entries() {
+            let entry = entry?;
+            let Ok(path) = entry.file_path().try_normalize() else {
+                continue;
+            };
+
+            if path.as_ref() == name.as_ref() {
+                return self.get_entry(entry.wayfinder()).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the sum of every entry's declared uncompressed size.
+    ///
+    /// The sizes come straight from the central directory and are
+    /// unvalidated, so treat the total as a hint (eg: for progress bars or
+    /// disk space pre-checks). The sum saturates instead of overflowing.
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// assert!(archive.total_uncompressed_size()? > 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn total_uncompressed_size(&self) -> Result<u64, Error> {
+        let mut total = 0u64;
+        for entry in self.entries() {
+            total = total.saturating_add(entry?.uncompressed_size_hint());
+        }
+        Ok(total)
+    }
+
+    /// Calls `f` with each entry in the central directory, stopping at the
+    /// first error, whether from parsing or returned by `f`.
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// let mut count = 0;
+    /// archive.walk(|_entry| {
+    ///     count += 1;
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(count, archive.entries_hint());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn walk<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&ZipFileHeaderRecord<'_>) -> Result<(), Error>,
+    {
+        for entry in self.entries() {
+            f(&entry?)?;
+        }
+        Ok(())
+    }
+
+    /// Counts the records in the central directory, returning a count that,
+    /// unlike [`ZipSliceArchive::entries_hint`], can be trusted for
+    /// allocation decisions.
+    ///
+    /// The hint comes from the end of central directory record, which a
+    /// hostile archive can inflate, so a hint above the true count is
+    /// tolerated. A truncated central directory, or one with more records
+    /// than the hint can account for, is an error.
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// assert_eq!(archive.validate_entry_count()?, archive.entries_hint());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate_entry_count(&self) -> Result<u64, Error> {
+        let mut count = 0u64;
+        for entry in self.entries() {
+            entry?;
+            count += 1;
         }
+        check_entry_count(self.entries_hint(), count)
+    }
+
+    /// Retrieves an entry like [`ZipSliceArchive::get_entry`], but first
+    /// checks that the local file header agrees with the central directory.
+    ///
+    /// The compression method is always compared. The CRC and sizes are
+    /// compared unless the local header defers them to a data descriptor.
+    /// Archives where the two headers disagree are corrupt or crafted to
+    /// confuse readers that trust different headers.
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// let mut entries = archive.entries();
+    /// let wayfinder = entries.next_entry()?.unwrap().wayfinder();
+    /// let entry = archive.get_entry_checked(wayfinder)?;
+    /// assert!(!entry.data().is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if the headers disagree,
+    /// in addition to the errors of [`ZipSliceArchive::get_entry`].
+    pub fn get_entry_checked(
+        &self,
+        entry: ZipArchiveEntryWayfinder,
+    ) -> Result<ZipSliceEntry<'_>, Error> {
+        check_local_header_offset(entry.local_header_offset, self.eocd.offset())?;
+        let data = self.data.as_ref();
+        let header = &data[(entry.local_header_offset as usize).min(data.len())..];
+        let file_header = ZipLocalFileHeaderFixed::parse(header)
+            .map_err(|e| e.at_offset(entry.local_header_offset))?;
+        let extra_field = header[ZipLocalFileHeaderFixed::SIZE..]
+            .get(file_header.file_name_len as usize..file_header.variable_length())
+            .ok_or(Error::from(ErrorKind::Eof))?;
+        check_local_header(&file_header, extra_field, &entry)?;
+        self.get_entry(entry)
     }
 
     /// Retrieves a specific entry from the archive by its [`ZipArchiveEntryWayfinder`].
@@ -113,10 +321,12 @@ impl<T: AsRef<[u8]>> ZipSliceArchive<T> {
     ///
     /// Returns an `Error` if the entry cannot be found or read, or if the
     /// archive is malformed.
-    pub fn get_entry(&self, entry: ZipArchiveEntryWayfinder) -> Result<ZipSliceEntry, Error> {
+    pub fn get_entry(&self, entry: ZipArchiveEntryWayfinder) -> Result<ZipSliceEntry<'_>, Error> {
+        check_local_header_offset(entry.local_header_offset, self.eocd.offset())?;
         let data = self.data.as_ref();
         let header = &data[(entry.local_header_offset as usize).min(data.len())..];
-        let file_header = ZipLocalFileHeaderFixed::parse(header)?;
+        let file_header = ZipLocalFileHeaderFixed::parse(header)
+            .map_err(|e| e.at_offset(entry.local_header_offset))?;
         let header = &header[ZipLocalFileHeaderFixed::SIZE..];
 
         let variable_length = file_header.variable_length();
@@ -154,6 +364,35 @@ impl<T: AsRef<[u8]>> ZipSliceArchive<T> {
             data_start_offset,
         })
     }
+
+    /// Returns the raw, compressed data of an entry.
+    ///
+    /// A shorthand for [`ZipSliceArchive::get_entry`] followed by
+    /// [`ZipSliceEntry::data`]. The data borrows from the archive, so an
+    /// archive that owns its data (eg: `ZipSliceArchive<Vec<u8>>`) can hand
+    /// out entry bytes directly without converting it with
+    /// [`ZipSliceArchive::into_reader`].
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(data)?;
+    /// let mut entries = archive.entries();
+    /// let wayfinder = entries.next_entry()?.unwrap().wayfinder();
+    /// let compressed = archive.entry_bytes(wayfinder)?;
+    /// assert_eq!(compressed.len() as u64, wayfinder.compressed_size_hint());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn entry_bytes(&self, entry: ZipArchiveEntryWayfinder) -> Result<&[u8], Error> {
+        self.get_entry(entry).map(|x| x.data())
+    }
+
+    /// Returns a copy of the raw, compressed data of an entry that can outlive
+    /// the archive.
+    ///
+    /// See [`ZipSliceArchive::entry_bytes`] to borrow the data instead.
+    pub fn entry_bytes_owned(&self, entry: ZipArchiveEntryWayfinder) -> Result<Vec<u8>, Error> {
+        self.entry_bytes(entry).map(<[u8]>::to_vec)
+    }
 }
 
 /// Represents a single entry (file or directory) within a `ZipSliceArchive`.
@@ -172,6 +411,14 @@ impl<'a> ZipSliceEntry<'a> {
         self.data
     }
 
+    /// Returns a reader over the raw, compressed data of the entry.
+    ///
+    /// This allows the same decompression pipeline to be used for both slice
+    /// and reader based archives (see [`ZipEntry::reader`]).
+    pub fn as_reader(&self) -> impl std::io::Read + 'a {
+        self.data
+    }
+
     /// Returns a verifier for the CRC and uncompressed size of the entry.
     ///
     /// Useful when it's more practical to oneshot decompress the data,
@@ -192,6 +439,39 @@ impl<'a> ZipSliceEntry<'a> {
             verifier: self.verifier,
             crc: 0,
             size: 0,
+            max_size: u64::MAX,
+            strict: false,
+        }
+    }
+
+    /// Returns a verifying reader like [`ZipSliceEntry::verifying_reader`]
+    /// that also verifies a stored CRC of 0.
+    ///
+    /// See [`ZipVerification::valid_strict`] for the tradeoff.
+    pub fn verifying_reader_strict<D>(&self, reader: D) -> ZipSliceVerifier<D>
+    where
+        D: std::io::Read,
+    {
+        ZipSliceVerifier {
+            strict: true,
+            ..self.verifying_reader(reader)
+        }
+    }
+
+    /// Returns a verifying reader like [`ZipSliceEntry::verifying_reader`]
+    /// that also fails with [`ErrorKind::LimitExceeded`] once the
+    /// decompressed output exceeds `max_bytes`.
+    ///
+    /// Unlike checks against the declared uncompressed size, the limit holds
+    /// even when the archive lies about the size of an entry, which guards
+    /// against decompression bombs.
+    pub fn verifying_reader_limited<D>(&self, reader: D, max_bytes: u64) -> ZipSliceVerifier<D>
+    where
+        D: std::io::Read,
+    {
+        ZipSliceVerifier {
+            max_size: max_bytes,
+            ..self.verifying_reader(reader)
         }
     }
 
@@ -206,12 +486,27 @@ impl<'a> ZipSliceEntry<'a> {
     }
 }
 
+/// Errors once more than `max_size` bytes have been decompressed.
+fn check_size_limit(size: u64, max_size: u64) -> std::io::Result<()> {
+    if size <= max_size {
+        return Ok(());
+    }
+
+    let err = Error::from(ErrorKind::LimitExceeded {
+        limit: max_size,
+        actual: size,
+    });
+    Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
+
 /// Verifies the wrapped reader returns the expected CRC and uncompressed size
 #[derive(Debug, Clone)]
 pub struct ZipSliceVerifier<D> {
     reader: D,
     crc: u32,
     size: u64,
+    max_size: u64,
+    strict: bool,
     verifier: ZipVerification,
 }
 
@@ -230,13 +525,17 @@ where
         let read = self.reader.read(buf)?;
         self.crc = crc32_chunk(&buf[..read], self.crc);
         self.size += read as u64;
+        check_size_limit(self.size, self.max_size)?;
 
         if read == 0 || self.size >= self.verifier.size() {
             self.verifier
-                .valid(ZipVerification {
-                    crc: self.crc,
-                    uncompressed_size: self.size,
-                })
+                .check(
+                    ZipVerification {
+                        crc: self.crc,
+                        uncompressed_size: self.size,
+                    },
+                    self.strict,
+                )
                 .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
         }
 
@@ -251,22 +550,28 @@ where
 pub struct ZipSliceEntries<'data> {
     entry_data: &'data [u8],
     base_offset: u64,
+    central_dir_end_pos: u64,
+    remaining_entries: u64,
 }
 
 impl<'data> ZipSliceEntries<'data> {
     /// Yield the next zip file entry in the central directory if there is any
     #[inline]
     pub fn next_entry(&mut self) -> Result<Option<ZipFileHeaderRecord<'data>>, Error> {
-        if self.entry_data.is_empty() {
+        if self.entry_data.is_empty() || is_digital_signature(self.entry_data) {
             return Ok(None);
         }
 
-        let file_header = ZipFileHeaderFixed::parse(self.entry_data)?;
+        self.remaining_entries = take_entry(self.remaining_entries)?;
+
+        let record_offset = self.central_dir_end_pos - self.entry_data.len() as u64;
+        let file_header =
+            ZipFileHeaderFixed::parse(self.entry_data).map_err(|e| e.at_offset(record_offset))?;
         self.entry_data = &self.entry_data[ZipFileHeaderFixed::SIZE..];
         let Some((file_name, extra_field, file_comment, entry_data)) =
             file_header.parse_variable_length(self.entry_data)
         else {
-            return Err(Error::from(ErrorKind::Eof));
+            return Err(Error::from(ErrorKind::Eof).at_offset(record_offset));
         };
 
         let mut entry =
@@ -275,6 +580,32 @@ impl<'data> ZipSliceEntries<'data> {
         self.entry_data = entry_data;
         Ok(Some(entry))
     }
+
+    /// Drains the remaining entries into owned pairs of normalized path and
+    /// wayfinder, suitable for storing as a manifest of the archive.
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// let manifest = archive.entries().manifest()?;
+    /// assert_eq!(manifest[0].0.as_ref(), "test.txt");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry is malformed or its name is not valid
+    /// UTF-8.
+    pub fn manifest(
+        mut self,
+    ) -> Result<Vec<(ZipFilePath<NormalizedPathBuf>, ZipArchiveEntryWayfinder)>, Error> {
+        let mut result = Vec::new();
+        while let Some(entry) = self.next_entry()? {
+            let path = entry.file_path().try_normalize()?.into_owned();
+            result.push((path, entry.wayfinder()));
+        }
+        Ok(result)
+    }
 }
 
 impl<'data> Iterator for ZipSliceEntries<'data> {
@@ -311,6 +642,7 @@ pub struct ZipArchive<R> {
     pub(crate) reader: R,
     pub(crate) comment: ZipString,
     pub(crate) eocd: EndOfCentralDirectory,
+    pub(crate) max_entries: u64,
 }
 
 impl ZipArchive<()> {
@@ -338,12 +670,32 @@ impl ZipArchive<()> {
             .map_err(|(_, e)| e)
     }
 
+    /// Parses an archive from a file like [`ZipArchive::from_file`], but
+    /// allocates a buffer of [`RECOMMENDED_BUFFER_SIZE`] internally.
+    ///
+    /// Iterating the entries of the returned archive still requires a buffer.
+    ///
+    /// ```rust
+    /// let file = std::fs::File::open("assets/test.zip")?;
+    /// let archive = rawzip::ZipArchive::from_file_buffered(file)?;
+    /// assert_eq!(archive.entries_hint(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_file_buffered(file: std::fs::File) -> Result<ZipArchive<FileReader>, Error> {
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        Self::from_file(file, &mut buffer)
+    }
+
     /// Parses an archive from a seekable reader.
     ///
     /// Prefer [`ZipArchive::from_file`] and [`ZipArchive::from_slice`] when
     /// possible, as they are more efficient due to not wrapping the underlying
     /// reader in a mutex to support positioned io.
     ///
+    /// Every read seeks to its offset and back again, which discards the
+    /// buffer of a [`std::io::BufReader`]. Use
+    /// [`ZipArchive::from_buffered_seekable`] for buffered readers.
+    ///
     /// ```rust
     /// # use rawzip::{ZipArchive, Error, RECOMMENDED_BUFFER_SIZE, ZipFileHeaderRecord};
     /// # use std::io::Cursor;
@@ -366,6 +718,109 @@ impl ZipArchive<()> {
             .locate_in_reader(reader, buffer, end_offset)
             .map_err(|(_, e)| e)
     }
+
+    /// Parses an archive from a seekable reader that is expensive to seek,
+    /// such as a [`std::io::BufReader`].
+    ///
+    /// The reader is wrapped in a [`SeekingReader`], which skips seeking when
+    /// a read continues from the end of the previous one, so sequential reads
+    /// are served from the reader's buffer. The reader's stream position is
+    /// not preserved.
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, RECOMMENDED_BUFFER_SIZE};
+    /// let file = std::io::BufReader::new(std::fs::File::open("assets/test.zip")?);
+    /// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    /// let archive = ZipArchive::from_buffered_seekable(file, &mut buffer)?;
+    /// assert_eq!(archive.entries_hint(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_buffered_seekable<R>(
+        mut reader: R,
+        buffer: &mut [u8],
+    ) -> Result<ZipArchive<SeekingReader<R>>, Error>
+    where
+        R: Read + Seek,
+    {
+        let end_offset = reader.seek(std::io::SeekFrom::End(0))?;
+        let reader = SeekingReader::new(reader);
+        ZipLocator::new()
+            .locate_in_reader(reader, buffer, end_offset)
+            .map_err(|(_, e)| e)
+    }
+
+    /// Parses an archive from a [`ReaderAt`] that knows its length, which
+    /// saves the seek to the end that [`ZipArchive::from_seekable`] needs.
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, RECOMMENDED_BUFFER_SIZE};
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    /// let archive = ZipArchive::from_reader_at(data, &mut buffer)?;
+    /// assert_eq!(archive.entries_hint(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if the reader doesn't
+    /// know its length, in addition to the errors of locating the archive.
+    pub fn from_reader_at<R>(reader: R, buffer: &mut [u8]) -> Result<ZipArchive<R>, Error>
+    where
+        R: ReaderAt + ReaderLen,
+    {
+        let Some(end_offset) = ReaderLen::len(&reader) else {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "length of reader is unknown".to_string(),
+            }));
+        };
+
+        ZipLocator::new()
+            .locate_in_reader(reader, buffer, end_offset)
+            .map_err(|(_, e)| e)
+    }
+
+    /// Parses an archive split across multiple segments (eg: `.z01`, `.z02`,
+    /// ..., `.zip`), given as `(length, reader)` pairs in order.
+    ///
+    /// The segments are stitched together with a [`SpannedReaderAt`]. Offsets
+    /// recorded in the archive are relative to the disk named alongside them
+    /// and are resolved against where that segment starts, as written by
+    /// `zip -s`. The spanning marker (`PK\x07\x08`) that starts the first
+    /// segment is counted in the offsets of the first disk, so it is left in
+    /// place. Archives split byte-wise after the fact record every offset
+    /// against disk 0, so they read as if they were whole.
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, RECOMMENDED_BUFFER_SIZE};
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let (head, tail) = data.split_at(data.len() / 2);
+    /// let segments = vec![(head.len() as u64, head), (tail.len() as u64, tail)];
+    ///
+    /// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    /// let archive = ZipArchive::from_segments(segments, &mut buffer)?;
+    /// assert_eq!(archive.entries_hint(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_segments<R>(
+        segments: Vec<(u64, R)>,
+        buffer: &mut [u8],
+    ) -> Result<ZipArchive<SpannedReaderAt<R>>, Error>
+    where
+        R: ReaderAt,
+    {
+        let reader = SpannedReaderAt::new(segments);
+        let end_offset = reader.len();
+        let disk_starts = reader.segment_starts().to_vec();
+        let archive = ZipLocator::new()
+            .disk_starts(disk_starts)
+            .locate_in_reader(reader, buffer, end_offset)
+            .map_err(|(_, e)| e)?;
+
+        let (disk, offset) = archive.eocd.central_dir_location();
+        archive.eocd.disk_offset(disk, offset)?;
+        Ok(archive)
+    }
 }
 
 impl<R> ZipArchive<R> {
@@ -403,11 +858,7 @@ impl<R> ZipArchive<R> {
         ZipEntries {
             buffer,
             archive: self,
-            pos: 0,
-            end: 0,
-            offset: self.eocd.offset(),
-            base_offset: self.eocd.base_offset(),
-            central_dir_end_pos: self.eocd.end_position(),
+            cursor: CentralDirectoryCursor::new(&self.eocd, self.max_entries),
         }
     }
 
@@ -419,7 +870,7 @@ impl<R> ZipArchive<R> {
     }
 
     /// Returns the comment of the zip archive, if any.
-    pub fn comment(&self) -> ZipStr {
+    pub fn comment(&self) -> ZipStr<'_> {
         self.comment.as_str()
     }
 
@@ -430,6 +881,24 @@ impl<R> ZipArchive<R> {
     pub fn base_offset(&self) -> u64 {
         self.eocd.base_offset()
     }
+
+    /// Returns the byte range of the central directory as its start offset
+    /// and end position.
+    ///
+    /// The end position is where the (zip64) end of central directory record
+    /// begins. Useful for hashing or copying the directory region directly.
+    pub fn central_directory_range(&self) -> (u64, u64) {
+        (self.eocd.offset(), self.eocd.end_position())
+    }
+
+    /// Returns true if the archive has a ZIP64 end of central directory
+    /// record.
+    ///
+    /// Readers without ZIP64 support may fail to open such archives. See
+    /// [`ZipFileHeaderRecord::is_zip64`] for individual entries.
+    pub fn is_zip64(&self) -> bool {
+        self.eocd.zip64.is_some()
+    }
 }
 
 impl<R> ZipArchive<R>
@@ -437,7 +906,14 @@ where
     R: ReaderAt,
 {
     /// Retrieves a specific entry from the archive by a wayfinder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if the wayfinder's local
+    /// header offset isn't before the central directory, and otherwise an
+    /// error if the local file header can't be read.
     pub fn get_entry(&self, entry: ZipArchiveEntryWayfinder) -> Result<ZipEntry<'_, R>, Error> {
+        check_local_header_offset(entry.local_header_offset, self.eocd.offset())?;
         let mut buffer = [0u8; ZipLocalFileHeaderFixed::SIZE];
         self.reader
             .read_exact_at(&mut buffer, entry.local_header_offset)?;
@@ -446,80 +922,918 @@ where
         // out the local file header to verify the signature and understand the
         // variable length. Not everyone uses this as the source of truth:
         // https://labs.redyops.com/index.php/2020/04/30/spending-a-night-reading-the-zip-file-format-specification/
-        let file_header = ZipLocalFileHeaderFixed::parse(&buffer)?;
-        let body_offset = entry.local_header_offset
-            + ZipLocalFileHeaderFixed::SIZE as u64
-            + file_header.variable_length() as u64;
-
-        Ok(ZipEntry {
-            archive: self,
-            entry,
-            body_offset,
-            body_end_offset: entry.compressed_size + body_offset,
-        })
+        let file_header = ZipLocalFileHeaderFixed::parse(&buffer)
+            .map_err(|e| e.at_offset(entry.local_header_offset))?;
+        Ok(ZipEntry::new(self, entry, file_header))
     }
-}
-
-/// Represents a single entry (file or directory) within a [`ZipArchive`]
-#[derive(Debug, Clone)]
-pub struct ZipEntry<'archive, R> {
-    archive: &'archive ZipArchive<R>,
-    body_offset: u64,
-    body_end_offset: u64,
-    entry: ZipArchiveEntryWayfinder,
-}
 
-impl<'archive, R> ZipEntry<'archive, R>
-where
-    R: ReaderAt,
-{
-    /// Returns a [`ZipReader`] for reading the compressed data of this entry.
-    pub fn reader(&self) -> ZipReader<'archive, R> {
-        ZipReader {
-            archive: self.archive,
-            entry: self.entry,
-            offset: self.body_offset,
-            end_offset: self.body_end_offset,
-        }
+    /// Retrieves an entry like [`ZipArchive::get_entry`], but first checks
+    /// that the local file header agrees with the central directory.
+    ///
+    /// See [`ZipSliceArchive::get_entry_checked`] for what is compared.
+    pub fn get_entry_checked(
+        &self,
+        entry: ZipArchiveEntryWayfinder,
+    ) -> Result<ZipEntry<'_, R>, Error> {
+        let result = self.get_entry(entry)?;
+        let local_header = &result.local_header;
+        let mut extra_field = vec![0u8; local_header.extra_field_len as usize];
+        let extra_field_offset = entry.local_header_offset
+            + ZipLocalFileHeaderFixed::SIZE as u64
+            + u64::from(local_header.file_name_len);
+        self.reader
+            .read_exact_at(&mut extra_field, extra_field_offset)?;
+        check_local_header(local_header, &extra_field, &entry)?;
+        Ok(result)
     }
 
-    /// Returns a reader that wraps a decompressor and verify the size and CRC
-    /// of the decompressed data once finished.
-    pub fn verifying_reader<D>(&self, reader: D) -> ZipVerifier<'archive, D, R>
-    where
-        D: std::io::Read,
-    {
-        ZipVerifier {
-            reader,
-            crc: 0,
-            size: 0,
-            archive: self.archive,
-            end_offset: self.body_end_offset,
-            wayfinder: self.entry,
-        }
+    /// Returns a reader of an entry's compressed data, a shortcut for
+    /// [`ZipArchive::get_entry`] followed by [`ZipEntry::reader`].
+    ///
+    /// Readers only borrow the archive, so any number of them can read
+    /// entries at once. As long as `R` is [`Sync`] (like [`FileReader`] and
+    /// byte slices), the archive can be shared with scoped threads that each
+    /// read their own entries, without locking or cloning the archive.
+    /// Readers that seek a shared stream, like [`SeekingReader`], are `Sync`
+    /// but serialize reads behind a mutex, so open the archive once per
+    /// thread instead when reads should proceed in parallel.
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    ///
+    /// let file = std::fs::File::open("assets/test.zip")?;
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let archive = rawzip::ZipArchive::from_file(file, &mut buffer)?;
+    /// let mut wayfinders = Vec::new();
+    /// let mut entries = archive.entries(&mut buffer);
+    /// while let Some(entry) = entries.next_entry()? {
+    ///     wayfinders.push(entry.wayfinder());
+    /// }
+    ///
+    /// let sizes = std::thread::scope(|scope| {
+    ///     let handles: Vec<_> = wayfinders
+    ///         .into_iter()
+    ///         .map(|wayfinder| {
+    ///             let archive = &archive;
+    ///             scope.spawn(move || -> Result<usize, rawzip::Error> {
+    ///                 let mut data = Vec::new();
+    ///                 archive.reader_for(wayfinder)?.read_to_end(&mut data)?;
+    ///                 Ok(data.len())
+    ///             })
+    ///         })
+    ///         .collect();
+    ///     handles
+    ///         .into_iter()
+    ///         .map(|handle| handle.join().unwrap())
+    ///         .collect::<Result<Vec<_>, _>>()
+    /// })?;
+    /// assert_eq!(sizes.len(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`FileReader`]: crate::FileReader
+    /// [`SeekingReader`]: crate::SeekingReader
+    pub fn reader_for(&self, entry: ZipArchiveEntryWayfinder) -> Result<ZipReader<'_, R>, Error> {
+        self.get_entry(entry).map(|entry| entry.reader())
     }
 
-    /// Returns a tuple of start and end byte offsets for the compressed data
-    /// within the underlying reader.
+    /// Returns the first pair of entries found whose compressed data
+    /// overlaps, or `None` if there are none.
     ///
-    /// This method uses the information from the local file header in its
-    /// calculations.
+    /// Overlapping entries are a hallmark of zip bombs
+    /// (<https://www.bamsoftware.com/hacks/zipbomb/>), where many central
+    /// directory records reference the same compressed data. Well formed
+    /// archives will return `None`, so extraction code can reject an archive
+    /// up front with a single call.
     ///
-    /// # Security Usage
+    /// Only the first overlapping pair is reported rather than every pair: a
+    /// zip bomb can have every entry overlap every other one, so collecting
+    /// all pairs would grow quadratically with the number of entries, while
+    /// one pair is enough to reject the archive. The pair is reported as
+    /// wayfinders instead of names, as entry names aren't guaranteed to be
+    /// UTF-8 and the wayfinders can be passed straight to
+    /// [`ZipArchive::get_entry`].
     ///
-    /// This method is useful for detecting overlapping entries, which are often
-    /// used in zip bombs. By comparing the ranges returned by this method
-    /// across multiple entries, you can identify when entries share compressed
-    /// data:
+    /// Entries without any compressed data (eg: directories) never overlap.
     ///
     /// ```rust
-    /// # use rawzip::{ZipArchive, Error};
-    /// # fn example(data: &[u8]) -> Result<(), Error> {
-    /// let archive = ZipArchive::from_slice(data)?;
-    /// let mut ranges = Vec::new();
-    ///
-    /// for entry_result in archive.entries() {
-    ///     let entry = entry_result?;
+    /// # use rawzip::{ZipArchive, Error, RECOMMENDED_BUFFER_SIZE};
+    /// # use std::fs::File;
+    /// fn example(file: File) -> Result<(), Error> {
+    ///     let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    ///     let archive = ZipArchive::from_file(file, &mut buffer)?;
+    ///     if let Some((a, b)) = archive.check_overlapping_entries(&mut buffer)? {
+    ///         panic!(
+    ///             "entries at {} and {} overlap",
+    ///             a.local_header_offset(),
+    ///             b.local_header_offset()
+    ///         );
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn check_overlapping_entries(
+        &self,
+        buffer: &mut [u8],
+    ) -> Result<Option<(ZipArchiveEntryWayfinder, ZipArchiveEntryWayfinder)>, Error> {
+        let mut wayfinders = Vec::new();
+        let mut entries = self.entries(buffer);
+        while let Some(entry) = entries.next_entry()? {
+            wayfinders.push(entry.wayfinder());
+        }
+
+        let mut ranges = Vec::with_capacity(wayfinders.len());
+        for (index, wayfinder) in wayfinders.iter().enumerate() {
+            let (start, end) = self.get_entry(*wayfinder)?.compressed_data_range();
+            if start < end {
+                ranges.push((start, end, index));
+            }
+        }
+
+        Ok(first_overlapping_range(ranges).map(|(a, b)| (wayfinders[a], wayfinders[b])))
+    }
+
+    /// Returns the sum of every entry's declared uncompressed size.
+    ///
+    /// The sizes come straight from the central directory and are
+    /// unvalidated, so treat the total as a hint (eg: for progress bars or
+    /// disk space pre-checks). The sum saturates instead of overflowing.
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, Error, RECOMMENDED_BUFFER_SIZE};
+    /// # use std::fs::File;
+    /// fn example(file: File) -> Result<(), Error> {
+    ///     let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    ///     let archive = ZipArchive::from_file(file, &mut buffer)?;
+    ///     let total = archive.total_uncompressed_size(&mut buffer)?;
+    ///     println!("extracting {} bytes", total);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn total_uncompressed_size(&self, buffer: &mut [u8]) -> Result<u64, Error> {
+        let mut total = 0u64;
+        let mut entries = self.entries(buffer);
+        while let Some(entry) = entries.next_entry()? {
+            total = total.saturating_add(entry.uncompressed_size_hint());
+        }
+        Ok(total)
+    }
+
+    /// Calls `f` with each entry in the central directory, stopping at the
+    /// first error, whether from parsing or returned by `f`.
+    ///
+    /// A convenience over driving [`ZipArchive::entries`] by hand when every
+    /// entry is processed the same way.
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, Error, RECOMMENDED_BUFFER_SIZE};
+    /// # use std::fs::File;
+    /// fn example(file: File) -> Result<(), Error> {
+    ///     let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    ///     let archive = ZipArchive::from_file(file, &mut buffer)?;
+    ///     archive.walk(&mut buffer, |entry| {
+    ///         println!("{}", entry.file_path().try_normalize()?.as_ref());
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn walk<F>(&self, buffer: &mut [u8], mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&ZipFileHeaderRecord<'_>) -> Result<(), Error>,
+    {
+        let mut entries = self.entries(buffer);
+        while let Some(entry) = entries.next_entry()? {
+            f(&entry)?;
+        }
+        Ok(())
+    }
+
+    /// Counts the records in the central directory, returning a count that
+    /// can be trusted for allocation decisions.
+    ///
+    /// See [`ZipSliceArchive::validate_entry_count`] for how the count is
+    /// checked against the hint.
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, Error, RECOMMENDED_BUFFER_SIZE};
+    /// # use std::fs::File;
+    /// fn example(file: File) -> Result<(), Error> {
+    ///     let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    ///     let archive = ZipArchive::from_file(file, &mut buffer)?;
+    ///     let count = archive.validate_entry_count(&mut buffer)?;
+    ///     println!("{} entries", count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn validate_entry_count(&self, buffer: &mut [u8]) -> Result<u64, Error> {
+        let mut count = 0u64;
+        let mut entries = self.entries(buffer);
+        while entries.next_entry()?.is_some() {
+            count += 1;
+        }
+        check_entry_count(self.entries_hint(), count)
+    }
+
+    /// Decompresses every entry and verifies its size and CRC, stopping at
+    /// the first failure.
+    ///
+    /// The `decompressor` is given each entry's compression method and
+    /// compressed data, and returns a reader of the decompressed data. The
+    /// returned error names the entry that failed, see [`Error::entry_name`].
+    ///
+    /// ```rust
+    /// use rawzip::{CompressionMethod, Error, ErrorKind, ZipArchive, RECOMMENDED_BUFFER_SIZE};
+    /// use std::io::Read;
+    ///
+    /// let file = std::fs::File::open("assets/test.zip")?;
+    /// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    /// let archive = ZipArchive::from_file(file, &mut buffer)?;
+    /// archive.verify_all(&mut buffer, |method, reader| match method {
+    ///     CompressionMethod::Store => Ok(Box::new(reader)),
+    ///     CompressionMethod::Deflate => Ok(Box::new(flate2::read::DeflateDecoder::new(reader))),
+    ///     _ => Err(Error::from(ErrorKind::InvalidInput {
+    ///         msg: format!("unsupported compression method: {:?}", method),
+    ///     })),
+    /// })?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_all<F>(&self, buffer: &mut [u8], mut decompressor: F) -> Result<(), Error>
+    where
+        F: for<'a> FnMut(CompressionMethod, ZipReader<'a, R>) -> Result<Box<dyn Read + 'a>, Error>,
+    {
+        let mut entries = self.entries(buffer);
+        while let Some(entry) = entries.next_entry()? {
+            let name = String::from_utf8_lossy(entry.file_path().as_ref()).into_owned();
+            let method = entry.compression_method();
+            let wayfinder = entry.wayfinder();
+
+            let result = self.get_entry(wayfinder).and_then(|entry| {
+                let decompressed = decompressor(method, entry.reader())?;
+                let mut reader = entry.verifying_reader(decompressed);
+                std::io::copy(&mut reader, &mut std::io::sink()).map_err(Error::from_io)?;
+                Ok(())
+            });
+
+            result.map_err(|e| e.for_entry(name))?;
+        }
+
+        Ok(())
+    }
+
+    /// Decompresses an entry into `out`, verifying its size and CRC, and
+    /// returns the number of decompressed bytes written.
+    ///
+    /// The `decompressor` is given the entry's compression method and
+    /// compressed data, and returns a reader of the decompressed data.
+    ///
+    /// ```rust
+    /// use rawzip::{CompressionMethod, Error, ErrorKind, ZipArchive, RECOMMENDED_BUFFER_SIZE};
+    ///
+    /// let file = std::fs::File::open("assets/test.zip")?;
+    /// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    /// let archive = ZipArchive::from_file(file, &mut buffer)?;
+    /// let wayfinder = archive.entries(&mut buffer).next_entry()?.unwrap().wayfinder();
+    /// let mut out = Vec::new();
+    /// archive.extract_entry_to(wayfinder, |method, reader| match method {
+    ///     CompressionMethod::Deflate => Ok(flate2::read::DeflateDecoder::new(reader)),
+    ///     _ => Err(Error::from(ErrorKind::InvalidInput {
+    ///         msg: format!("unsupported compression method: {:?}", method),
+    ///     })),
+    /// }, &mut out)?;
+    /// assert_eq!(out, b"This is a test text file.\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the decompressor's error, or an error if reading or writing
+    /// fails or the size or CRC doesn't match.
+    pub fn extract_entry_to<'a, F, D, W>(
+        &'a self,
+        wayfinder: ZipArchiveEntryWayfinder,
+        decompressor: F,
+        mut out: W,
+    ) -> Result<u64, Error>
+    where
+        F: FnOnce(CompressionMethod, ZipReader<'a, R>) -> Result<D, Error>,
+        D: Read,
+        W: Write,
+    {
+        let entry = self.get_entry(wayfinder)?;
+        let decompressed = decompressor(wayfinder.compression_method(), entry.reader())?;
+        let mut reader = entry.verifying_reader(decompressed);
+        std::io::copy(&mut reader, &mut out).map_err(Error::from_io)
+    }
+}
+
+/// Returns the `actual` number of central directory records, unless there are
+/// more than the end of central directory record's `hint` accounts for.
+///
+/// Legacy writers without ZIP64 support store the count of archives with more
+/// than 65535 entries truncated to 16 bits or saturated at `0xFFFF`, so those
+/// hints are accepted.
+fn check_entry_count(hint: u64, actual: u64) -> Result<u64, Error> {
+    let legacy_hint = hint == u64::from(u16::MAX) || hint == actual & u64::from(u16::MAX);
+    if actual > hint && !legacy_hint {
+        return Err(Error::from(ErrorKind::InconsistentArchive {
+            msg: format!(
+                "central directory has {} records but the end of central directory declares {}",
+                actual, hint
+            ),
+        }));
+    }
+
+    Ok(actual)
+}
+
+/// Errors if a local file header at `offset` doesn't precede the central
+/// directory starting at `central_directory_offset`, as every local header
+/// must.
+pub(crate) fn check_local_header_offset(
+    offset: u64,
+    central_directory_offset: u64,
+) -> Result<(), Error> {
+    if offset >= central_directory_offset {
+        return Err(Error::from(ErrorKind::InconsistentArchive {
+            msg: format!(
+                "local header offset {} is not before the central directory at {}",
+                offset, central_directory_offset
+            ),
+        }));
+    }
+
+    Ok(())
+}
+
+/// Errors if the local file header disagrees with the central directory.
+pub(crate) fn check_local_header(
+    local: &ZipLocalFileHeaderFixed,
+    extra_field: &[u8],
+    entry: &ZipArchiveEntryWayfinder,
+) -> Result<(), Error> {
+    let mismatch = |field: &'static str, local: u64, central: u64| {
+        Error::from(ErrorKind::LocalHeaderMismatch {
+            field,
+            local,
+            central,
+        })
+        .at_offset(entry.local_header_offset)
+    };
+
+    if local.compression_method != entry.compression_method {
+        return Err(mismatch(
+            "compression method",
+            u64::from(local.compression_method.as_u16()),
+            u64::from(entry.compression_method.as_u16()),
+        ));
+    }
+
+    // Streamed entries defer the CRC and sizes to the data descriptor
+    if local.flags & 0x08 != 0 {
+        return Ok(());
+    }
+
+    if local.crc32 != entry.crc {
+        return Err(mismatch(
+            "crc",
+            u64::from(local.crc32),
+            u64::from(entry.crc),
+        ));
+    }
+
+    let mut uncompressed_size = u64::from(local.uncompressed_size);
+    let mut compressed_size = u64::from(local.compressed_size);
+    if let Some(mut field) = find_extra_field(extra_field, 0x0001) {
+        // Unlike the central directory, a local zip64 extra field must hold
+        // both sizes whenever either one overflows, so only fall back to
+        // reading just the sentinel fields for short (non-conforming) fields.
+        if field.len() >= 16 {
+            if local.uncompressed_size == u32::MAX {
+                uncompressed_size = le_u64(&field[..8]);
+            }
+
+            if local.compressed_size == u32::MAX {
+                compressed_size = le_u64(&field[8..16]);
+            }
+        } else {
+            if local.uncompressed_size == u32::MAX {
+                if let Some(size) = field.get(..8).map(le_u64) {
+                    uncompressed_size = size;
+                    field = &field[8..];
+                }
+            }
+
+            if local.compressed_size == u32::MAX {
+                if let Some(size) = field.get(..8).map(le_u64) {
+                    compressed_size = size;
+                }
+            }
+        }
+    }
+
+    if uncompressed_size != entry.uncompressed_size {
+        return Err(mismatch(
+            "uncompressed size",
+            uncompressed_size,
+            entry.uncompressed_size,
+        ));
+    }
+
+    if compressed_size != entry.compressed_size {
+        return Err(mismatch(
+            "compressed size",
+            compressed_size,
+            entry.compressed_size,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns the data of the first extra field with the given header id.
+fn find_extra_field(mut extra_fields: &[u8], id: u16) -> Option<&[u8]> {
+    while let (Some(kind), Some(size)) = (
+        extra_fields.get(0..2).map(le_u16),
+        extra_fields.get(2..4).map(le_u16),
+    ) {
+        extra_fields = &extra_fields[4..];
+        let end_pos = (size as usize).min(extra_fields.len());
+        let (field, rest) = extra_fields.split_at(end_pos);
+        if kind == id {
+            return Some(field);
+        }
+        extra_fields = rest;
+    }
+
+    None
+}
+
+/// Given half-open ranges tagged with an index, returns the index pair of the
+/// first ranges found to overlap.
+fn first_overlapping_range(mut ranges: Vec<(u64, u64, usize)>) -> Option<(usize, usize)> {
+    ranges.sort_unstable();
+
+    // Once sorted by start, a range overlaps an earlier one exactly when it
+    // starts before the furthest end seen so far
+    let mut furthest: Option<(u64, usize)> = None;
+    for (start, end, index) in ranges {
+        match furthest {
+            Some((furthest_end, furthest_index)) if furthest_end > start => {
+                return Some((furthest_index, index));
+            }
+            _ => furthest = Some((end, index)),
+        }
+    }
+
+    None
+}
+
+/// Represents a single entry (file or directory) within a [`ZipArchive`]
+#[derive(Debug, Clone)]
+pub struct ZipEntry<'archive, R> {
+    archive: &'archive ZipArchive<R>,
+    body_offset: u64,
+    body_end_offset: u64,
+    entry: ZipArchiveEntryWayfinder,
+    local_header: ZipLocalFileHeaderFixed,
+}
+
+impl<'archive, R> ZipEntry<'archive, R>
+where
+    R: ReaderAt,
+{
+    /// Creates the entry whose data follows the given local file header.
+    pub(crate) fn new(
+        archive: &'archive ZipArchive<R>,
+        entry: ZipArchiveEntryWayfinder,
+        local_header: ZipLocalFileHeaderFixed,
+    ) -> Self {
+        let body_offset = entry.local_header_offset
+            + ZipLocalFileHeaderFixed::SIZE as u64
+            + local_header.variable_length() as u64;
+
+        ZipEntry {
+            archive,
+            entry,
+            body_offset,
+            body_end_offset: entry.compressed_size + body_offset,
+            local_header,
+        }
+    }
+
+    /// The local file header of the entry.
+    pub(crate) fn local_header(&self) -> &ZipLocalFileHeaderFixed {
+        &self.local_header
+    }
+
+    /// Reads the extra field of the entry's local file header.
+    pub(crate) fn local_extra_field(&self) -> Result<Vec<u8>, Error> {
+        let extra_field_offset = self.entry.local_header_offset
+            + ZipLocalFileHeaderFixed::SIZE as u64
+            + u64::from(self.local_header.file_name_len);
+        let mut extra_field = vec![0u8; usize::from(self.local_header.extra_field_len)];
+        self.archive
+            .reader
+            .read_exact_at(&mut extra_field, extra_field_offset)?;
+        Ok(extra_field)
+    }
+
+    /// Returns a [`ZipReader`] for reading the compressed data of this entry.
+    ///
+    /// The reader yields exactly the number of compressed bytes recorded in
+    /// the central directory (including sizes from a ZIP64 extra field) and
+    /// then signals EOF, so a trailing data descriptor is never read. If the
+    /// archive ends before then, an [`UnexpectedEof`] error is returned.
+    ///
+    /// [`UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
+    pub fn reader(&self) -> ZipReader<'archive, R> {
+        ZipReader {
+            archive: self.archive,
+            entry: self.entry,
+            start_offset: self.body_offset,
+            offset: self.body_offset,
+            end_offset: self.body_end_offset,
+        }
+    }
+
+    /// Returns a reader of the decompressed data that supports seeking, as
+    /// created by `decompressor` from the entry's compressed data.
+    ///
+    /// Only some formats (eg: stored data or zstd's seekable format) can seek
+    /// without decompressing everything before the target position, so
+    /// decompressors return an error for the methods they can't seek in. The
+    /// [`ZipReader`] given to the decompressor is itself seekable within the
+    /// compressed data.
+    ///
+    /// ```rust
+    /// use rawzip::{CompressionMethod, Error, ErrorKind, SeekableDecompressor, ZipReader};
+    /// use std::io::{Read, Seek, SeekFrom};
+    ///
+    /// struct Stored;
+    ///
+    /// impl<'a, R: rawzip::ReaderAt> SeekableDecompressor<ZipReader<'a, R>> for Stored {
+    ///     type Reader = ZipReader<'a, R>;
+    ///
+    ///     fn decompress(
+    ///         self,
+    ///         method: CompressionMethod,
+    ///         compressed: ZipReader<'a, R>,
+    ///     ) -> Result<Self::Reader, Error> {
+    ///         match method {
+    ///             CompressionMethod::Store => Ok(compressed),
+    ///             _ => Err(Error::from(ErrorKind::InvalidInput {
+    ///                 msg: format!("can't seek in {:?} data", method),
+    ///             })),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive.store_reader("file.txt", &b"Hello, world!"[..])?;
+    /// archive.finish()?;
+    ///
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let archive = rawzip::ZipArchive::from_seekable(output, &mut buffer)?;
+    /// let mut entries = archive.entries(&mut buffer);
+    /// let entry = entries.next_entry()?.unwrap();
+    /// let entry = archive.get_entry(entry.wayfinder())?;
+    /// let mut reader = entry.seek_in(Stored)?;
+    /// reader.seek(SeekFrom::Start(7))?;
+    /// let mut rest = String::new();
+    /// reader.read_to_string(&mut rest)?;
+    /// assert_eq!(rest, "world!");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn seek_in<D>(&self, decompressor: D) -> Result<D::Reader, Error>
+    where
+        D: SeekableDecompressor<ZipReader<'archive, R>>,
+    {
+        let method = self.entry.compression_method.as_method();
+        decompressor.decompress(method, self.reader())
+    }
+
+    /// Returns a [`ZipReader`] wrapped in a read-ahead buffer.
+    ///
+    /// Decompressors tend to issue many small reads, and each read of a
+    /// [`ZipReader`] is a positioned read against the underlying reader (ie: a
+    /// syscall for a [`FileReader`]). Buffering coalesces these reads. The
+    /// buffer capacity is bounded by the length of the compressed data so small
+    /// entries don't over-allocate.
+    ///
+    /// The returned reader implements [`std::io::BufRead`], so it can be handed
+    /// directly to decompressors that accept buffered input.
+    pub fn buffered_reader(&self, capacity: usize) -> std::io::BufReader<ZipReader<'archive, R>> {
+        let len = self.body_end_offset - self.body_offset;
+        let capacity = (capacity as u64).min(len).max(1) as usize;
+        std::io::BufReader::with_capacity(capacity, self.reader())
+    }
+
+    /// Returns a reader that decrypts an entry protected with the traditional
+    /// PKWARE encryption (ZipCrypto).
+    ///
+    /// The 12 byte encryption header is consumed and its check byte is
+    /// compared against the entry's CRC (or modification time when the entry
+    /// has a data descriptor). The returned reader yields the compressed data,
+    /// so layer a decompressor and [`ZipEntry::verifying_reader`] on top.
+    ///
+    /// ZipCrypto is weak encryption, see [`ZipCryptoReader`] for details.
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    ///
+    /// let file = std::fs::File::open("assets/zipcrypto.zip")?;
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let archive = rawzip::ZipArchive::from_file(file, &mut buffer)?;
+    /// let mut entries = archive.entries(&mut buffer);
+    /// let entry = entries.next_entry()?.unwrap();
+    /// let wayfinder = entry.wayfinder();
+    ///
+    /// let entry = archive.get_entry(wayfinder)?;
+    /// let decrypted = entry.decrypting_reader(b"password")?;
+    /// let mut contents = String::new();
+    /// entry.verifying_reader(decrypted).read_to_string(&mut contents)?;
+    /// assert_eq!(contents, "Hello, world!\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorKind::InvalidPassword`] if the check byte doesn't match,
+    /// and [`ErrorKind::InvalidInput`] if the entry isn't encrypted with
+    /// ZipCrypto.
+    pub fn decrypting_reader(
+        &self,
+        password: &[u8],
+    ) -> Result<ZipCryptoReader<ZipReader<'archive, R>>, Error> {
+        let flags = self.local_header.flags;
+        if flags & FLAG_ENCRYPTED == 0 {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "entry is not encrypted".to_string(),
+            }));
+        }
+
+        if flags & FLAG_STRONG_ENCRYPTION != 0 {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "strong encryption is not supported".to_string(),
+            }));
+        }
+
+        // The check byte is the high byte of the CRC, unless the CRC
+        // isn't known upfront, in which case Info-ZIP uses the file time
+        let check_byte = if self.entry.has_data_descriptor {
+            (self.local_header.last_mod_time >> 8) as u8
+        } else {
+            (self.entry.crc >> 24) as u8
+        };
+
+        ZipCryptoReader::new(self.reader(), password, check_byte)
+    }
+
+    /// Returns a reader that authenticates and decrypts an entry encrypted
+    /// with WinZip AES (AE-1 or AE-2).
+    ///
+    /// The key strength is read from the AES extra field of the local file
+    /// header. The returned reader yields data compressed with
+    /// [`AesExtraField::compression_method`], which is available from
+    /// [`ZipFileHeaderRecord::aes_extra_field`].
+    ///
+    /// AE-2 entries store a CRC of zero, so rather than wrapping the
+    /// decompressor in [`ZipEntry::verifying_reader`], rely on the
+    /// authentication code that the returned reader checks.
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    ///
+    /// let file = std::fs::File::open("assets/aes.zip")?;
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let archive = rawzip::ZipArchive::from_file(file, &mut buffer)?;
+    /// let mut entries = archive.entries(&mut buffer);
+    /// let entry = entries.next_entry()?.unwrap();
+    /// let aes = entry.aes_extra_field().unwrap();
+    /// assert_eq!(aes.compression_method(), rawzip::CompressionMethod::Deflate);
+    ///
+    /// let entry = archive.get_entry(entry.wayfinder())?;
+    /// let decrypted = entry.aes_decrypting_reader(b"password")?;
+    /// let mut contents = String::new();
+    /// flate2::read::DeflateDecoder::new(decrypted).read_to_string(&mut contents)?;
+    /// assert!(contents.starts_with("AES-128"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorKind::InvalidPassword`] if the password verification
+    /// value doesn't match, and [`ErrorKind::InvalidInput`] if the entry isn't
+    /// AES encrypted.
+    #[cfg(feature = "aes")]
+    pub fn aes_decrypting_reader(
+        &self,
+        password: &[u8],
+    ) -> Result<AesReader<ZipReader<'archive, R>>, Error> {
+        if self.local_header.compression_method.as_method() != CompressionMethod::Aes {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "entry is not AES encrypted".to_string(),
+            }));
+        }
+
+        let extra_field = self.local_extra_field()?;
+        let aes = AesExtraField::find(&extra_field).ok_or_else(|| {
+            Error::from(ErrorKind::InconsistentArchive {
+                msg: "missing AES extra field".to_string(),
+            })
+        })?;
+
+        AesReader::new(
+            self.reader(),
+            password,
+            aes.strength(),
+            self.entry.compressed_size,
+        )
+    }
+
+    /// Returns the CRC stored for the entry without reading its data.
+    ///
+    /// This is the CRC from the central directory, unless the entry has a data
+    /// descriptor and the central directory CRC is zero, in which case the CRC
+    /// is read from the data descriptor that trails the compressed data.
+    ///
+    /// ```rust
+    /// let file = std::fs::File::open("assets/go-with-datadesc-sig.zip")?;
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let archive = rawzip::ZipArchive::from_file(file, &mut buffer)?;
+    /// let mut entries = archive.entries(&mut buffer);
+    /// let entry = entries.next_entry()?.unwrap();
+    /// let entry = archive.get_entry(entry.wayfinder())?;
+    /// assert_eq!(entry.stored_crc()?, 0x7e3265a8);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn stored_crc(&self) -> Result<u32, Error> {
+        if self.entry.has_data_descriptor && self.entry.crc == 0 {
+            DataDescriptor::read_at(&self.archive.reader, self.body_end_offset).map(|x| x.crc)
+        } else {
+            Ok(self.entry.crc)
+        }
+    }
+
+    /// Reads the data descriptor that trails the entry's compressed data, or
+    /// `None` if the entry doesn't have one.
+    ///
+    /// The sizes in a data descriptor are 8 bytes when the local header has a
+    /// ZIP64 extra field, and otherwise 4 bytes. As some writers use 8 byte
+    /// sizes without the extra field, the form whose sizes agree with the
+    /// central directory is chosen.
+    ///
+    /// ```rust
+    /// let file = std::fs::File::open("assets/go-with-datadesc-sig.zip")?;
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let archive = rawzip::ZipArchive::from_file(file, &mut buffer)?;
+    /// let mut entries = archive.entries(&mut buffer);
+    /// let entry = entries.next_entry()?.unwrap();
+    /// let entry = archive.get_entry(entry.wayfinder())?;
+    /// let descriptor = entry.data_descriptor()?.unwrap();
+    /// assert_eq!(descriptor.crc(), 0x7e3265a8);
+    /// assert!(descriptor.had_signature());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn data_descriptor(&self) -> Result<Option<DataDescriptorRecord>, Error> {
+        if !self.entry.has_data_descriptor {
+            return Ok(None);
+        }
+
+        let extra_field = self.local_extra_field()?;
+        let local_zip64 = find_extra_field(&extra_field, 0x0001).is_some();
+
+        let mut buffer = [0u8; DataDescriptorRecord::MAX_SIZE];
+        let read = self.archive.reader.try_read_at_least_at(
+            &mut buffer,
+            DataDescriptorRecord::MAX_SIZE,
+            self.body_end_offset,
+        )?;
+        DataDescriptorRecord::parse(&buffer[..read], &self.entry, local_zip64)
+            .map(Some)
+            .map_err(|e| e.at_offset(self.body_end_offset))
+    }
+
+    /// Returns a reader that wraps a decompressor and verify the size and CRC
+    /// of the decompressed data once finished.
+    pub fn verifying_reader<D>(&self, reader: D) -> ZipVerifier<'archive, D, R>
+    where
+        D: std::io::Read,
+    {
+        ZipVerifier {
+            reader,
+            crc: 0,
+            size: 0,
+            max_size: u64::MAX,
+            strict: false,
+            archive: self.archive,
+            end_offset: self.body_end_offset,
+            wayfinder: self.entry,
+        }
+    }
+
+    /// Returns a verifying reader like [`ZipEntry::verifying_reader`] that
+    /// also verifies a stored CRC of 0.
+    ///
+    /// This is useful for entries with a data descriptor, whose CRC is
+    /// authoritative. See [`ZipVerification::valid_strict`] for the tradeoff.
+    pub fn verifying_reader_strict<D>(&self, reader: D) -> ZipVerifier<'archive, D, R>
+    where
+        D: std::io::Read,
+    {
+        ZipVerifier {
+            strict: true,
+            ..self.verifying_reader(reader)
+        }
+    }
+
+    /// Decompresses the entire entry into memory, verifying its size and CRC.
+    ///
+    /// The `decompressor` is given the entry's compression method and
+    /// compressed data, and returns a reader of the decompressed data. The
+    /// output is preallocated from the declared uncompressed size, capped at
+    /// 16 MiB, and grows beyond that only as data arrives.
+    ///
+    /// ```rust
+    /// use rawzip::{CompressionMethod, Error, ErrorKind, ZipArchive, RECOMMENDED_BUFFER_SIZE};
+    ///
+    /// let file = std::fs::File::open("assets/test.zip")?;
+    /// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    /// let archive = ZipArchive::from_file(file, &mut buffer)?;
+    /// let wayfinder = archive.entries(&mut buffer).next_entry()?.unwrap().wayfinder();
+    /// let entry = archive.get_entry(wayfinder)?;
+    /// let contents = entry.read_to_vec(|method, reader| match method {
+    ///     CompressionMethod::Deflate => Ok(flate2::read::DeflateDecoder::new(reader)),
+    ///     _ => Err(Error::from(ErrorKind::InvalidInput {
+    ///         msg: format!("unsupported compression method: {:?}", method),
+    ///     })),
+    /// })?;
+    /// assert_eq!(contents, b"This is a test text file.\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the decompressor's error, or an error if reading fails or the
+    /// size or CRC doesn't match.
+    pub fn read_to_vec<F, D>(&self, decompressor: F) -> Result<Vec<u8>, Error>
+    where
+        F: FnOnce(CompressionMethod, ZipReader<'archive, R>) -> Result<D, Error>,
+        D: std::io::Read,
+    {
+        let method = self.local_header.compression_method.as_method();
+        let decompressed = decompressor(method, self.reader())?;
+        let capacity = self.entry.uncompressed_size.min(MAX_PREALLOCATION) as usize;
+        let mut result = Vec::with_capacity(capacity);
+        self.verifying_reader(decompressed)
+            .read_to_end(&mut result)
+            .map_err(Error::from_io)?;
+        Ok(result)
+    }
+
+    /// Returns a verifying reader like [`ZipEntry::verifying_reader`] that
+    /// also fails with [`ErrorKind::LimitExceeded`] once the decompressed
+    /// output exceeds `max_bytes`.
+    ///
+    /// Unlike checks against the declared uncompressed size, the limit holds
+    /// even when the archive lies about the size of an entry, which guards
+    /// against decompression bombs.
+    pub fn verifying_reader_limited<D>(
+        &self,
+        reader: D,
+        max_bytes: u64,
+    ) -> ZipVerifier<'archive, D, R>
+    where
+        D: std::io::Read,
+    {
+        ZipVerifier {
+            max_size: max_bytes,
+            ..self.verifying_reader(reader)
+        }
+    }
+
+    /// Returns a tuple of start and end byte offsets for the compressed data
+    /// within the underlying reader.
+    ///
+    /// This method uses the information from the local file header in its
+    /// calculations.
+    ///
+    /// # Security Usage
+    ///
+    /// This method is useful for detecting overlapping entries, which are often
+    /// used in zip bombs. By comparing the ranges returned by this method
+    /// across multiple entries, you can identify when entries share compressed
+    /// data:
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, Error};
+    /// # fn example(data: &[u8]) -> Result<(), Error> {
+    /// let archive = ZipArchive::from_slice(data)?;
+    /// let mut ranges = Vec::new();
+    ///
+    /// for entry_result in archive.entries() {
+    ///     let entry = entry_result?;
     ///     let wayfinder = entry.wayfinder();
     ///     if let Ok(zip_entry) = archive.get_entry(wayfinder) {
     ///         ranges.push(zip_entry.compressed_data_range());
@@ -568,6 +1882,21 @@ impl ZipVerification {
     /// This function will return an error if the size or CRC does not match
     /// the expected values.
     pub fn valid(&self, rhs: ZipVerification) -> Result<(), Error> {
+        self.check(rhs, false)
+    }
+
+    /// Validates the size and CRC of the entry like [`ZipVerification::valid`],
+    /// but also verifies an expected CRC of 0.
+    ///
+    /// A stored CRC of 0 usually means the writer didn't compute one (eg:
+    /// WinZip AES AE-2 entries), so it is skipped by default. That default
+    /// also accepts corrupt data from writers that lazily store 0. Use strict
+    /// validation when the stored CRC is known to be authoritative.
+    pub fn valid_strict(&self, rhs: ZipVerification) -> Result<(), Error> {
+        self.check(rhs, true)
+    }
+
+    fn check(&self, rhs: ZipVerification, strict: bool) -> Result<(), Error> {
         if self.size() != rhs.size() {
             return Err(Error::from(ErrorKind::InvalidSize {
                 expected: self.size(),
@@ -575,8 +1904,8 @@ impl ZipVerification {
             }));
         }
 
-        // If the CRC is 0, then it is not verified.
-        if self.crc() != 0 && self.crc() != rhs.crc() {
+        // If the CRC is 0, then it is not verified unless strict.
+        if (strict || self.crc() != 0) && self.crc() != rhs.crc() {
             return Err(Error::from(ErrorKind::InvalidChecksum {
                 expected: self.crc(),
                 actual: rhs.crc(),
@@ -593,6 +1922,8 @@ pub struct ZipVerifier<'archive, Decompressor, ReaderAt> {
     reader: Decompressor,
     crc: u32,
     size: u64,
+    max_size: u64,
+    strict: bool,
     archive: &'archive ZipArchive<ReaderAt>,
     end_offset: u64,
     wayfinder: ZipArchiveEntryWayfinder,
@@ -614,24 +1945,28 @@ where
         let read = self.reader.read(buf)?;
         self.crc = crc32_chunk(&buf[..read], self.crc);
         self.size += read as u64;
+        check_size_limit(self.size, self.max_size)?;
 
         if read == 0 || self.size >= self.wayfinder.uncompressed_size_hint() {
             let crc = if self.wayfinder.has_data_descriptor {
                 DataDescriptor::read_at(&self.archive.reader, self.end_offset).map(|x| x.crc)
             } else {
-                Ok(self.crc)
+                Ok(self.wayfinder.crc)
             };
 
             crc.and_then(|crc| {
                 let expected = ZipVerification {
-                    crc: self.crc,
+                    crc,
                     uncompressed_size: self.wayfinder.uncompressed_size_hint(),
                 };
 
-                expected.valid(ZipVerification {
-                    crc,
-                    uncompressed_size: self.size,
-                })
+                expected.check(
+                    ZipVerification {
+                        crc: self.crc,
+                        uncompressed_size: self.size,
+                    },
+                    self.strict,
+                )
             })
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
         }
@@ -640,11 +1975,29 @@ where
     }
 }
 
+/// Creates a seekable reader of decompressed data, for use with
+/// [`ZipEntry::seek_in`].
+///
+/// `C` is the seekable reader of the compressed data handed to the
+/// decompressor.
+pub trait SeekableDecompressor<C> {
+    /// The reader of decompressed data
+    type Reader: Read + Seek;
+
+    /// Wraps the `compressed` data of an entry compressed with `method`,
+    /// returning an error if seeking isn't supported for the method.
+    fn decompress(self, method: CompressionMethod, compressed: C) -> Result<Self::Reader, Error>;
+}
+
 /// A reader for a Zip entry's compressed data.
+///
+/// Seeking is relative to the start of the compressed data, and reads past
+/// its end return EOF.
 #[derive(Debug, Clone)]
 pub struct ZipReader<'archive, R> {
     archive: &'archive ZipArchive<R>,
     entry: ZipArchiveEntryWayfinder,
+    start_offset: u64,
     offset: u64,
     end_offset: u64,
 }
@@ -675,21 +2028,79 @@ where
     }
 }
 
+impl<R> ZipReader<'_, R> {
+    /// The compressed size of the entry's data
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.end_offset - self.start_offset
+    }
+
+    /// Returns true if the entry has no compressed data
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of compressed bytes left to read
+    ///
+    /// Useful for reporting progress through a decompressor that wraps this
+    /// reader.
+    #[inline]
+    pub fn bytes_remaining(&self) -> u64 {
+        self.end_offset.saturating_sub(self.offset)
+    }
+}
+
 impl<R> Read for ZipReader<'_, R>
 where
     R: ReaderAt,
 {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let read_size = buf.len().min((self.end_offset - self.offset) as usize);
+        let remaining =
+            usize::try_from(self.end_offset.saturating_sub(self.offset)).unwrap_or(usize::MAX);
+        let read_size = buf.len().min(remaining);
         let read = self
             .archive
             .reader
             .read_at(&mut buf[..read_size], self.offset)?;
+
+        // A truncated archive must not pass for the end of the entry's data
+        if read == 0 && read_size != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "archive ended before the entry's compressed data",
+            ));
+        }
+
         self.offset += read as u64;
         Ok(read)
     }
 }
 
+impl<R> Seek for ZipReader<'_, R> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            std::io::SeekFrom::Start(n) => Some(n),
+            std::io::SeekFrom::End(n) => {
+                (self.end_offset - self.start_offset).checked_add_signed(n)
+            }
+            std::io::SeekFrom::Current(n) => {
+                (self.offset - self.start_offset).checked_add_signed(n)
+            }
+        };
+
+        let position = position.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        self.offset = self.start_offset.saturating_add(position);
+        Ok(position)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct DataDescriptor {
     crc: u32,
@@ -730,11 +2141,122 @@ impl DataDescriptor {
     }
 }
 
+/// The data descriptor that trails the compressed data of a streamed entry,
+/// as returned by [`ZipEntry::data_descriptor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataDescriptorRecord {
+    crc: u32,
+    compressed_size: u64,
+    uncompressed_size: u64,
+    had_signature: bool,
+    is_zip64: bool,
+}
+
+impl DataDescriptorRecord {
+    /// A signature, CRC, and two 8 byte sizes
+    const MAX_SIZE: usize = 24;
+
+    fn parse(
+        data: &[u8],
+        entry: &ZipArchiveEntryWayfinder,
+        local_zip64: bool,
+    ) -> Result<DataDescriptorRecord, Error> {
+        let had_signature = data.get(0..4).map(le_u32) == Some(DataDescriptor::SIGNATURE);
+        let data = if had_signature { &data[4..] } else { data };
+        if data.len() < 12 {
+            return Err(Error::from(ErrorKind::Eof));
+        }
+
+        let crc = le_u32(&data[0..4]);
+        let narrow = (
+            u64::from(le_u32(&data[4..8])),
+            u64::from(le_u32(&data[8..12])),
+        );
+        let wide = data
+            .get(4..20)
+            .map(|sizes| (le_u64(&sizes[0..8]), le_u64(&sizes[8..16])));
+        let central = (entry.compressed_size, entry.uncompressed_size);
+
+        let is_zip64 = match wide {
+            Some(_) if local_zip64 => true,
+            _ if narrow == central => false,
+            Some(wide) if wide == central => true,
+            Some(_) => central.0 >= u64::from(u32::MAX) || central.1 >= u64::from(u32::MAX),
+            None => false,
+        };
+
+        let (compressed_size, uncompressed_size) = match wide {
+            Some(wide) if is_zip64 => wide,
+            _ => narrow,
+        };
+
+        Ok(DataDescriptorRecord {
+            crc,
+            compressed_size,
+            uncompressed_size,
+            had_signature,
+            is_zip64,
+        })
+    }
+
+    /// Returns the CRC32 checksum of the uncompressed data.
+    pub fn crc(&self) -> u32 {
+        self.crc
+    }
+
+    /// Returns the size of the compressed data.
+    pub fn compressed_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    /// Returns the size of the uncompressed data.
+    pub fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// Returns true if the descriptor started with the optional signature.
+    pub fn had_signature(&self) -> bool {
+        self.had_signature
+    }
+
+    /// Returns true if the sizes were stored as 8 byte ZIP64 values.
+    pub fn is_zip64(&self) -> bool {
+        self.is_zip64
+    }
+}
+
+/// Resolves an offset relative to the start of `disk` in a split archive.
+pub(crate) fn resolve_disk_offset(starts: &[u64], disk: u32, offset: u64) -> Result<u64, Error> {
+    let Some(start) = starts.get(disk as usize) else {
+        return Err(Error::from(ErrorKind::InconsistentArchive {
+            msg: format!(
+                "disk {} is past the last of {} segments",
+                disk,
+                starts.len()
+            ),
+        }));
+    };
+
+    start.checked_add(offset).ok_or_else(|| {
+        Error::from(ErrorKind::InconsistentArchive {
+            msg: format!("offset {} on disk {} overflows", offset, disk),
+        })
+    })
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct EndOfCentralDirectory {
     pub(crate) zip64: Option<Zip64EndOfCentralDirectoryRecord>,
     pub(crate) eocd: EndOfCentralDirectoryRecordFixed,
     pub(crate) stream_pos: u64,
+
+    /// Whether probing found the central directory at a base offset of zero
+    /// despite the computed base offset. See [`ZipLocator::probe_base_offset`].
+    pub(crate) zero_base_offset: bool,
+
+    /// Offset at which each disk of a split archive starts in the reader.
+    /// Offsets recorded in the archive are relative to their disk.
+    pub(crate) disk_starts: Option<Vec<u64>>,
 }
 
 impl EndOfCentralDirectory {
@@ -742,24 +2264,51 @@ impl EndOfCentralDirectory {
     #[inline]
     fn base_offset(&self) -> u64 {
         match &self.zip64 {
+            _ if self.disk_starts.is_some() => 0,
             Some(_) => 0,
-            None => {
-                let size = u64::from(self.eocd.central_dir_size);
-                let offset = u64::from(self.eocd.central_dir_offset);
-                self.stream_pos.saturating_sub(size).saturating_sub(offset)
-
-                // In the case that the base_offset is calculated to be non-zero
-                // Go's zip reader will check if base_offset of zero would
-                // correspond to a valid directory header and if so, set it to
-                // zero anyways.
-                // https://github.com/golang/go/blob/c0e149b6b1aa2daca64c00804809bc2279e21eee/src/archive/zip/reader.go#L636
-                //
-                // Neither rc-zip or rust's zip crate can handle the file so we
-                // don't either
-                //
-                // See Go's test-badbase.zip and test-baddirsz.zip for test cases
-            }
+            None if self.zero_base_offset => 0,
+            None => self.computed_base_offset(),
+        }
+    }
+
+    /// The base offset implied by the directory size and offset fields.
+    #[inline]
+    fn computed_base_offset(&self) -> u64 {
+        let size = u64::from(self.eocd.central_dir_size);
+        let offset = u64::from(self.eocd.central_dir_offset);
+        self.stream_pos.saturating_sub(size).saturating_sub(offset)
+    }
+
+    /// In the case that the base offset is calculated to be non-zero, Go's
+    /// zip reader checks if a base offset of zero would correspond to a valid
+    /// directory header and if so, uses zero anyways.
+    /// <https://github.com/golang/go/blob/c0e149b6b1aa2daca64c00804809bc2279e21eee/src/archive/zip/reader.go#L636>
+    ///
+    /// Neither rc-zip or rust's zip crate do this, so it's opt-in.
+    pub(crate) fn probe_zero_base_offset<R>(&mut self, reader: &R) -> Result<(), Error>
+    where
+        R: ReaderAt,
+    {
+        if self.zip64.is_some() || self.computed_base_offset() == 0 {
+            return Ok(());
+        }
+
+        let offset = u64::from(self.eocd.central_dir_offset);
+        let mut buffer = [0u8; ZipFileHeaderFixed::SIZE];
+        match reader.read_exact_at(&mut buffer, offset) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(Error::io(e)),
         }
+
+        let Ok(header) = ZipFileHeaderFixed::parse(&buffer) else {
+            return Ok(());
+        };
+
+        // The whole record must fit before the end of central directory
+        let record_end = offset + (ZipFileHeaderFixed::SIZE + header.variable_length()) as u64;
+        self.zero_base_offset = record_end <= self.stream_pos;
+        Ok(())
     }
 
     /// end position of the central directory
@@ -772,26 +2321,196 @@ impl EndOfCentralDirectory {
         self.stream_pos
     }
 
-    /// offset of the start of the central directory
-    #[inline]
-    fn offset(&self) -> u64 {
-        self.zip64
-            .as_ref()
-            .map(|x| x.central_dir_offset)
-            .unwrap_or_else(|| self.base_offset() + u64::from(self.eocd.central_dir_offset))
+    /// offset of the start of the central directory
+    #[inline]
+    fn offset(&self) -> u64 {
+        let (disk, offset) = self.central_dir_location();
+        // Split archives have the disk validated by `ZipArchive::from_segments`
+        self.disk_offset(disk, offset).unwrap_or(u64::MAX)
+    }
+
+    /// The disk with the start of the central directory and the offset of
+    /// the central directory on that disk.
+    #[inline]
+    pub(crate) fn central_dir_location(&self) -> (u32, u64) {
+        match &self.zip64 {
+            Some(zip64) => (zip64.cd_disk, zip64.central_dir_offset),
+            None => (
+                u32::from(self.eocd.eocd_disk),
+                u64::from(self.eocd.central_dir_offset),
+            ),
+        }
+    }
+
+    /// Resolves an offset relative to the start of `disk` to an offset in the
+    /// reader.
+    ///
+    /// Archives that are not split ignore the disk, as many writers leave
+    /// garbage in the disk fields.
+    #[inline]
+    pub(crate) fn disk_offset(&self, disk: u32, offset: u64) -> Result<u64, Error> {
+        match &self.disk_starts {
+            Some(starts) => resolve_disk_offset(starts, disk, offset),
+            None => Ok(self.base_offset() + offset),
+        }
+    }
+
+    #[inline]
+    fn entries(&self) -> u64 {
+        self.zip64
+            .as_ref()
+            .map(|z| z.num_entries)
+            .unwrap_or(u64::from(self.eocd.num_entries))
+    }
+
+    #[inline]
+    fn comment_len(&self) -> usize {
+        self.eocd.comment_len as usize
+    }
+}
+
+/// Where the next read of a [`CentralDirectoryCursor`] goes.
+pub(crate) enum CursorStep {
+    /// At least this many bytes must be read into the buffer
+    Read(usize),
+
+    /// The buffer holds the next record
+    Ready,
+
+    /// The central directory is exhausted
+    Done,
+}
+
+/// The state of iterating a central directory through a caller provided
+/// buffer, independent of how the buffer is filled.
+///
+/// This is shared by the blocking and async entry iterators, which drive it
+/// by reading into the buffer until [`CentralDirectoryCursor::step`] reports
+/// the next record is ready.
+#[derive(Debug)]
+pub(crate) struct CentralDirectoryCursor {
+    pos: usize,
+    end: usize,
+    offset: u64,
+    central_dir_end_pos: u64,
+    remaining_entries: u64,
+
+    /// A record whose fixed header was consumed while its variable length
+    /// fields are still being read.
+    header: Option<ZipFileHeaderFixed>,
+}
+
+impl CentralDirectoryCursor {
+    pub(crate) fn new(eocd: &EndOfCentralDirectory, max_entries: u64) -> Self {
+        CentralDirectoryCursor {
+            pos: 0,
+            end: 0,
+            offset: eocd.offset(),
+            central_dir_end_pos: eocd.end_position(),
+            remaining_entries: max_entries,
+            header: None,
+        }
+    }
+
+    /// Advances through the buffered data, reporting what is needed before
+    /// the next record can be taken.
+    pub(crate) fn step(&mut self, buffer: &[u8]) -> Result<CursorStep, Error> {
+        let header = match &self.header {
+            Some(header) => header,
+            None if is_digital_signature(&buffer[self.pos..self.end]) => {
+                return Ok(CursorStep::Done)
+            }
+            None if self.pos + ZipFileHeaderFixed::SIZE < self.end => {
+                self.remaining_entries = take_entry(self.remaining_entries)?;
+                let record_offset = self.position();
+                let data = &buffer[self.pos..self.end];
+                let header =
+                    ZipFileHeaderFixed::parse(data).map_err(|e| e.at_offset(record_offset))?;
+                self.pos += ZipFileHeaderFixed::SIZE;
+                self.header.insert(header)
+            }
+            None if self.offset >= self.central_dir_end_pos => return Ok(CursorStep::Done),
+            None => return Ok(CursorStep::Read(ZipFileHeaderFixed::SIZE)),
+        };
+
+        let variable_length = header.variable_length();
+        let buffered = self.end - self.pos;
+        if variable_length > buffered {
+            return Ok(CursorStep::Read(variable_length - buffered));
+        }
+
+        Ok(CursorStep::Ready)
     }
 
-    #[inline]
-    fn entries(&self) -> u64 {
-        self.zip64
-            .as_ref()
-            .map(|z| z.num_entries)
-            .unwrap_or(u64::from(self.eocd.num_entries))
+    /// Moves the unparsed data to the front of the buffer, returning the
+    /// rest of the buffer to read the central directory into and the offset
+    /// to read from. Report how much was read with
+    /// [`CentralDirectoryCursor::filled`].
+    pub(crate) fn fill_target<'a>(&mut self, buffer: &'a mut [u8]) -> (&'a mut [u8], u64) {
+        let remaining = self.end - self.pos;
+        buffer.copy_within(self.pos..self.end, 0);
+        self.pos = 0;
+        self.end = remaining;
+        let max_read =
+            ((self.central_dir_end_pos - self.offset) as usize).min(buffer.len() - remaining);
+        (&mut buffer[remaining..][..max_read], self.offset)
     }
 
-    #[inline]
-    fn comment_len(&self) -> usize {
-        self.eocd.comment_len as usize
+    /// Records that `read` bytes were read into the buffer returned by
+    /// [`CentralDirectoryCursor::fill_target`].
+    pub(crate) fn filled(&mut self, read: usize) {
+        self.offset += read as u64;
+        self.end += read;
+    }
+
+    /// Takes the record that [`CentralDirectoryCursor::step`] reported as
+    /// ready.
+    pub(crate) fn take_record<'a>(
+        &mut self,
+        buffer: &'a [u8],
+        eocd: &EndOfCentralDirectory,
+    ) -> Result<ZipFileHeaderRecord<'a>, Error> {
+        let header = self.header.take().expect("record is ready");
+        let data = &buffer[self.pos..self.end];
+        let variable_length = header.variable_length();
+        let (file_name, extra_field, file_comment, _) = header
+            .parse_variable_length(data)
+            .expect("variable length precheck failed");
+        let mut record =
+            ZipFileHeaderRecord::from_parts(header, file_name, extra_field, file_comment);
+        self.pos += variable_length;
+        record.local_header_offset =
+            eocd.disk_offset(record.disk_number_start, record.local_header_offset)?;
+        Ok(record)
+    }
+
+    /// The offset of the central directory record that is read next.
+    pub(crate) fn position(&self) -> u64 {
+        let pending = self.header.as_ref().map_or(0, |_| ZipFileHeaderFixed::SIZE);
+        self.offset - (self.end - self.pos + pending) as u64
+    }
+
+    /// Repositions the cursor to the central directory record at `offset`.
+    pub(crate) fn seek_to_offset(
+        &mut self,
+        offset: u64,
+        eocd: &EndOfCentralDirectory,
+    ) -> Result<(), Error> {
+        let start = eocd.offset();
+        if offset < start || offset > self.central_dir_end_pos {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: format!(
+                    "offset {} is outside of the central directory ({}..{})",
+                    offset, start, self.central_dir_end_pos
+                ),
+            }));
+        }
+
+        self.offset = offset;
+        self.pos = 0;
+        self.end = 0;
+        self.header = None;
+        Ok(())
     }
 }
 
@@ -800,11 +2519,7 @@ impl EndOfCentralDirectory {
 pub struct ZipEntries<'archive, 'buf, R> {
     buffer: &'buf mut [u8],
     archive: &'archive ZipArchive<R>,
-    pos: usize,
-    end: usize,
-    offset: u64,
-    base_offset: u64,
-    central_dir_end_pos: u64,
+    cursor: CentralDirectoryCursor,
 }
 
 impl<R> ZipEntries<'_, '_, R>
@@ -816,59 +2531,118 @@ where
     /// This method reads from the underlying archive reader into the provided
     /// buffer to parse entry headers.
     #[inline]
-    pub fn next_entry(&mut self) -> Result<Option<ZipFileHeaderRecord>, Error> {
-        if self.pos + ZipFileHeaderFixed::SIZE >= self.end {
-            if self.offset >= self.central_dir_end_pos {
-                return Ok(None);
+    pub fn next_entry(&mut self) -> Result<Option<ZipFileHeaderRecord<'_>>, Error> {
+        loop {
+            match self.cursor.step(self.buffer)? {
+                CursorStep::Read(size) => {
+                    let (buffer, offset) = self.cursor.fill_target(self.buffer);
+                    let read = self.archive.reader.read_at_least_at(buffer, size, offset)?;
+                    self.cursor.filled(read);
+                }
+                CursorStep::Ready => break,
+                CursorStep::Done => return Ok(None),
             }
-
-            let remaining = self.end - self.pos;
-            self.buffer.copy_within(self.pos..self.end, 0);
-            let max_read = ((self.central_dir_end_pos - self.offset) as usize)
-                .min(self.buffer.len() - remaining);
-            let read = self.archive.reader.read_at_least_at(
-                &mut self.buffer[remaining..][..max_read],
-                ZipFileHeaderFixed::SIZE,
-                self.offset,
-            )?;
-            self.offset += read as u64;
-            self.pos = 0;
-            self.end = remaining + read;
         }
 
-        let data = &self.buffer[self.pos..self.end];
-        let file_header = ZipFileHeaderFixed::parse(data)?;
-        self.pos += ZipFileHeaderFixed::SIZE;
+        let record = self.cursor.take_record(self.buffer, &self.archive.eocd)?;
+        Ok(Some(record))
+    }
 
-        let variable_length = file_header.variable_length();
-        if self.pos + variable_length > self.end {
-            // Need to read more data
-            let remaining = self.end - self.pos;
-            self.buffer.copy_within(self.pos..self.end, 0);
-            let max_read = ((self.central_dir_end_pos - self.offset) as usize)
-                .min(self.buffer.len() - remaining);
-            let read = self.archive.reader.read_at_least_at(
-                &mut self.buffer[remaining..][..max_read],
-                variable_length - remaining,
-                self.offset,
-            )?;
-            self.offset += read as u64;
-            self.pos = 0;
-            self.end = remaining + read;
-        }
-
-        let data = &self.buffer[self.pos..self.end];
-        let (file_name, extra_field, file_comment, _) = file_header
-            .parse_variable_length(data)
-            .expect("variable length precheck failed");
-        let mut file_header =
-            ZipFileHeaderRecord::from_parts(file_header, file_name, extra_field, file_comment);
-        file_header.local_header_offset += self.base_offset;
-        self.pos += variable_length;
-        Ok(Some(file_header))
+    /// Returns the offset of the central directory record that
+    /// [`ZipEntries::next_entry`] will read next.
+    ///
+    /// Iteration can be resumed from this offset with
+    /// [`ZipEntries::seek_to_offset`], even by another iterator.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.cursor.position()
+    }
+
+    /// Repositions the iterator to the central directory record at `offset`,
+    /// as previously returned by [`ZipEntries::position`].
+    ///
+    /// This allows checkpointing iteration over huge archives, like for
+    /// paginating entries, and resuming without reading the central directory
+    /// from the start. Entries yielded after seeking still count towards the
+    /// limit set by [`ZipLocator::max_entries`].
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, RECOMMENDED_BUFFER_SIZE};
+    /// let file = std::fs::File::open("assets/test.zip")?;
+    /// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    /// let archive = ZipArchive::from_file(file, &mut buffer)?;
+    ///
+    /// let mut entries = archive.entries(&mut buffer);
+    /// entries.next_entry()?;
+    /// let checkpoint = entries.position();
+    ///
+    /// let mut entries = archive.entries(&mut buffer);
+    /// entries.seek_to_offset(checkpoint)?;
+    /// let entry = entries.next_entry()?.unwrap();
+    /// assert_eq!(entry.file_path().as_ref(), b"gophercolor16x16.png");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if `offset` lies outside
+    /// of the central directory. An offset inside of the directory that isn't
+    /// the start of a record causes the next entry to fail to parse.
+    pub fn seek_to_offset(&mut self, offset: u64) -> Result<(), Error> {
+        self.cursor.seek_to_offset(offset, &self.archive.eocd)
+    }
+
+    /// Drains the remaining entries into owned pairs of normalized path and
+    /// wayfinder, suitable for storing as a manifest of the archive.
+    ///
+    /// ```rust
+    /// # use rawzip::{ZipArchive, Error, RECOMMENDED_BUFFER_SIZE};
+    /// # use std::fs::File;
+    /// fn example(file: File) -> Result<(), Error> {
+    ///     let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    ///     let archive = ZipArchive::from_file(file, &mut buffer)?;
+    ///     for (path, wayfinder) in archive.entries(&mut buffer).manifest()? {
+    ///         println!("{}: {} bytes", path.as_ref(), wayfinder.uncompressed_size_hint());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry is malformed or its name is not valid
+    /// UTF-8.
+    pub fn manifest(
+        mut self,
+    ) -> Result<Vec<(ZipFilePath<NormalizedPathBuf>, ZipArchiveEntryWayfinder)>, Error> {
+        let mut result = Vec::new();
+        while let Some(entry) = self.next_entry()? {
+            let path = entry.file_path().try_normalize()?.into_owned();
+            result.push((path, entry.wayfinder()));
+        }
+        Ok(result)
     }
 }
 
+/// Whether the data starts with the digital signature record (4.3.13), which
+/// may follow the last central directory header and ends the entries.
+#[inline]
+fn is_digital_signature(data: &[u8]) -> bool {
+    data.len() >= 4 && le_u32(data) == DIGITAL_SIGNATURE_SIGNATURE
+}
+
+/// Decrements the number of central directory records an iterator may still
+/// yield, erroring if the limit configured by [`ZipLocator::max_entries`] has
+/// been reached.
+#[inline]
+pub(crate) fn take_entry(remaining_entries: u64) -> Result<u64, Error> {
+    remaining_entries.checked_sub(1).ok_or_else(|| {
+        Error::from(ErrorKind::InvalidInput {
+            msg: "exceeded maximum number of entries".to_string(),
+        })
+    })
+}
+
 /// 4.4.2
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct VersionMadeBy(u16);
@@ -961,6 +2735,11 @@ impl Zip64EndOfCentralDirectoryRecord {
 
 /// A numeric identifier for a compression method used in a Zip archive.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct CompressionMethodId(u16);
 
 impl CompressionMethodId {
@@ -1001,6 +2780,27 @@ impl CompressionMethodId {
     }
 }
 
+/// The scheme used to encrypt an individual Zip archive entry
+///
+/// See [`ZipFileHeaderRecord::encryption`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionMethod {
+    /// Traditional PKWARE encryption, decrypted with
+    /// [`ZipEntry::decrypting_reader`]
+    ZipCrypto,
+
+    /// WinZip AES encryption of the given key strength, decrypted with
+    /// `ZipEntry::aes_decrypting_reader` behind the `aes` feature
+    Aes(AesStrength),
+
+    /// PKWARE's strong encryption, which is not supported
+    StrongEncryption,
+
+    /// Marked as WinZip AES encrypted (compression method 99), but without a
+    /// valid AES extra field to describe the encryption
+    Unknown,
+}
+
 /// The compression method used on an individual Zip archive entry
 ///
 /// Documented in the spec under: 4.4.5
@@ -1016,6 +2816,9 @@ pub enum CompressionMethod {
     Imploded = 6,
     Tokenizing = 7,
     Deflate = 8,
+    /// Deflate with a 64 KiB window, as produced by Windows' built-in
+    /// compressor for large files. Decompress with `Deflate64Decoder` behind
+    /// the `deflate64` feature.
     Deflate64 = 9,
     Terse = 10,
     Bzip2 = 12,
@@ -1071,11 +2874,130 @@ impl From<u16> for CompressionMethod {
     }
 }
 
+impl CompressionMethod {
+    /// Every compression method with a name, in order of their ids.
+    const NAMED: [CompressionMethod; 22] = [
+        CompressionMethod::Store,
+        CompressionMethod::Shrunk,
+        CompressionMethod::Reduce1,
+        CompressionMethod::Reduce2,
+        CompressionMethod::Reduce3,
+        CompressionMethod::Reduce4,
+        CompressionMethod::Imploded,
+        CompressionMethod::Tokenizing,
+        CompressionMethod::Deflate,
+        CompressionMethod::Deflate64,
+        CompressionMethod::Terse,
+        CompressionMethod::Bzip2,
+        CompressionMethod::Lzma,
+        CompressionMethod::Lz77,
+        CompressionMethod::ZstdDeprecated,
+        CompressionMethod::Zstd,
+        CompressionMethod::Mp3,
+        CompressionMethod::Xz,
+        CompressionMethod::Jpeg,
+        CompressionMethod::WavPack,
+        CompressionMethod::Ppmd,
+        CompressionMethod::Aes,
+    ];
+
+    fn name(&self) -> Option<&'static str> {
+        let name = match self {
+            CompressionMethod::Store => "store",
+            CompressionMethod::Shrunk => "shrunk",
+            CompressionMethod::Reduce1 => "reduce1",
+            CompressionMethod::Reduce2 => "reduce2",
+            CompressionMethod::Reduce3 => "reduce3",
+            CompressionMethod::Reduce4 => "reduce4",
+            CompressionMethod::Imploded => "imploded",
+            CompressionMethod::Tokenizing => "tokenizing",
+            CompressionMethod::Deflate => "deflate",
+            CompressionMethod::Deflate64 => "deflate64",
+            CompressionMethod::Terse => "terse",
+            CompressionMethod::Bzip2 => "bzip2",
+            CompressionMethod::Lzma => "lzma",
+            CompressionMethod::Lz77 => "lz77",
+            CompressionMethod::ZstdDeprecated => "zstd-deprecated",
+            CompressionMethod::Zstd => "zstd",
+            CompressionMethod::Mp3 => "mp3",
+            CompressionMethod::Xz => "xz",
+            CompressionMethod::Jpeg => "jpeg",
+            CompressionMethod::WavPack => "wavpack",
+            CompressionMethod::Ppmd => "ppmd",
+            CompressionMethod::Aes => "aes",
+            CompressionMethod::Unknown(_) => return None,
+        };
+        Some(name)
+    }
+}
+
+/// Displays the lowercase name of the method (eg: `deflate`), or
+/// `unknown(id)` for unrecognized methods.
+///
+/// ```rust
+/// use rawzip::CompressionMethod;
+/// assert_eq!(CompressionMethod::Deflate.to_string(), "deflate");
+/// assert_eq!(CompressionMethod::Unknown(42).to_string(), "unknown(42)");
+/// ```
+impl std::fmt::Display for CompressionMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "unknown({})", self.as_id().as_u16()),
+        }
+    }
+}
+
+/// Parses the name of a compression method case-insensitively, accepting the
+/// same names produced by its [`Display`](std::fmt::Display) implementation.
+///
+/// ```rust
+/// use rawzip::CompressionMethod;
+/// assert_eq!("Deflate".parse::<CompressionMethod>()?, CompressionMethod::Deflate);
+/// assert!("gzip".parse::<CompressionMethod>().is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+impl std::str::FromStr for CompressionMethod {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let named = CompressionMethod::NAMED.into_iter().find(|method| {
+            method
+                .name()
+                .is_some_and(|name| name.eq_ignore_ascii_case(s))
+        });
+        if let Some(method) = named {
+            return Ok(method);
+        }
+
+        let id = s
+            .get(..8)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("unknown("))
+            .and_then(|_| s[8..].strip_suffix(')'))
+            .and_then(|id| id.parse::<u16>().ok());
+        match id {
+            Some(id) => Ok(CompressionMethod::from(id)),
+            None => Err(Error::from(ErrorKind::InvalidInput {
+                msg: format!("unknown compression method: {}", s),
+            })),
+        }
+    }
+}
+
 /// A borrowed data from a Zip archive, typically for comments or non-path text.
 ///
 /// Zip archives may contain text that is not strictly UTF-8. This type
 /// represents such text as a byte slice.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+///
+/// It displays as lossily decoded UTF-8, while the debug representation is
+/// quoted text with invalid UTF-8 bytes escaped as `\xNN`.
+///
+/// ```rust
+/// let text = rawzip::ZipStr::new(b"caf\xe9\n");
+/// assert_eq!(text.to_string(), "caf\u{FFFD}\n");
+/// assert_eq!(format!("{:?}", text), r#""caf\xe9\n""#);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ZipStr<'a>(&'a [u8]);
 
 impl<'a> ZipStr<'a> {
@@ -1097,12 +3019,65 @@ impl<'a> ZipStr<'a> {
     pub fn into_owned(&self) -> ZipString {
         ZipString::new(self.0.to_vec())
     }
+
+    /// Decodes the data as UTF-8, replacing invalid sequences with `U+FFFD`.
+    ///
+    /// ```rust
+    /// let text = rawzip::ZipStr::new(b"caf\xe9");
+    /// assert_eq!(text.to_str_lossy(), "caf\u{FFFD}");
+    /// ```
+    #[inline]
+    pub fn to_str_lossy(&self) -> std::borrow::Cow<'a, str> {
+        String::from_utf8_lossy(self.0)
+    }
+
+    /// Decodes the data as UTF-8, returning an error if it is not valid UTF-8.
+    #[inline]
+    pub fn to_utf8(&self) -> Result<&'a str, Error> {
+        std::str::from_utf8(self.0).map_err(Error::utf8)
+    }
+}
+
+impl std::fmt::Display for ZipStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&self.to_str_lossy())
+    }
+}
+
+impl std::fmt::Debug for ZipStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let write_escaped = |f: &mut std::fmt::Formatter<'_>, text: &str| {
+            text.chars()
+                .try_for_each(|c| write!(f, "{}", c.escape_debug()))
+        };
+
+        f.write_str("\"")?;
+        let mut rest = self.0;
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(text) => {
+                    write_escaped(f, text)?;
+                    break;
+                }
+                Err(e) => {
+                    let (valid, invalid) = rest.split_at(e.valid_up_to());
+                    write_escaped(f, std::str::from_utf8(valid).map_err(|_| std::fmt::Error)?)?;
+                    let invalid_len = e.error_len().unwrap_or(invalid.len());
+                    for byte in &invalid[..invalid_len] {
+                        write!(f, "\\x{:02x}", byte)?;
+                    }
+                    rest = &invalid[invalid_len..];
+                }
+            }
+        }
+        f.write_str("\"")
+    }
 }
 
 /// An owned string (`Vec<u8>`) from a Zip archive, typically for comments or non-path text.
 ///
-/// Similar to `ZipStr`, but owns its data.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// Similar to `ZipStr`, but owns its data. It is formatted like a `ZipStr`.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ZipString(Vec<u8>);
 
 impl ZipString {
@@ -1114,11 +3089,23 @@ impl ZipString {
 
     /// Returns a borrowed `ZipStr` view of this `ZipString`.
     #[inline]
-    pub fn as_str(&self) -> ZipStr {
+    pub fn as_str(&self) -> ZipStr<'_> {
         ZipStr::new(self.0.as_slice())
     }
 }
 
+impl std::fmt::Display for ZipString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.as_str(), f)
+    }
+}
+
+impl std::fmt::Debug for ZipString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.as_str(), f)
+    }
+}
+
 /// Represents a record from the Zip archive's central directory for a single
 /// file
 ///
@@ -1142,10 +3129,10 @@ pub struct ZipFileHeaderRecord<'a> {
     file_name_len: u16,
     extra_field_len: u16,
     file_comment_len: u16,
-    disk_number_start: u32,
+    pub(crate) disk_number_start: u32,
     internal_file_attrs: u16,
     external_file_attrs: u32,
-    local_header_offset: u64,
+    pub(crate) local_header_offset: u64,
     file_name: ZipFilePath<RawPath<'a>>,
     extra_field: &'a [u8],
     file_comment: ZipStr<'a>,
@@ -1154,7 +3141,7 @@ pub struct ZipFileHeaderRecord<'a> {
 
 impl<'a> ZipFileHeaderRecord<'a> {
     #[inline]
-    fn from_parts(
+    pub(crate) fn from_parts(
         header: ZipFileHeaderFixed,
         file_name: &'a [u8],
         extra_field: &'a [u8],
@@ -1194,15 +3181,10 @@ impl<'a> ZipFileHeaderRecord<'a> {
 
         let mut extra_fields = extra_field;
 
-        loop {
-            let Some(kind) = extra_fields.get(0..2).map(le_u16) else {
-                break;
-            };
-
-            let Some(size) = extra_fields.get(2..4).map(le_u16) else {
-                break;
-            };
-
+        while let (Some(kind), Some(size)) = (
+            extra_fields.get(0..2).map(le_u16),
+            extra_fields.get(2..4).map(le_u16),
+        ) {
             extra_fields = &extra_fields[4..];
             let end_pos = (size as usize).min(extra_fields.len());
             let (mut field, rest) = extra_fields.split_at(end_pos);
@@ -1272,6 +3254,68 @@ impl<'a> ZipFileHeaderRecord<'a> {
         self.flags & 0x08 != 0
     }
 
+    /// Returns true if the entry's sizes or offset were read from a ZIP64
+    /// extra field because they overflowed the fixed header.
+    #[inline]
+    pub fn is_zip64(&self) -> bool {
+        self.is_zip64
+    }
+
+    /// Returns true if the entry is marked as text rather than binary data.
+    ///
+    /// This is bit 0 of the internal file attributes, which some tools use
+    /// to decide whether to convert line endings. It is only a hint.
+    #[inline]
+    pub fn is_text(&self) -> bool {
+        self.internal_file_attrs & 1 != 0
+    }
+
+    /// Returns true if the entry's data is encrypted.
+    #[inline]
+    pub fn is_encrypted(&self) -> bool {
+        self.flags & FLAG_ENCRYPTED != 0
+    }
+
+    /// Returns the scheme the entry's data is encrypted with, or `None` if
+    /// the entry isn't encrypted.
+    ///
+    /// Lets callers pick the matching decryptor, or prompt for a password
+    /// only when one can be used.
+    ///
+    /// ```rust
+    /// use rawzip::{AesStrength, EncryptionMethod, ZipArchive};
+    ///
+    /// let data = std::fs::read("assets/aes.zip")?;
+    /// let archive = ZipArchive::from_slice(&data)?;
+    /// let entry = archive.entries().next_entry()?.unwrap();
+    /// assert_eq!(entry.encryption(), Some(EncryptionMethod::Aes(AesStrength::Aes128)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn encryption(&self) -> Option<EncryptionMethod> {
+        if !self.is_encrypted() {
+            None
+        } else if self.flags & FLAG_STRONG_ENCRYPTION != 0 {
+            Some(EncryptionMethod::StrongEncryption)
+        } else if self.compression_method.as_method() == CompressionMethod::Aes {
+            match self.aes_extra_field() {
+                Some(aes) => Some(EncryptionMethod::Aes(aes.strength())),
+                None => Some(EncryptionMethod::Unknown),
+            }
+        } else {
+            Some(EncryptionMethod::ZipCrypto)
+        }
+    }
+
+    /// Returns the WinZip AES extra field of an AES encrypted entry.
+    ///
+    /// The field describes the key strength and the compression method that
+    /// was applied before encryption.
+    #[inline]
+    pub fn aes_extra_field(&self) -> Option<AesExtraField> {
+        AesExtraField::find(self.extra_field)
+    }
+
     /// Describes where the file's data is located within the archive.
     #[inline]
     pub fn wayfinder(&self) -> ZipArchiveEntryWayfinder {
@@ -1281,6 +3325,7 @@ impl<'a> ZipFileHeaderRecord<'a> {
             local_header_offset: self.local_header_offset,
             has_data_descriptor: self.has_data_descriptor(),
             crc: self.crc32,
+            compression_method: self.compression_method,
         }
     }
 
@@ -1358,6 +3403,80 @@ impl<'a> ZipFileHeaderRecord<'a> {
         extract_best_timestamp(self.extra_field, self.last_mod_time, self.last_mod_date)
     }
 
+    /// Returns the last modification date and time as UTC.
+    ///
+    /// Timestamps from the MS-DOS fields, whose time zone is unknown, are
+    /// taken as UTC, as most extraction tools do. Use
+    /// [`ZipFileHeaderRecord::last_modified`] to tell the two apart.
+    ///
+    /// ```rust
+    /// let data = std::fs::read("assets/time-win7.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// let entry = archive.entries().next_entry()?.unwrap();
+    /// assert!(entry.last_modified().is_ambiguous());
+    /// assert_eq!(entry.last_modified_utc().to_string(), "2017-10-31T21:11:58Z");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn last_modified_utc(&self) -> UtcDateTime {
+        match self.last_modified() {
+            ZipDateTimeKind::Utc(dt) => dt,
+            ZipDateTimeKind::Local(dt) => dt.assume_utc(),
+        }
+    }
+
+    /// Returns which field [`ZipFileHeaderRecord::last_modified`] was read
+    /// from.
+    ///
+    /// Only the MS-DOS fields lack a time zone, so timestamps from any other
+    /// source can be trusted as UTC.
+    ///
+    /// ```rust
+    /// use rawzip::time::TimestampSource;
+    ///
+    /// let data = std::fs::read("assets/time-7zip.zip")?;
+    /// let archive = rawzip::ZipArchive::from_slice(&data)?;
+    /// let entry = archive.entries().next_entry()?.unwrap();
+    /// assert_eq!(entry.timestamp_source(), TimestampSource::Ntfs);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn timestamp_source(&self) -> TimestampSource {
+        let (_, source) = extract_best_timestamp_with_source(
+            self.extra_field,
+            self.last_mod_time,
+            self.last_mod_date,
+        );
+        source
+    }
+
+    /// Returns the timestamps from the Extended Timestamp extra field, if
+    /// present.
+    ///
+    /// These are read from the central directory record, where writers
+    /// conventionally only store the modification time. The access and
+    /// creation times are usually only available in the local file header.
+    #[inline]
+    pub fn extended_timestamps(&self) -> Option<ExtendedTimestamps> {
+        find_extended_timestamps(self.extra_field)
+    }
+
+    /// Returns the Unix user and group IDs of the entry's owner, if recorded.
+    ///
+    /// The IDs are read from Info-ZIP's Unix extra field (0x7875), falling
+    /// back to the trailing IDs of the original Unix extra field (0x5855).
+    /// Writers often only store the IDs in the local file header, so this
+    /// returns `None` for many archives.
+    #[inline]
+    pub fn unix_uid_gid(&self) -> Option<(u32, u32)> {
+        find_extra_field(self.extra_field, UNIX_OWNER_ID)
+            .and_then(parse_unix_owner)
+            .or_else(|| {
+                find_extra_field(self.extra_field, UNIX_TIMESTAMP_OWNER_ID)
+                    .and_then(parse_unix_timestamp_owner)
+            })
+    }
+
     /// Returns the file mode information extracted from the external file attributes.
     #[inline]
     pub fn mode(&self) -> EntryMode {
@@ -1384,13 +3503,20 @@ impl<'a> ZipFileHeaderRecord<'a> {
 }
 
 /// Contains directions to where the Zip entry's data is located within the Zip archive.
+///
+/// With the `serde` feature, wayfinders can be persisted (eg: as an index of
+/// an archive's entries) and later passed to [`ZipArchive::get_entry`]
+/// without walking the central directory again. The local header is still
+/// validated when the entry is retrieved.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ZipArchiveEntryWayfinder {
     uncompressed_size: u64,
     compressed_size: u64,
-    local_header_offset: u64,
+    pub(crate) local_header_offset: u64,
     crc: u32,
     has_data_descriptor: bool,
+    compression_method: CompressionMethodId,
 }
 
 impl ZipArchiveEntryWayfinder {
@@ -1411,6 +3537,18 @@ impl ZipArchiveEntryWayfinder {
     pub fn compressed_size_hint(&self) -> u64 {
         self.compressed_size
     }
+
+    /// Equivalent to [`ZipFileHeaderRecord::local_header_offset`]
+    #[inline]
+    pub fn local_header_offset(&self) -> u64 {
+        self.local_header_offset
+    }
+
+    /// Equivalent to [`ZipFileHeaderRecord::compression_method`]
+    #[inline]
+    pub fn compression_method(&self) -> CompressionMethod {
+        self.compression_method.as_method()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1429,7 +3567,7 @@ pub(crate) struct ZipLocalFileHeaderFixed {
 }
 
 impl ZipLocalFileHeaderFixed {
-    const SIZE: usize = 30;
+    pub(crate) const SIZE: usize = 30;
     pub const SIGNATURE: u32 = 0x04034b50;
 
     pub fn parse(data: &[u8]) -> Result<ZipLocalFileHeaderFixed, Error> {
@@ -1485,7 +3623,7 @@ impl ZipLocalFileHeaderFixed {
 }
 
 #[derive(Debug, Clone)]
-struct ZipFileHeaderFixed {
+pub(crate) struct ZipFileHeaderFixed {
     pub signature: u32,
     pub version_made_by: u16,
     pub version_needed: u16,
@@ -1519,7 +3657,7 @@ type VariableFields<'a> = (
 );
 
 impl ZipFileHeaderFixed {
-    const SIZE: usize = 46;
+    pub(crate) const SIZE: usize = 46;
 
     #[inline]
     pub fn parse(data: &[u8]) -> Result<ZipFileHeaderFixed, Error> {
@@ -1685,4 +3823,153 @@ mod tests {
         assert_eq!(slice_range1, reader_range1);
         assert_eq!(slice_range2, reader_range2);
     }
+
+    #[test]
+    fn test_slice_entry_as_reader() {
+        let test_zip = std::fs::read("assets/test.zip").unwrap();
+        let archive = ZipArchive::from_slice(&test_zip).unwrap();
+        for entry in archive.entries() {
+            let entry = archive.get_entry(entry.unwrap().wayfinder()).unwrap();
+            let mut actual = Vec::new();
+            entry.as_reader().read_to_end(&mut actual).unwrap();
+            assert_eq!(actual, entry.data());
+        }
+    }
+
+    #[test]
+    fn test_comment_decoding() {
+        let test_zip = std::fs::read("assets/test.zip").unwrap();
+        let archive = ZipArchive::from_slice(&test_zip).unwrap();
+        let comment = archive.comment();
+        assert_eq!(comment.to_utf8().unwrap(), "This is a zipfile comment.");
+        assert_eq!(comment.to_str_lossy(), "This is a zipfile comment.");
+
+        let invalid = ZipStr::new(b"bad \xff\xfe bytes");
+        assert!(matches!(
+            invalid.to_utf8().unwrap_err().kind(),
+            ErrorKind::InvalidUtf8(_)
+        ));
+        assert_eq!(invalid.to_str_lossy(), "bad \u{FFFD}\u{FFFD} bytes");
+    }
+
+    #[derive(Debug)]
+    struct CountingReader {
+        data: Vec<u8>,
+        reads: std::cell::Cell<usize>,
+    }
+
+    impl ReaderAt for CountingReader {
+        fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+            self.reads.set(self.reads.get() + 1);
+            self.data.as_slice().read_at(buf, offset)
+        }
+    }
+
+    #[test]
+    fn test_buffered_reader_read_count() {
+        let data = std::fs::read("assets/test.zip").unwrap();
+        let reader = CountingReader {
+            data,
+            reads: std::cell::Cell::new(0),
+        };
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let end_offset = reader.data.len() as u64;
+        let archive = ZipLocator::new()
+            .locate_in_reader(reader, &mut buffer, end_offset)
+            .map_err(|(_, e)| e)
+            .unwrap();
+
+        let mut entries = archive.entries(&mut buffer);
+        let wayfinder = entries.next_entry().unwrap().unwrap().wayfinder();
+        let entry = archive.get_entry(wayfinder).unwrap();
+
+        let read_all = |reader: &mut dyn Read| {
+            let mut out = Vec::new();
+            let mut byte = [0u8; 1];
+            while reader.read(&mut byte).unwrap() != 0 {
+                out.push(byte[0]);
+            }
+            out
+        };
+
+        let start = archive.get_ref().reads.get();
+        let unbuffered = read_all(&mut entry.reader());
+        let unbuffered_reads = archive.get_ref().reads.get() - start;
+
+        let start = archive.get_ref().reads.get();
+        let buffered = read_all(&mut entry.buffered_reader(RECOMMENDED_BUFFER_SIZE));
+        let buffered_reads = archive.get_ref().reads.get() - start;
+
+        assert_eq!(buffered, unbuffered);
+        assert_eq!(unbuffered_reads, unbuffered.len() + 1);
+        assert_eq!(buffered_reads, 2);
+    }
+
+    #[test]
+    fn test_compression_method_display_roundtrip() {
+        let methods = CompressionMethod::NAMED
+            .into_iter()
+            .chain([CompressionMethod::Unknown(42)]);
+        for method in methods {
+            let name = method.to_string();
+            assert_eq!(name.parse::<CompressionMethod>().unwrap(), method);
+            assert_eq!(
+                name.to_uppercase().parse::<CompressionMethod>().unwrap(),
+                method
+            );
+        }
+
+        assert_eq!(CompressionMethod::Bzip2.to_string(), "bzip2");
+        assert_eq!(CompressionMethod::Unknown(42).to_string(), "unknown(42)");
+        for name in ["", "gzip", "deflate ", "unknown()", "unknown(70000)"] {
+            let err = name.parse::<CompressionMethod>().unwrap_err();
+            assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+        }
+    }
+
+    #[test]
+    fn test_zip_str_debug_escapes() {
+        let cases: [(&[u8], &str); 6] = [
+            (b"", r#""""#),
+            (b"plain text", r#""plain text""#),
+            (b"tab\tquote\"", r#""tab\tquote\"""#),
+            ("caf\u{e9}".as_bytes(), "\"caf\u{e9}\""),
+            (b"\xff\xfeabc", r#""\xff\xfeabc""#),
+            (b"abc\xe2\x82", r#""abc\xe2\x82""#),
+        ];
+
+        for (data, expected) in cases {
+            assert_eq!(format!("{:?}", ZipStr::new(data)), expected);
+            assert_eq!(format!("{:?}", ZipString::new(data.to_vec())), expected);
+            assert_eq!(
+                ZipString::new(data.to_vec()).to_string(),
+                String::from_utf8_lossy(data)
+            );
+        }
+
+        assert_eq!(format!("{:>5}", ZipStr::new(b"ab")), "   ab");
+    }
+
+    #[test]
+    fn test_first_overlapping_range() {
+        assert_eq!(first_overlapping_range(Vec::new()), None);
+        assert_eq!(
+            first_overlapping_range(vec![(10, 20, 1), (0, 10, 0), (20, 30, 2)]),
+            None
+        );
+
+        // A short range nested in a long one still overlaps later ranges
+        assert_eq!(
+            first_overlapping_range(vec![(0, 100, 0), (10, 20, 1), (50, 60, 2)]),
+            Some((0, 1))
+        );
+        assert_eq!(
+            first_overlapping_range(vec![(40, 60, 2), (0, 50, 0), (10, 20, 1)]),
+            Some((0, 1))
+        );
+
+        // Every range overlapping every other one, as in a zip bomb
+        let ranges = (0..100_000).map(|i| (0, 10, i)).collect();
+        assert_eq!(first_overlapping_range(ranges), Some((0, 1)));
+    }
 }
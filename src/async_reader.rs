@@ -0,0 +1,586 @@
+use crate::archive::{
+    check_local_header, check_local_header_offset, CentralDirectoryCursor, CursorStep,
+    MAX_PREALLOCATION,
+};
+use crate::errors::{Error, ErrorKind};
+use crate::{
+    CompressionMethod, FileReader, ReaderAt, ReaderLen, ZipArchive, ZipArchiveEntryWayfinder,
+    ZipEntry, ZipFileHeaderRecord, ZipLocalFileHeaderFixed, ZipLocator, ZipStr, ZipString,
+};
+use std::future::Future;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// The largest read issued at once when prefetching data into memory, so a
+/// corrupt size doesn't translate into a huge allocation up front.
+const PREFETCH_CHUNK_SIZE: usize = 1 << 20;
+
+/// The most bytes a data descriptor spans: a signature, CRC, and two ZIP64
+/// sizes.
+const DATA_DESCRIPTOR_MAX_SIZE: u64 = 24;
+
+/// The future returned by [`AsyncReaderAt::read_at`].
+pub type ReadAtFuture<'a> = Pin<Box<dyn Future<Output = std::io::Result<usize>> + Send + 'a>>;
+
+/// Provides reading bytes at a specific offset asynchronously
+///
+/// This is the async sibling of [`ReaderAt`] for sources where a read
+/// shouldn't block the executor, like async files or object storage accessed
+/// over the network. Futures are boxed so that the trait can be implemented
+/// without `async fn` in traits.
+///
+/// Implement it directly for network sources, or bridge a blocking
+/// [`ReaderAt`] with [`TokioReaderAt`].
+///
+/// ```rust
+/// use rawzip::{AsyncReaderAt, ReadAtFuture};
+///
+/// struct InMemory(Vec<u8>);
+///
+/// impl AsyncReaderAt for InMemory {
+///     fn read_at<'a>(&'a self, buf: &'a mut [u8], offset: u64) -> ReadAtFuture<'a> {
+///         Box::pin(async move { rawzip::ReaderAt::read_at(&self.0, buf, offset) })
+///     }
+/// }
+/// ```
+pub trait AsyncReaderAt {
+    /// Read bytes from the reader at a specific offset
+    fn read_at<'a>(&'a self, buf: &'a mut [u8], offset: u64) -> ReadAtFuture<'a>;
+}
+
+impl<T: AsyncReaderAt + ?Sized> AsyncReaderAt for &T {
+    #[inline]
+    fn read_at<'a>(&'a self, buf: &'a mut [u8], offset: u64) -> ReadAtFuture<'a> {
+        (**self).read_at(buf, offset)
+    }
+}
+
+impl<T: AsyncReaderAt + ?Sized> AsyncReaderAt for Arc<T> {
+    #[inline]
+    fn read_at<'a>(&'a self, buf: &'a mut [u8], offset: u64) -> ReadAtFuture<'a> {
+        (**self).read_at(buf, offset)
+    }
+}
+
+/// Reads until `size` bytes are read or the reader is exhausted, returning
+/// the number of bytes read.
+async fn try_read_at_least_at<R>(
+    reader: &R,
+    buffer: &mut [u8],
+    mut size: usize,
+    offset: u64,
+) -> std::io::Result<usize>
+where
+    R: AsyncReaderAt + ?Sized,
+{
+    size = size.min(buffer.len());
+    let mut pos = 0;
+    while pos < size {
+        let read = reader
+            .read_at(&mut buffer[pos..], offset + pos as u64)
+            .await?;
+        if read == 0 {
+            return Ok(pos);
+        }
+        pos += read;
+    }
+    Ok(pos)
+}
+
+async fn read_at_least_at<R>(
+    reader: &R,
+    buffer: &mut [u8],
+    size: usize,
+    offset: u64,
+) -> Result<usize, Error>
+where
+    R: AsyncReaderAt + ?Sized,
+{
+    if buffer.len() < size {
+        return Err(Error::from(ErrorKind::BufferTooSmall));
+    }
+
+    let read = try_read_at_least_at(reader, buffer, size, offset).await?;
+    if read < size {
+        return Err(Error::from(ErrorKind::Eof));
+    }
+
+    Ok(read)
+}
+
+/// Bytes read ahead of time from an [`AsyncReaderAt`], so that the
+/// synchronous parsing of the rest of the crate can run over them.
+///
+/// Reads before the prefetched range fail, and reads past it hit EOF.
+#[derive(Debug)]
+pub(crate) struct Prefetched {
+    data: Vec<u8>,
+    offset: u64,
+}
+
+impl Prefetched {
+    /// Reads up to `len` bytes at `offset`, stopping early at the end of the
+    /// reader.
+    pub(crate) async fn read<R>(reader: &R, offset: u64, len: u64) -> std::io::Result<Self>
+    where
+        R: AsyncReaderAt + ?Sized,
+    {
+        let mut data = Vec::new();
+        while (data.len() as u64) < len {
+            let start = data.len();
+            let chunk = (len - start as u64).min(PREFETCH_CHUNK_SIZE as u64) as usize;
+            data.resize(start + chunk, 0);
+            let read =
+                try_read_at_least_at(reader, &mut data[start..], chunk, offset + start as u64)
+                    .await?;
+            data.truncate(start + read);
+            if read < chunk {
+                break;
+            }
+        }
+
+        Ok(Prefetched { data, offset })
+    }
+}
+
+impl ReaderAt for Prefetched {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        let Some(start) = offset.checked_sub(self.offset) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "read before the prefetched data",
+            ));
+        };
+
+        let data = self.data.get(start as usize..).unwrap_or_default();
+        let len = data.len().min(buf.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        Ok(len)
+    }
+}
+
+/// Bridges a blocking [`ReaderAt`] to an [`AsyncReaderAt`] by issuing each
+/// read on tokio's blocking thread pool.
+///
+/// Reads are copied through an intermediate buffer, as the blocking task
+/// can't borrow the caller's. Requires a tokio runtime.
+///
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use rawzip::{AsyncReaderAt, TokioReaderAt};
+///
+/// let reader = TokioReaderAt::new(std::fs::read("assets/test.zip")?);
+/// let mut signature = [0u8; 4];
+/// reader.read_at(&mut signature, 0).await?;
+/// assert_eq!(&signature, b"PK\x03\x04");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TokioReaderAt<R> {
+    inner: Arc<R>,
+}
+
+impl<R> TokioReaderAt<R> {
+    /// Wraps a blocking reader.
+    pub fn new(inner: R) -> Self {
+        TokioReaderAt {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Returns a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+}
+
+impl TokioReaderAt<FileReader> {
+    /// Opens the file at `path` for reading without blocking the executor.
+    pub async fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let file = tokio::fs::File::open(path).await?.into_std().await;
+        Ok(TokioReaderAt::new(FileReader::from(file)))
+    }
+}
+
+impl<R> Clone for TokioReaderAt<R> {
+    fn clone(&self) -> Self {
+        TokioReaderAt {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<R: ReaderLen> ReaderLen for TokioReaderAt<R> {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        self.inner.len()
+    }
+}
+
+impl<R> AsyncReaderAt for TokioReaderAt<R>
+where
+    R: ReaderAt + Send + Sync + 'static,
+{
+    fn read_at<'a>(&'a self, buf: &'a mut [u8], offset: u64) -> ReadAtFuture<'a> {
+        let inner = Arc::clone(&self.inner);
+        let len = buf.len();
+        Box::pin(async move {
+            let data = tokio::task::spawn_blocking(move || {
+                let mut data = vec![0u8; len];
+                let read = inner.read_at(&mut data, offset)?;
+                data.truncate(read);
+                Ok::<_, std::io::Error>(data)
+            })
+            .await
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))??;
+
+            buf[..data.len()].copy_from_slice(&data);
+            Ok(data.len())
+        })
+    }
+}
+
+/// A Zip archive read through an [`AsyncReaderAt`].
+///
+/// The async counterpart of [`ZipArchive`]. Archives are located with
+/// [`AsyncZipArchive::from_reader_at`] or
+/// [`ZipLocator::locate_in_async_reader`].
+///
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use rawzip::{AsyncZipArchive, TokioReaderAt, RECOMMENDED_BUFFER_SIZE};
+///
+/// let reader = TokioReaderAt::open("assets/test.zip").await?;
+/// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+/// let archive = AsyncZipArchive::from_reader_at(reader, &mut buffer).await?;
+///
+/// let mut names = Vec::new();
+/// let mut entries = archive.entries(&mut buffer);
+/// while let Some(entry) = entries.next_entry().await? {
+///     names.push(entry.file_path().try_normalize()?.as_ref().to_string());
+/// }
+/// assert_eq!(names, ["test.txt", "gophercolor16x16.png"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsyncZipArchive<R> {
+    archive: ZipArchive<R>,
+}
+
+impl AsyncZipArchive<()> {
+    /// Parses an archive from an [`AsyncReaderAt`] that knows its length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if the reader doesn't
+    /// know its length, in addition to the errors of locating the archive.
+    pub async fn from_reader_at<R>(
+        reader: R,
+        buffer: &mut [u8],
+    ) -> Result<AsyncZipArchive<R>, Error>
+    where
+        R: AsyncReaderAt + ReaderLen,
+    {
+        let Some(end_offset) = ReaderLen::len(&reader) else {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "length of reader is unknown".to_string(),
+            }));
+        };
+
+        ZipLocator::new()
+            .locate_in_async_reader(reader, buffer, end_offset)
+            .await
+            .map_err(|(_, e)| e)
+    }
+}
+
+impl<R> AsyncZipArchive<R> {
+    /// Swaps the reader that an archive was located with for `reader`.
+    pub(crate) fn with_reader<T>(archive: ZipArchive<T>, reader: R) -> Self {
+        AsyncZipArchive {
+            archive: ZipArchive {
+                reader,
+                comment: archive.comment,
+                eocd: archive.eocd,
+                max_entries: archive.max_entries,
+            },
+        }
+    }
+
+    /// Returns a lending iterator over the entries in the central directory
+    /// of the archive.
+    ///
+    /// The buffer is filled with the central directory as the iterator
+    /// advances, like [`ZipArchive::entries`].
+    pub fn entries<'archive, 'buf>(
+        &'archive self,
+        buffer: &'buf mut [u8],
+    ) -> AsyncZipEntries<'archive, 'buf, R> {
+        AsyncZipEntries {
+            buffer,
+            archive: self,
+            cursor: CentralDirectoryCursor::new(&self.archive.eocd, self.archive.max_entries),
+        }
+    }
+
+    /// Returns a hint for the total number of entries in the archive.
+    ///
+    /// This value is read from the End of Central Directory record.
+    pub fn entries_hint(&self) -> u64 {
+        self.archive.entries_hint()
+    }
+
+    /// Returns the comment of the zip archive, if any.
+    pub fn comment(&self) -> ZipStr<'_> {
+        self.archive.comment()
+    }
+
+    /// Returns the offset of the start of the zip file data.
+    ///
+    /// See [`ZipArchive::base_offset`].
+    pub fn base_offset(&self) -> u64 {
+        self.archive.base_offset()
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.archive.reader
+    }
+
+    /// Consumes the archive, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.archive.reader
+    }
+}
+
+impl<R> AsyncZipArchive<R>
+where
+    R: AsyncReaderAt,
+{
+    /// Retrieves a specific entry from the archive by a wayfinder.
+    ///
+    /// # Errors
+    ///
+    /// See [`ZipArchive::get_entry`].
+    pub async fn get_entry(
+        &self,
+        entry: ZipArchiveEntryWayfinder,
+    ) -> Result<AsyncZipEntry<'_, R>, Error> {
+        let (central_dir_offset, _) = self.archive.central_directory_range();
+        check_local_header_offset(entry.local_header_offset, central_dir_offset)?;
+        let mut buffer = [0u8; ZipLocalFileHeaderFixed::SIZE];
+        read_at_least_at(
+            &self.archive.reader,
+            &mut buffer,
+            ZipLocalFileHeaderFixed::SIZE,
+            entry.local_header_offset,
+        )
+        .await?;
+
+        let local_header = ZipLocalFileHeaderFixed::parse(&buffer)
+            .map_err(|e| e.at_offset(entry.local_header_offset))?;
+        Ok(AsyncZipEntry {
+            archive: self,
+            entry,
+            local_header,
+        })
+    }
+
+    /// Retrieves a specific entry like [`AsyncZipArchive::get_entry`], and
+    /// also checks that its local file header agrees with the central
+    /// directory.
+    ///
+    /// # Errors
+    ///
+    /// See [`ZipArchive::get_entry_checked`].
+    pub async fn get_entry_checked(
+        &self,
+        entry: ZipArchiveEntryWayfinder,
+    ) -> Result<AsyncZipEntry<'_, R>, Error> {
+        let result = self.get_entry(entry).await?;
+        let local_header = &result.local_header;
+        let extra_field_offset = entry.local_header_offset
+            + ZipLocalFileHeaderFixed::SIZE as u64
+            + u64::from(local_header.file_name_len);
+        let mut extra_field = vec![0u8; usize::from(local_header.extra_field_len)];
+        let len = extra_field.len();
+        read_at_least_at(
+            &self.archive.reader,
+            &mut extra_field,
+            len,
+            extra_field_offset,
+        )
+        .await?;
+        check_local_header(local_header, &extra_field, &entry)?;
+        Ok(result)
+    }
+}
+
+/// A lending iterator over file header records in an [`AsyncZipArchive`].
+#[derive(Debug)]
+pub struct AsyncZipEntries<'archive, 'buf, R> {
+    buffer: &'buf mut [u8],
+    archive: &'archive AsyncZipArchive<R>,
+    cursor: CentralDirectoryCursor,
+}
+
+impl<R> AsyncZipEntries<'_, '_, R>
+where
+    R: AsyncReaderAt,
+{
+    /// Yield the next zip file entry in the central directory if there is any
+    ///
+    /// See [`ZipEntries::next_entry`](crate::ZipEntries::next_entry).
+    pub async fn next_entry(&mut self) -> Result<Option<ZipFileHeaderRecord<'_>>, Error> {
+        loop {
+            match self.cursor.step(self.buffer)? {
+                CursorStep::Read(size) => {
+                    let (buffer, offset) = self.cursor.fill_target(self.buffer);
+                    let reader = &self.archive.archive.reader;
+                    let read = read_at_least_at(reader, buffer, size, offset).await?;
+                    self.cursor.filled(read);
+                }
+                CursorStep::Ready => break,
+                CursorStep::Done => return Ok(None),
+            }
+        }
+
+        let record = self
+            .cursor
+            .take_record(self.buffer, &self.archive.archive.eocd)?;
+        Ok(Some(record))
+    }
+
+    /// Returns the offset of the central directory record that
+    /// [`AsyncZipEntries::next_entry`] will read next.
+    ///
+    /// See [`ZipEntries::position`](crate::ZipEntries::position).
+    pub fn position(&self) -> u64 {
+        self.cursor.position()
+    }
+
+    /// Repositions the iterator to the central directory record at `offset`,
+    /// as previously returned by [`AsyncZipEntries::position`].
+    ///
+    /// See [`ZipEntries::seek_to_offset`](crate::ZipEntries::seek_to_offset).
+    pub fn seek_to_offset(&mut self, offset: u64) -> Result<(), Error> {
+        self.cursor
+            .seek_to_offset(offset, &self.archive.archive.eocd)
+    }
+}
+
+/// A single entry within an [`AsyncZipArchive`].
+#[derive(Debug, Clone)]
+pub struct AsyncZipEntry<'archive, R> {
+    archive: &'archive AsyncZipArchive<R>,
+    entry: ZipArchiveEntryWayfinder,
+    local_header: ZipLocalFileHeaderFixed,
+}
+
+impl<R> AsyncZipEntry<'_, R>
+where
+    R: AsyncReaderAt,
+{
+    /// Returns a tuple of start and end byte offsets for the compressed data
+    /// within the underlying reader.
+    pub fn compressed_data_range(&self) -> (u64, u64) {
+        let start = self.entry.local_header_offset
+            + ZipLocalFileHeaderFixed::SIZE as u64
+            + self.local_header.variable_length() as u64;
+        (start, start + self.entry.compressed_size_hint())
+    }
+
+    /// Reads the compressed data into memory and decompresses it, verifying
+    /// its size and CRC.
+    ///
+    /// The `decompressor` is given the entry's compression method and a
+    /// reader of the compressed data, and returns a reader of the
+    /// decompressed data, like [`ZipEntry::read_to_vec`]. Decompression
+    /// itself runs synchronously once the compressed data has been read.
+    ///
+    /// Both the compressed and decompressed data are held in memory, with no
+    /// bound beyond what the archive declares. Use
+    /// [`AsyncZipEntry::read_to_vec_limited`] for untrusted archives.
+    ///
+    /// # Errors
+    ///
+    /// Returns the decompressor's error, or an error if reading fails or the
+    /// size or CRC doesn't match.
+    pub async fn read_to_vec<F, D>(&self, decompressor: F) -> Result<Vec<u8>, Error>
+    where
+        F: FnOnce(CompressionMethod, Cursor<Vec<u8>>) -> Result<D, Error>,
+        D: Read,
+    {
+        self.read_to_vec_limited(u64::MAX, decompressor).await
+    }
+
+    /// Reads and decompresses the entry like [`AsyncZipEntry::read_to_vec`],
+    /// holding at most `max_bytes` of either compressed or decompressed data
+    /// in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::LimitExceeded`] error if the compressed size
+    /// exceeds `max_bytes` (before anything is read), or once the
+    /// decompressed output does, in addition to the errors of
+    /// [`AsyncZipEntry::read_to_vec`].
+    pub async fn read_to_vec_limited<F, D>(
+        &self,
+        max_bytes: u64,
+        decompressor: F,
+    ) -> Result<Vec<u8>, Error>
+    where
+        F: FnOnce(CompressionMethod, Cursor<Vec<u8>>) -> Result<D, Error>,
+        D: Read,
+    {
+        let (start, end) = self.compressed_data_range();
+        if end - start > max_bytes {
+            return Err(Error::from(ErrorKind::LimitExceeded {
+                limit: max_bytes,
+                actual: end - start,
+            }));
+        }
+
+        // Prefetch any data descriptor too, as verification reads its CRC
+        let reader = &self.archive.archive.reader;
+        let mut compressed =
+            Prefetched::read(reader, start, end - start + DATA_DESCRIPTOR_MAX_SIZE)
+                .await?
+                .data;
+        if (compressed.len() as u64) < end - start {
+            return Err(Error::from(ErrorKind::Eof));
+        }
+        let descriptor = Prefetched {
+            data: compressed.split_off((end - start) as usize),
+            offset: end,
+        };
+
+        let method = self.local_header.compression_method.as_method();
+        let decompressed = decompressor(method, Cursor::new(compressed))?;
+        let archive = ZipArchive {
+            reader: descriptor,
+            comment: ZipString::new(Vec::new()),
+            eocd: self.archive.archive.eocd.clone(),
+            max_entries: self.archive.archive.max_entries,
+        };
+        let entry = ZipEntry::new(&archive, self.entry, self.local_header.clone());
+
+        let capacity = self
+            .entry
+            .uncompressed_size_hint()
+            .min(max_bytes)
+            .min(MAX_PREALLOCATION) as usize;
+        let mut result = Vec::with_capacity(capacity);
+        entry
+            .verifying_reader_limited(decompressed, max_bytes)
+            .read_to_end(&mut result)
+            .map_err(Error::from_io)?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,32 @@
+//! Encoding of file names to IBM Code Page 437, the default (non UTF-8)
+//! encoding of names in a ZIP archive.
+
+/// The characters for bytes `0x80..=0xFF`. Bytes below `0x80` are ASCII.
+const HIGH_HALF: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// Encodes `name` to CP-437, returning the first character that has no
+/// CP-437 representation on failure.
+pub(crate) fn encode(name: &str) -> Result<Vec<u8>, char> {
+    name.chars()
+        .map(|ch| {
+            if ch.is_ascii() {
+                Ok(ch as u8)
+            } else {
+                HIGH_HALF
+                    .iter()
+                    .position(|&x| x == ch)
+                    .map(|i| 0x80 + i as u8)
+                    .ok_or(ch)
+            }
+        })
+        .collect()
+}
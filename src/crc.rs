@@ -44,13 +44,110 @@ static CRC_TABLE: [[u32; 256]; 16] = gen_crc_table();
 ///
 /// Benchmarks showed that function should be fast enough for all uses, only
 /// losing to `crc32fast` at the largest payload size and even then eking out a
-/// single digit performance improvement.
+/// single digit performance improvement. Enable the `crc32fast` feature to
+/// compute checksums with its hardware accelerated implementation instead.
 pub fn crc32(data: &[u8]) -> u32 {
     crc32_chunk(data, 0)
 }
 
+/// Combines the CRC32 of two byte sequences into the CRC32 of their
+/// concatenation, given the length of the second sequence.
+///
+/// This allows chunks to be hashed independently (eg: in parallel) and
+/// merged without hashing the data again.
+///
+/// ```rust
+/// let (head, tail) = b"Hello, world!".split_at(5);
+/// let crc = rawzip::crc32_combine(rawzip::crc32(head), rawzip::crc32(tail), tail.len() as u64);
+/// assert_eq!(crc, rawzip::crc32(b"Hello, world!"));
+/// ```
+pub fn crc32_combine(mut crc1: u32, crc2: u32, mut len2: u64) -> u32 {
+    // Port of zlib's crc32_combine: applies len2 zero bytes to crc1 through
+    // repeated squaring of the operator matrix that appends a single zero bit
+    if len2 == 0 {
+        return crc1;
+    }
+
+    let mut odd = [0u32; 32];
+    odd[0] = 0xEDB88320;
+    let mut row = 1;
+    for entry in odd.iter_mut().skip(1) {
+        *entry = row;
+        row <<= 1;
+    }
+
+    // Operators for two and then four zero bits
+    let mut even = gf2_matrix_square(&odd);
+    odd = gf2_matrix_square(&even);
+
+    loop {
+        // Apply zeros in increasing powers of two (starting at a byte)
+        even = gf2_matrix_square(&odd);
+        if len2 & 1 != 0 {
+            crc1 = gf2_matrix_times(&even, crc1);
+        }
+        len2 >>= 1;
+        if len2 == 0 {
+            break;
+        }
+
+        odd = gf2_matrix_square(&even);
+        if len2 & 1 != 0 {
+            crc1 = gf2_matrix_times(&odd, crc1);
+        }
+        len2 >>= 1;
+        if len2 == 0 {
+            break;
+        }
+    }
+
+    crc1 ^ crc2
+}
+
+fn gf2_matrix_times(matrix: &[u32; 32], mut vector: u32) -> u32 {
+    let mut sum = 0;
+    let mut rows = matrix.iter();
+    while vector != 0 {
+        let row = rows.next().copied().unwrap_or_default();
+        if vector & 1 != 0 {
+            sum ^= row;
+        }
+        vector >>= 1;
+    }
+    sum
+}
+
+fn gf2_matrix_square(matrix: &[u32; 32]) -> [u32; 32] {
+    let mut square = [0u32; 32];
+    for (row, &entry) in square.iter_mut().zip(matrix.iter()) {
+        *row = gf2_matrix_times(matrix, entry);
+    }
+    square
+}
+
+/// Advances a raw (non-inverted) CRC32 state by a single byte, as used by the
+/// traditional PKWARE encryption key schedule.
+#[inline]
+pub(crate) fn crc32_byte(crc: u32, byte: u8) -> u32 {
+    (crc >> 8) ^ CRC_TABLE[0][((crc ^ u32::from(byte)) & 0xFF) as usize]
+}
+
 #[inline]
 pub fn crc32_chunk(data: &[u8], prev: u32) -> u32 {
+    #[cfg(feature = "crc32fast")]
+    {
+        let mut hasher = crc32fast::Hasher::new_with_initial(prev);
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    #[cfg(not(feature = "crc32fast"))]
+    crc32_chunk_software(data, prev)
+}
+
+#[inline]
+#[cfg_attr(feature = "crc32fast", allow(dead_code))]
+fn crc32_chunk_software(data: &[u8], prev: u32) -> u32 {
     let mut chunks = data.chunks_exact(16);
     let mut crc = chunks.by_ref().fold(!prev, |crc, data| {
         CRC_TABLE[0x0][data[0xf] as usize]
@@ -81,6 +178,7 @@ pub fn crc32_chunk(data: &[u8], prev: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use quickcheck_macros::quickcheck;
 
     #[test]
     fn test_crc() {
@@ -94,4 +192,30 @@ mod tests {
         let abc = b"EU4txt\nchecksum=\"ced5411e2d4a5ec724595c2c4f1b7347\"";
         assert_eq!(crc32(abc), 1702863696);
     }
+
+    #[test]
+    fn test_crc32_combine() {
+        let data = (0..5000u32)
+            .map(|i| (i * 7 % 251) as u8)
+            .collect::<Vec<_>>();
+        for split in [0, 1, 15, 16, 17, 1000, 4096, 4999, 5000] {
+            let (head, tail) = data.split_at(split);
+            let combined = crc32_combine(crc32(head), crc32(tail), tail.len() as u64);
+            assert_eq!(combined, crc32(&data), "split at {split}");
+        }
+    }
+
+    #[cfg(feature = "crc32fast")]
+    #[quickcheck]
+    fn test_crc32_chunk_backends(data: Vec<u8>, prev: u32) -> bool {
+        crc32_chunk(&data, prev) == crc32_chunk_software(&data, prev)
+    }
+
+    #[quickcheck]
+    fn test_crc32_combine_chunks(chunks: Vec<Vec<u8>>) -> bool {
+        let combined = chunks.iter().fold(0, |crc, chunk| {
+            crc32_combine(crc, crc32(chunk), chunk.len() as u64)
+        });
+        combined == crc32(&chunks.concat())
+    }
 }
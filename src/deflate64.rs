@@ -0,0 +1,62 @@
+use std::io::{BufReader, Read};
+
+/// A reader that decompresses Deflate64 ([`CompressionMethod::Deflate64`])
+/// data.
+///
+/// Deflate64 extends deflate's window from 32 KiB to 64 KiB, repurposes
+/// length code 285 to encode lengths up to 65538 bytes, and adds two
+/// distance codes for the larger window. Deflate decoders like flate2 reject
+/// or silently misread it, so entries written by Windows' built-in
+/// compressor, which switches to Deflate64 for large files, need this
+/// decoder instead.
+///
+/// ```rust
+/// use rawzip::{CompressionMethod, Deflate64Decoder, Error, ErrorKind, ZipArchive, RECOMMENDED_BUFFER_SIZE};
+///
+/// let file = std::fs::File::open("assets/deflate64.zip")?;
+/// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+/// let archive = ZipArchive::from_file(file, &mut buffer)?;
+/// let wayfinder = archive.entries(&mut buffer).next_entry()?.unwrap().wayfinder();
+/// let entry = archive.get_entry(wayfinder)?;
+/// let contents = entry.read_to_vec(|method, reader| match method {
+///     CompressionMethod::Deflate64 => Ok(Deflate64Decoder::new(reader)),
+///     _ => Err(Error::from(ErrorKind::InvalidInput {
+///         msg: format!("unsupported compression method: {:?}", method),
+///     })),
+/// })?;
+/// assert_eq!(contents.len(), 40310);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`CompressionMethod::Deflate64`]: crate::CompressionMethod::Deflate64
+pub struct Deflate64Decoder<R> {
+    inner: ::deflate64::Deflate64Decoder<BufReader<R>>,
+}
+
+impl<R: Read> Deflate64Decoder<R> {
+    /// Creates a decoder of the compressed data read from `reader`.
+    pub fn new(reader: R) -> Self {
+        Deflate64Decoder {
+            inner: ::deflate64::Deflate64Decoder::new(reader),
+        }
+    }
+
+    /// Returns the underlying reader of compressed data.
+    ///
+    /// Compressed data already buffered by the decoder is lost.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner().into_inner()
+    }
+}
+
+impl<R> std::fmt::Debug for Deflate64Decoder<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Deflate64Decoder").finish_non_exhaustive()
+    }
+}
+
+impl<R: Read> Read for Deflate64Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
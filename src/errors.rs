@@ -9,6 +9,19 @@ impl Error {
         Error::from(ErrorKind::IO(err))
     }
 
+    /// Converts an IO error, unwrapping errors that originated from this crate
+    /// (eg: a failed verification surfaced through [`std::io::Read`]).
+    pub(crate) fn from_io(err: std::io::Error) -> Error {
+        if !err.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            return Error::io(err);
+        }
+
+        let inner = err.into_inner().expect("io error has an inner error");
+        *inner
+            .downcast::<Error>()
+            .expect("inner error is a rawzip error")
+    }
+
     pub(crate) fn utf8(err: std::str::Utf8Error) -> Error {
         Error::from(ErrorKind::InvalidUtf8(err))
     }
@@ -17,15 +30,77 @@ impl Error {
         matches!(self.inner.kind, ErrorKind::Eof)
     }
 
+    /// Records the byte offset within the archive where parsing failed.
+    pub(crate) fn at_offset(mut self, offset: u64) -> Error {
+        self.inner.offset = Some(offset);
+        self
+    }
+
+    /// Records the name of the entry that the error concerns.
+    pub(crate) fn for_entry(mut self, name: String) -> Error {
+        self.inner.entry_name = Some(name);
+        self
+    }
+
     /// The kind of error that occurred
     pub fn kind(&self) -> &ErrorKind {
         &self.inner.kind
     }
+
+    /// The byte offset within the archive of the structure that failed to
+    /// parse, if known.
+    pub fn offset(&self) -> Option<u64> {
+        self.inner.offset
+    }
+
+    /// The name of the entry the error concerns, if known.
+    pub fn entry_name(&self) -> Option<&str> {
+        self.inner.entry_name.as_deref()
+    }
+
+    /// Returns true if the error is due to malformed or tampered archive
+    /// data, so retrying won't help.
+    ///
+    /// [`ErrorKind::InvalidInput`] is not included, as it reports misuse of
+    /// the API, and neither is [`ErrorKind::LimitExceeded`], as a well formed
+    /// entry may simply be larger than the caller allows.
+    ///
+    /// ```rust
+    /// let err = rawzip::ZipArchive::from_slice(b"not a zip").unwrap_err();
+    /// assert!(err.is_corruption());
+    /// assert!(!err.is_io());
+    /// ```
+    pub fn is_corruption(&self) -> bool {
+        match self.inner.kind {
+            ErrorKind::MissingEndOfCentralDirectory
+            | ErrorKind::MissingZip64EndOfCentralDirectory
+            | ErrorKind::InvalidSignature { .. }
+            | ErrorKind::InvalidChecksum { .. }
+            | ErrorKind::InvalidSize { .. }
+            | ErrorKind::InvalidUtf8(_)
+            | ErrorKind::LocalHeaderMismatch { .. }
+            | ErrorKind::InconsistentArchive { .. }
+            | ErrorKind::InvalidAuthenticationCode
+            | ErrorKind::Eof => true,
+            ErrorKind::BufferTooSmall
+            | ErrorKind::InvalidInput { .. }
+            | ErrorKind::LimitExceeded { .. }
+            | ErrorKind::InvalidPassword
+            | ErrorKind::IO(_) => false,
+        }
+    }
+
+    /// Returns true if the error came from the underlying reader or writer.
+    pub fn is_io(&self) -> bool {
+        matches!(self.inner.kind, ErrorKind::IO(_))
+    }
 }
 
 #[derive(Debug)]
 struct ErrorInner {
     kind: ErrorKind,
+    offset: Option<u64>,
+    entry_name: Option<String>,
 }
 
 /// The kind of error that occurred
@@ -50,12 +125,33 @@ pub enum ErrorKind {
     /// An unexpected inflated file size
     InvalidSize { expected: u64, actual: u64 },
 
+    /// Data exceeded a size limit set by the caller
+    LimitExceeded { limit: u64, actual: u64 },
+
     /// Invalid UTF-8 sequence
     InvalidUtf8(std::str::Utf8Error),
 
     /// An invalid input error with associated message
     InvalidInput { msg: String },
 
+    /// A field of a local file header disagrees with the entry's central
+    /// directory record
+    LocalHeaderMismatch {
+        field: &'static str,
+        local: u64,
+        central: u64,
+    },
+
+    /// Records of the archive contradict each other, or a record required by
+    /// another is missing
+    InconsistentArchive { msg: String },
+
+    /// The password failed verification for an encrypted entry
+    InvalidPassword,
+
+    /// The authentication code of AES encrypted data did not match
+    InvalidAuthenticationCode,
+
     /// An IO error
     IO(std::io::Error),
 
@@ -68,6 +164,12 @@ impl std::error::Error for Error {}
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{}", self.inner.kind)?;
+        if let Some(name) = &self.inner.entry_name {
+            write!(f, " in entry {:?}", name)?;
+        }
+        if let Some(offset) = self.inner.offset {
+            write!(f, " at offset {}", offset)?;
+        }
         Ok(())
     }
 }
@@ -105,12 +207,35 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::InvalidSize { expected, actual } => {
                 write!(f, "Invalid size: expected {}, got {}", expected, actual)
             }
+            ErrorKind::LimitExceeded { limit, actual } => {
+                write!(f, "Limit of {} bytes exceeded: got {}", limit, actual)
+            }
             ErrorKind::InvalidUtf8(ref err) => {
                 write!(f, "Invalid UTF-8: {}", err)
             }
             ErrorKind::InvalidInput { ref msg } => {
                 write!(f, "Invalid input: {}", msg)
             }
+            ErrorKind::LocalHeaderMismatch {
+                field,
+                local,
+                central,
+            } => {
+                write!(
+                    f,
+                    "Local header {} ({}) disagrees with central directory ({})",
+                    field, local, central
+                )
+            }
+            ErrorKind::InconsistentArchive { ref msg } => {
+                write!(f, "Inconsistent archive: {}", msg)
+            }
+            ErrorKind::InvalidPassword => {
+                write!(f, "Invalid password")
+            }
+            ErrorKind::InvalidAuthenticationCode => {
+                write!(f, "Invalid authentication code")
+            }
         }
     }
 }
@@ -118,7 +243,11 @@ impl std::fmt::Display for ErrorKind {
 impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Error {
         Error {
-            inner: Box::new(ErrorInner { kind }),
+            inner: Box::new(ErrorInner {
+                kind,
+                offset: None,
+                entry_name: None,
+            }),
         }
     }
 }
@@ -128,3 +257,47 @@ impl From<std::io::Error> for Error {
         Error::from(ErrorKind::IO(err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(ErrorKind::MissingEndOfCentralDirectory, true, false)]
+    #[case(ErrorKind::MissingZip64EndOfCentralDirectory, true, false)]
+    #[case(ErrorKind::BufferTooSmall, false, false)]
+    #[case(ErrorKind::InvalidSignature { expected: 1, actual: 2 }, true, false)]
+    #[case(ErrorKind::InvalidChecksum { expected: 1, actual: 2 }, true, false)]
+    #[case(ErrorKind::InvalidSize { expected: 1, actual: 2 }, true, false)]
+    #[case(ErrorKind::LimitExceeded { limit: 1, actual: 2 }, false, false)]
+    #[case(
+        ErrorKind::InvalidUtf8(String::from_utf8(vec![0xff]).unwrap_err().utf8_error()),
+        true,
+        false
+    )]
+    #[case(ErrorKind::InvalidInput { msg: String::new() }, false, false)]
+    #[case(
+        ErrorKind::LocalHeaderMismatch { field: "crc", local: 1, central: 2 },
+        true,
+        false
+    )]
+    #[case(ErrorKind::InconsistentArchive { msg: String::new() }, true, false)]
+    #[case(ErrorKind::InvalidPassword, false, false)]
+    #[case(ErrorKind::InvalidAuthenticationCode, true, false)]
+    #[case(
+        ErrorKind::IO(std::io::Error::new(std::io::ErrorKind::Other, "io")),
+        false,
+        true
+    )]
+    #[case(ErrorKind::Eof, true, false)]
+    fn test_error_categories(
+        #[case] kind: ErrorKind,
+        #[case] is_corruption: bool,
+        #[case] is_io: bool,
+    ) {
+        let err = Error::from(kind);
+        assert_eq!(err.is_corruption(), is_corruption);
+        assert_eq!(err.is_io(), is_io);
+    }
+}
@@ -2,20 +2,42 @@
 #![forbid(unsafe_code)]
 
 mod archive;
+#[cfg(feature = "tokio")]
+mod async_reader;
+mod cp437;
 mod crc;
+#[cfg(feature = "deflate64")]
+mod deflate64;
 mod errors;
 mod locator;
 mod mode;
 pub mod path;
 mod reader_at;
 pub mod time;
+mod tree;
 mod utils;
+mod winzip_aes;
 mod writer;
+mod zipcrypto;
 
 pub use archive::*;
-pub use crc::crc32;
+#[cfg(feature = "tokio")]
+pub use async_reader::{
+    AsyncReaderAt, AsyncZipArchive, AsyncZipEntries, AsyncZipEntry, ReadAtFuture, TokioReaderAt,
+};
+pub use crc::{crc32, crc32_combine};
+#[cfg(feature = "deflate64")]
+pub use deflate64::Deflate64Decoder;
 pub use errors::{Error, ErrorKind};
 pub use locator::*;
 pub use mode::EntryMode;
-pub use reader_at::{FileReader, ReaderAt};
+pub use reader_at::{
+    BoundedReaderAt, CachingReaderAt, FileReader, FnReaderAt, ReaderAt, ReaderLen, SeekingReader,
+    SpannedReaderAt,
+};
+pub use tree::{write_dir_tree, DirTreeOptions};
+#[cfg(feature = "aes")]
+pub use winzip_aes::AesReader;
+pub use winzip_aes::{AesExtraField, AesStrength};
 pub use writer::*;
+pub use zipcrypto::ZipCryptoReader;
@@ -1,9 +1,12 @@
+use crate::archive::resolve_disk_offset;
 use crate::errors::{Error, ErrorKind};
 use crate::reader_at::{FileReader, ReaderAtExt};
 use crate::utils::{le_u16, le_u32, le_u64};
+#[cfg(feature = "tokio")]
+use crate::{archive::ZipFileHeaderFixed, async_reader::Prefetched};
 use crate::{
     EndOfCentralDirectory, ReaderAt, Zip64EndOfCentralDirectoryRecord, ZipArchive, ZipSliceArchive,
-    ZipString, END_OF_CENTRAL_DIR_LOCATOR_SIGNATURE,
+    ZipString, CENTRAL_HEADER_SIGNATURE, END_OF_CENTRAL_DIR_LOCATOR_SIGNATURE,
 };
 use std::cell::RefCell;
 use std::fs::File;
@@ -14,7 +17,7 @@ pub(crate) const END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES: [u8; 4] =
     END_OF_CENTRAL_DIR_SIGNAUTRE.to_le_bytes();
 
 // https://github.com/zlib-ng/minizip-ng/blob/55db144e03027b43263e5ebcb599bf0878ba58de/mz_zip.c#L78
-const END_OF_CENTRAL_DIR_MAX_OFFSET: u64 = 1 << 20;
+pub(crate) const END_OF_CENTRAL_DIR_MAX_OFFSET: u64 = 1 << 20;
 
 /// Locates the End of Central Directory (EOCD) record in a ZIP archive.
 ///
@@ -22,6 +25,9 @@ const END_OF_CENTRAL_DIR_MAX_OFFSET: u64 = 1 << 20;
 /// for reading the contents of a ZIP file.
 pub struct ZipLocator {
     max_search_space: u64,
+    max_entries: u64,
+    probe_base_offset: bool,
+    disk_starts: Option<Vec<u64>>,
 }
 
 impl Default for ZipLocator {
@@ -35,6 +41,9 @@ impl ZipLocator {
     pub fn new() -> Self {
         ZipLocator {
             max_search_space: END_OF_CENTRAL_DIR_MAX_OFFSET,
+            max_entries: u64::MAX,
+            probe_base_offset: false,
+            disk_starts: None,
         }
     }
 
@@ -52,11 +61,69 @@ impl ZipLocator {
         self
     }
 
+    /// Sets the maximum number of central directory records that the entry
+    /// iterators of the located archive will yield.
+    ///
+    /// Once the limit is reached, requesting another entry returns an
+    /// [`ErrorKind::InvalidInput`] error instead of continuing to parse. This
+    /// bounds the work done on untrusted input that claims a huge number of
+    /// entries. Defaults to unbounded.
+    ///
+    /// ```rust
+    /// use rawzip::ZipLocator;
+    ///
+    /// let locator = ZipLocator::new().max_entries(10_000);
+    /// ```
+    pub fn max_entries(mut self, max_entries: u64) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Sets whether to double check a non-zero base offset, like Go's zip
+    /// reader does.
+    ///
+    /// The base offset, where the archive starts within the data, is derived
+    /// from the size and offset of the central directory recorded in the end
+    /// of central directory record. Some writers record a wrong size, so the
+    /// archive appears to be prefixed with data it isn't. When probing, a
+    /// non-zero base offset is replaced with zero if a valid central
+    /// directory header is found at the recorded offset. Disabled by default,
+    /// as other readers reject such archives too.
+    ///
+    /// ```rust
+    /// use rawzip::ZipLocator;
+    ///
+    /// let data = std::fs::read("assets/test-baddirsz.zip")?;
+    /// assert!(ZipLocator::new().locate_in_slice(&data).is_ok_and(|archive| {
+    ///     archive.base_offset() != 0 && archive.entries().next_entry().is_err()
+    /// }));
+    ///
+    /// let archive = ZipLocator::new()
+    ///     .probe_base_offset(true)
+    ///     .locate_in_slice(&data)
+    ///     .map_err(|(_, e)| e)?;
+    /// assert_eq!(archive.base_offset(), 0);
+    /// assert_eq!(archive.entries().count(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn probe_base_offset(mut self, probe: bool) -> Self {
+        self.probe_base_offset = probe;
+        self
+    }
+
+    /// Resolves offsets recorded relative to each disk of a split archive
+    /// against where each disk starts in the reader.
+    pub(crate) fn disk_starts(mut self, disk_starts: Vec<u64>) -> Self {
+        self.disk_starts = Some(disk_starts);
+        self
+    }
+
     fn locate_in_byte_slice(&self, data: &[u8]) -> Result<EndOfCentralDirectory, Error> {
-        let location = find_end_of_central_dir_signature(data, self.max_search_space as usize)
+        let location = find_end_of_central_dir_record(data, self.max_search_space as usize)
             .ok_or(ErrorKind::MissingEndOfCentralDirectory)?;
 
-        let eocd = EndOfCentralDirectoryRecordFixed::parse(&data[location..])?;
+        let eocd = EndOfCentralDirectoryRecordFixed::parse(&data[location..])
+            .map_err(|e| e.at_offset(location as u64))?;
         let is_zip64 = eocd.is_zip64();
 
         if !is_zip64 {
@@ -64,19 +131,48 @@ impl ZipLocator {
                 zip64: None,
                 eocd,
                 stream_pos: location as u64,
+                zero_base_offset: false,
+                disk_starts: self.disk_starts.clone(),
             });
         }
 
-        let zip64l =
-            &data[location.saturating_sub(Zip64EndOfCentralDirectoryLocatorRecord::SIZE)..];
-        let zip64_locator = Zip64EndOfCentralDirectoryLocatorRecord::parse(zip64l)?;
+        let zip64_locator =
+            match location.checked_sub(Zip64EndOfCentralDirectoryLocatorRecord::SIZE) {
+                Some(pos) => Zip64EndOfCentralDirectoryLocatorRecord::parse(&data[pos..]),
+                None => Err(Error::from(ErrorKind::MissingZip64EndOfCentralDirectory)),
+            };
+
+        let zip64_locator = match zip64_locator {
+            Ok(locator) => locator,
+            Err(e) if is_missing_zip64_locator(&e) => {
+                let has_central_dir = eocd
+                    .zip64_fallback_central_dir_start(location as u64)
+                    .and_then(|pos| data.get(pos as usize..pos as usize + 4))
+                    .is_some_and(|sig| le_u32(sig) == CENTRAL_HEADER_SIGNATURE);
+                if !has_central_dir {
+                    return Err(e);
+                }
+
+                return Ok(EndOfCentralDirectory {
+                    zip64: None,
+                    eocd,
+                    stream_pos: location as u64,
+                    zero_base_offset: false,
+                    disk_starts: self.disk_starts.clone(),
+                });
+            }
+            Err(e) => return Err(e),
+        };
         let zip64_eocd = &data[(zip64_locator.directory_offset as usize).min(data.len())..];
-        let zip64_record = Zip64EndOfCentralDirectoryRecord::parse(zip64_eocd)?;
+        let zip64_record = Zip64EndOfCentralDirectoryRecord::parse(zip64_eocd)
+            .map_err(|e| e.at_offset(zip64_locator.directory_offset))?;
 
         Ok(EndOfCentralDirectory {
             zip64: Some(zip64_record),
             eocd,
             stream_pos: zip64_locator.directory_offset,
+            zero_base_offset: false,
+            disk_starts: self.disk_starts.clone(),
         })
     }
 
@@ -113,8 +209,20 @@ impl ZipLocator {
         &self,
         data: T,
     ) -> Result<ZipSliceArchive<T>, (T, Error)> {
-        match self.locate_in_byte_slice(data.as_ref()) {
-            Ok(eocd) => Ok(ZipSliceArchive { data, eocd }),
+        let located = self
+            .locate_in_byte_slice(data.as_ref())
+            .and_then(|mut eocd| {
+                if self.probe_base_offset {
+                    eocd.probe_zero_base_offset(&data.as_ref())?;
+                }
+                Ok(eocd)
+            });
+        match located {
+            Ok(eocd) => Ok(ZipSliceArchive {
+                data,
+                eocd,
+                max_entries: self.max_entries,
+            }),
             Err(e) => Err((data, e)),
         }
     }
@@ -210,15 +318,110 @@ impl ZipLocator {
     /// ```
     pub fn locate_in_reader<R>(
         &self,
-        mut reader: R,
+        reader: R,
         buffer: &mut [u8],
         end_offset: u64,
     ) -> Result<ZipArchive<R>, (R, Error)>
     where
         R: ReaderAt,
     {
-        let location_result =
-            find_end_of_central_dir(&mut reader, buffer, self.max_search_space, end_offset);
+        let mut archive = self.locate_eocd_in_reader(reader, buffer, end_offset)?;
+        if self.probe_base_offset {
+            if let Err(e) = archive.eocd.probe_zero_base_offset(&archive.reader) {
+                return Err((archive.reader, e));
+            }
+        }
+        Ok(archive)
+    }
+
+    /// Locates an archive in an [`AsyncReaderAt`], treating the specified
+    /// end offset as the starting point when searching backwards.
+    ///
+    /// The end of the reader is read into memory in one go and searched like
+    /// [`ZipLocator::locate_in_reader`], so the search space is capped to the
+    /// largest possible comment. A ZIP64 end of central directory record is
+    /// expected to directly precede its locator, as all writers place it.
+    ///
+    /// ```rust
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use rawzip::{TokioReaderAt, ZipLocator, RECOMMENDED_BUFFER_SIZE};
+    ///
+    /// let reader = TokioReaderAt::new(std::fs::read("assets/test.zip")?);
+    /// let end_offset = reader.get_ref().len() as u64;
+    /// let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    /// let archive = ZipLocator::new()
+    ///     .locate_in_async_reader(reader, &mut buffer, end_offset)
+    ///     .await
+    ///     .map_err(|(_, e)| e)?;
+    /// assert_eq!(archive.entries_hint(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`AsyncReaderAt`]: crate::AsyncReaderAt
+    #[cfg(feature = "tokio")]
+    pub async fn locate_in_async_reader<R>(
+        &self,
+        reader: R,
+        buffer: &mut [u8],
+        end_offset: u64,
+    ) -> Result<crate::AsyncZipArchive<R>, (R, Error)>
+    where
+        R: crate::AsyncReaderAt,
+    {
+        let max_record_len = EndOfCentralDirectoryRecordFixed::SIZE as u64 + u64::from(u16::MAX);
+        let search_space = self.max_search_space.min(max_record_len).min(end_offset);
+        let zip64_len = (Zip64EndOfCentralDirectoryLocatorRecord::SIZE
+            + Zip64EndOfCentralDirectoryRecord::SIZE) as u64;
+        let start = (end_offset - search_space).saturating_sub(zip64_len);
+        let len = end_offset - start + max_record_len;
+        let tail = match Prefetched::read(&reader, start, len).await {
+            Ok(tail) => tail,
+            Err(e) => return Err((reader, Error::io(e))),
+        };
+
+        let locator = ZipLocator {
+            max_search_space: search_space,
+            max_entries: self.max_entries,
+            probe_base_offset: false,
+            disk_starts: self.disk_starts.clone(),
+        };
+        let mut archive = match locator.locate_eocd_in_reader(tail, buffer, end_offset) {
+            Ok(archive) => archive,
+            Err((_, e)) => return Err((reader, e)),
+        };
+
+        if self.probe_base_offset {
+            let offset = u64::from(archive.eocd.eocd.central_dir_offset);
+            let probe = Prefetched::read(&reader, offset, ZipFileHeaderFixed::SIZE as u64).await;
+            let result = match probe {
+                Ok(header) => archive.eocd.probe_zero_base_offset(&header),
+                Err(e) => Err(Error::io(e)),
+            };
+            if let Err(e) = result {
+                return Err((reader, e));
+            }
+        }
+
+        Ok(crate::AsyncZipArchive::with_reader(archive, reader))
+    }
+
+    fn locate_eocd_in_reader<R>(
+        &self,
+        reader: R,
+        buffer: &mut [u8],
+        end_offset: u64,
+    ) -> Result<ZipArchive<R>, (R, Error)>
+    where
+        R: ReaderAt,
+    {
+        let location_result = find_end_of_central_dir_record_in_reader(
+            &reader,
+            buffer,
+            self.max_search_space,
+            end_offset,
+        );
 
         let (stream_pos, buffer_pos, buffer_valid_len) = match location_result {
             Ok(Some(location_tuple)) => location_tuple,
@@ -266,21 +469,32 @@ impl ZipLocator {
 
         let comment_len = eocd.comment_len as usize;
         let mut comment = vec![0u8; comment_len];
+        let buffered = end_of_central_directory.len().min(comment_len);
+        comment[..buffered].copy_from_slice(&end_of_central_directory[..buffered]);
 
-        // Unhappy path: entire comment not present in the buffer
-        if end_of_central_directory.len() < comment_len {
-            comment[..end_of_central_directory.len()].copy_from_slice(end_of_central_directory);
-            let pos = end_of_central_directory.len();
-            let result = reader.read_exact_at(
-                &mut comment[pos..],
-                stream_pos + EndOfCentralDirectoryRecordFixed::SIZE as u64 + pos as u64,
-            );
-
-            if let Err(e) = result {
+        // Unhappy path: if we needed to issue any reads since the original
+        // eocd or don't have enough data in the buffer, the zip64 locator must
+        // be read too
+        let eocd64l_size = Zip64EndOfCentralDirectoryLocatorRecord::SIZE;
+        let locator_in_buffer = !reader.is_marked() && eocd64l_size <= buffer_pos;
+        let read_locator = is_zip64 && !locator_in_buffer && eocd64l_size as u64 <= stream_pos;
+        let mut locator_data = [0u8; Zip64EndOfCentralDirectoryLocatorRecord::SIZE];
+
+        // Unhappy path: entire comment not present in the buffer. Reads of the
+        // rest of the comment and the zip64 locator are batched together.
+        let comment_offset =
+            stream_pos + EndOfCentralDirectoryRecordFixed::SIZE as u64 + buffered as u64;
+        let mut reads = Vec::with_capacity(2);
+        if buffered < comment_len {
+            reads.push((&mut comment[buffered..], comment_offset));
+        }
+        if read_locator {
+            reads.push((&mut locator_data[..], stream_pos - eocd64l_size as u64));
+        }
+        if !reads.is_empty() {
+            if let Err(e) = reader.read_at_vectored(&mut reads) {
                 return Err((reader.inner, Error::io(e)));
             }
-        } else {
-            comment.copy_from_slice(&end_of_central_directory[..comment_len]);
         }
 
         let comment = ZipString::new(comment);
@@ -292,41 +506,66 @@ impl ZipLocator {
                     zip64: None,
                     eocd,
                     stream_pos,
+                    zero_base_offset: false,
+                    disk_starts: self.disk_starts.clone(),
                 },
+                max_entries: self.max_entries,
             });
         }
 
-        let eocd64l_size = Zip64EndOfCentralDirectoryLocatorRecord::SIZE;
-
-        // Unhappy path: if we needed to issue any reads since the original
-        // eocd or don't have enough data in the buffer
-        let eocd64l_pos = if reader.is_marked() || eocd64l_size > buffer_pos {
-            if (eocd64l_size as u64) > stream_pos {
-                return Err((
-                    reader.inner,
-                    Error::from(ErrorKind::MissingZip64EndOfCentralDirectory),
-                ));
-            }
-
-            let read = reader.read_exact_at(
-                &mut buffer[..eocd64l_size],
-                stream_pos - eocd64l_size as u64,
-            );
-
-            match read {
-                Ok(_) => 0,
-                Err(e) => return Err((reader.inner, Error::io(e))),
-            }
+        let zip64_locator = if read_locator {
+            Zip64EndOfCentralDirectoryLocatorRecord::parse(&locator_data)
+        } else if locator_in_buffer {
+            Zip64EndOfCentralDirectoryLocatorRecord::parse(
+                &buffer[buffer_pos - eocd64l_size..buffer_pos],
+            )
         } else {
-            buffer_pos - eocd64l_size
+            Err(Error::from(ErrorKind::MissingZip64EndOfCentralDirectory))
         };
 
-        let zip64l_eocd = &buffer[eocd64l_pos..eocd64l_pos + eocd64l_size];
-        let zip64_locator = match Zip64EndOfCentralDirectoryLocatorRecord::parse(zip64l_eocd) {
+        let mut zip64_locator = match zip64_locator {
             Ok(locator) => locator,
+            Err(e) if is_missing_zip64_locator(&e) => {
+                let Some(cd_start) = eocd.zip64_fallback_central_dir_start(stream_pos) else {
+                    return Err((reader.inner, e));
+                };
+
+                let mut signature = [0u8; 4];
+                if let Err(e) = reader.read_exact_at(&mut signature, cd_start) {
+                    return Err((reader.inner, Error::io(e)));
+                }
+
+                if le_u32(&signature) != CENTRAL_HEADER_SIGNATURE {
+                    return Err((reader.inner, e));
+                }
+
+                return Ok(ZipArchive {
+                    reader: reader.inner,
+                    comment,
+                    eocd: EndOfCentralDirectory {
+                        zip64: None,
+                        eocd,
+                        stream_pos,
+                        zero_base_offset: false,
+                        disk_starts: self.disk_starts.clone(),
+                    },
+                    max_entries: self.max_entries,
+                });
+            }
             Err(e) => return Err((reader.inner, e)),
         };
 
+        if let Some(starts) = &self.disk_starts {
+            zip64_locator.directory_offset = match resolve_disk_offset(
+                starts,
+                zip64_locator.eocd_disk,
+                zip64_locator.directory_offset,
+            ) {
+                Ok(offset) => offset,
+                Err(e) => return Err((reader.inner, e)),
+            };
+        }
+
         let zip64_eocd_fixed_size = Zip64EndOfCentralDirectoryRecord::SIZE;
 
         // Unhappy path: zip64 eocd is not in the original buffer
@@ -356,7 +595,7 @@ impl ZipLocator {
         let zip64_eocd = &buffer[eocd64_start..eocd64_end];
         let zip64_record = match Zip64EndOfCentralDirectoryRecord::parse(zip64_eocd) {
             Ok(record) => record,
-            Err(e) => return Err((reader.inner, e)),
+            Err(e) => return Err((reader.inner, e.at_offset(zip64_locator.directory_offset))),
         };
 
         // todo: zip64 extensible data sector
@@ -368,7 +607,10 @@ impl ZipLocator {
                 zip64: Some(zip64_record),
                 eocd,
                 stream_pos: zip64_locator.directory_offset,
+                zero_base_offset: false,
+                disk_starts: self.disk_starts.clone(),
             },
+            max_entries: self.max_entries,
         })
     }
 }
@@ -404,6 +646,14 @@ where
             x => x,
         }
     }
+
+    fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+        self.inner.read_at_vectored(bufs)?;
+        if bufs.iter().any(|(buf, _)| !buf.is_empty()) {
+            *self.marked.borrow_mut() = true;
+        }
+        Ok(())
+    }
 }
 
 impl<T> std::io::Seek for Marker<T>
@@ -456,6 +706,26 @@ impl EndOfCentralDirectoryRecordFixed {
         Ok(result)
     }
 
+    /// Start of the central directory for a record that is flagged as zip64
+    /// only because it holds exactly 65535 entries.
+    ///
+    /// Returns `None` when any other field requires zip64 or the central
+    /// directory is too small to hold that many entries.
+    pub(crate) fn zip64_fallback_central_dir_start(&self, stream_pos: u64) -> Option<u64> {
+        const MIN_CENTRAL_HEADER_SIZE: u64 = 46;
+        let min_size = u64::from(u16::MAX) * MIN_CENTRAL_HEADER_SIZE;
+        if self.num_entries != u16::MAX
+            || self.total_entries != u16::MAX
+            || self.central_dir_offset == u32::MAX
+            || self.central_dir_size == u32::MAX
+            || u64::from(self.central_dir_size) < min_size
+        {
+            return None;
+        }
+
+        stream_pos.checked_sub(u64::from(self.central_dir_size))
+    }
+
     pub fn is_zip64(&self) -> bool {
         // https://github.com/zlib-ng/minizip-ng/blob/55db144e03027b43263e5ebcb599bf0878ba58de/mz_zip.c#L1011
         self.num_entries == u16::MAX || // 4.4.22
@@ -483,7 +753,7 @@ struct Zip64EndOfCentralDirectoryLocatorRecord {
 }
 
 impl Zip64EndOfCentralDirectoryLocatorRecord {
-    const SIZE: usize = 20;
+    pub(crate) const SIZE: usize = 20;
 
     pub fn parse(data: &[u8]) -> Result<Zip64EndOfCentralDirectoryLocatorRecord, Error> {
         if data.len() < Self::SIZE {
@@ -508,6 +778,15 @@ impl Zip64EndOfCentralDirectoryLocatorRecord {
     }
 }
 
+/// Whether a zip64 locator failed to parse because it is absent rather than
+/// because the underlying read failed.
+fn is_missing_zip64_locator(err: &Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::InvalidSignature { .. } | ErrorKind::MissingZip64EndOfCentralDirectory
+    )
+}
+
 pub(crate) fn find_end_of_central_dir_signature(
     data: &[u8],
     max_search_space: usize,
@@ -528,6 +807,28 @@ pub(crate) fn find_end_of_central_dir<T>(
 ) -> std::io::Result<Option<(u64, usize, usize)>>
 where
     T: ReaderAt,
+{
+    find_end_of_central_dir_where(reader, buffer, max_search_space, end_offset, |_, _| {
+        Ok(true)
+    })
+}
+
+/// Searches backwards like [`find_end_of_central_dir`] for the last signature
+/// that `accept` approves of, given its stream position and the data that
+/// follows it in the buffer.
+///
+/// Every candidate in a chunk is considered before the next chunk is read, so
+/// each byte is read once no matter how many candidates are rejected.
+fn find_end_of_central_dir_where<T, F>(
+    reader: T,
+    buffer: &mut [u8],
+    max_search_space: u64,
+    end_offset: u64,
+    mut accept: F,
+) -> std::io::Result<Option<(u64, usize, usize)>>
+where
+    T: ReaderAt,
+    F: FnMut(u64, &[u8]) -> std::io::Result<bool>,
 {
     if buffer.len() < END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES.len() {
         debug_assert!(false, "buffer not big enough to hold signature");
@@ -555,10 +856,18 @@ where
         reader.read_exact_at(&mut buffer[..read_size], offset)?;
         remaining -= read_size as u64;
 
-        let haystack = &buffer[..read_size + carry_over];
-        if let Some(i) = backwards_find(haystack, &END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES) {
+        let haystack_len = read_size + carry_over;
+        let mut search_end = haystack_len;
+        while let Some(i) =
+            backwards_find(&buffer[..search_end], &END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES)
+        {
             let stream_pos = (max_back + remaining) + (i as u64);
-            return Ok(Some((stream_pos, i, read_size + carry_over)));
+            if accept(stream_pos, &buffer[i..haystack_len])? {
+                return Ok(Some((stream_pos, i, haystack_len)));
+            }
+
+            // Exclude the last byte of the candidate's signature from the search
+            search_end = i + 3;
         }
 
         if remaining == 0 {
@@ -582,6 +891,86 @@ where
     }
 }
 
+/// Returns true if the comment of the end of central directory record at
+/// `pos` ends exactly at `end_offset`. A zip comment may itself contain the end
+/// of central directory signature, and only the real record satisfies this.
+fn comment_reaches_end(pos: u64, comment_len: u16, end_offset: u64) -> bool {
+    pos + EndOfCentralDirectoryRecordFixed::SIZE as u64 + u64::from(comment_len) == end_offset
+}
+
+/// Finds the end of central directory record, preferring the last candidate
+/// signature whose comment reaches the end of the data. Falls back to the last
+/// signature when no candidate does, as some archives have trailing data.
+pub(crate) fn find_end_of_central_dir_record(
+    data: &[u8],
+    max_search_space: usize,
+) -> Option<usize> {
+    let start_search = data.len().saturating_sub(max_search_space);
+    let last = find_end_of_central_dir_signature(data, max_search_space)?;
+
+    let mut candidate = Some(last);
+    while let Some(pos) = candidate {
+        let comment_len = data.get(pos + 20..pos + 22).map(le_u16);
+        if comment_len.is_some_and(|len| comment_reaches_end(pos as u64, len, data.len() as u64)) {
+            return Some(pos);
+        }
+
+        // Exclude the last byte of the candidate's signature from the search
+        candidate = backwards_find(
+            &data[start_search..pos + 3],
+            &END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES,
+        )
+        .map(|pos| pos + start_search);
+    }
+
+    Some(last)
+}
+
+/// The reader equivalent of [`find_end_of_central_dir_record`]. On success, the
+/// buffer holds the data around the returned record, as described by
+/// [`find_end_of_central_dir`].
+pub(crate) fn find_end_of_central_dir_record_in_reader<T>(
+    reader: &T,
+    buffer: &mut [u8],
+    max_search_space: u64,
+    end_offset: u64,
+) -> std::io::Result<Option<(u64, usize, usize)>>
+where
+    T: ReaderAt,
+{
+    let mut has_candidate = false;
+    let location = find_end_of_central_dir_where(
+        reader,
+        buffer,
+        max_search_space,
+        end_offset,
+        |stream_pos, data| {
+            has_candidate = true;
+            let record_end = stream_pos + EndOfCentralDirectoryRecordFixed::SIZE as u64;
+            let comment_len = match data.get(20..22) {
+                Some(data) => le_u16(data),
+                None if record_end <= end_offset => {
+                    let mut data = [0u8; 2];
+                    reader.read_exact_at(&mut data, stream_pos + 20)?;
+                    le_u16(&data)
+                }
+                None => return Ok(false),
+            };
+
+            // The buffer may stop short of the record's end when the record
+            // crosses read boundaries, which callers handle by reading the
+            // remainder
+            Ok(comment_reaches_end(stream_pos, comment_len, end_offset))
+        },
+    )?;
+
+    if location.is_some() || !has_candidate {
+        return Ok(location);
+    }
+
+    find_end_of_central_dir(reader, buffer, max_search_space, end_offset)
+}
+
 fn backwards_find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
     haystack
         .windows(needle.len())
@@ -666,6 +1055,86 @@ mod tests {
         }
     }
 
+    #[quickcheck]
+    fn test_find_end_of_central_dir_record_random(
+        mut data: Vec<u8>,
+        positions: Vec<u16>,
+        chunk_size: u16,
+    ) {
+        // Plant signatures, some of which have a comment that reaches the end
+        for pos in positions {
+            let pos = usize::from(pos);
+            if pos + EndOfCentralDirectoryRecordFixed::SIZE > data.len() {
+                continue;
+            }
+            data[pos..pos + 4].copy_from_slice(&END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES);
+            if pos % 2 == 0 {
+                let comment_len = data.len() - pos - EndOfCentralDirectoryRecordFixed::SIZE;
+                data[pos + 20..pos + 22].copy_from_slice(&(comment_len as u16).to_le_bytes());
+            }
+        }
+
+        let max_search_space = END_OF_CENTRAL_DIR_MAX_OFFSET;
+        let mem = find_end_of_central_dir_record(&data, max_search_space as usize);
+
+        let mut buffer = vec![0u8; chunk_size.max(4) as usize];
+        let reader = std::io::Cursor::new(&data);
+        let curse = find_end_of_central_dir_record_in_reader(
+            &reader,
+            &mut buffer,
+            max_search_space,
+            data.len() as u64,
+        )
+        .unwrap();
+
+        assert_eq!(mem.map(|x| x as u64), curse.map(|(a, _, _)| a));
+        if let Some((_, buffer_index, buffer_valid_len)) = curse {
+            assert_eq!(
+                buffer[buffer_index..buffer_index + 4],
+                END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES
+            );
+            assert!(buffer_valid_len <= buffer.len());
+        }
+    }
+
+    #[test]
+    fn test_find_end_of_central_dir_record_reads_linear() {
+        struct CountingReader<'a> {
+            data: &'a [u8],
+            read: std::cell::Cell<u64>,
+        }
+
+        impl ReaderAt for CountingReader<'_> {
+            fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+                let read = self.data.read_at(buf, offset)?;
+                self.read.set(self.read.get() + read as u64);
+                Ok(read)
+            }
+        }
+
+        // Every candidate is rejected, as no comment reaches the end
+        let data = END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES.repeat(1 << 18);
+        let reader = CountingReader {
+            data: &data,
+            read: std::cell::Cell::new(0),
+        };
+        let mut buffer = vec![0u8; crate::RECOMMENDED_BUFFER_SIZE];
+        let location = find_end_of_central_dir_record_in_reader(
+            &reader,
+            &mut buffer,
+            END_OF_CENTRAL_DIR_MAX_OFFSET,
+            data.len() as u64,
+        )
+        .unwrap();
+
+        let expected =
+            find_end_of_central_dir_record(&data, END_OF_CENTRAL_DIR_MAX_OFFSET as usize);
+        assert_eq!(location.map(|(pos, _, _)| pos as usize), expected);
+
+        // Roughly one pass over the data, plus a read of the fallback candidate
+        assert!(reader.read.get() < 2 * data.len() as u64);
+    }
+
     #[rstest]
     #[case(&[], 4, 1000, None)]
     #[case(&[6], 4, 1000, None)]
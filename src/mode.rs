@@ -1,3 +1,5 @@
+use crate::utils::le_u16;
+
 /// ZIP creator system constants used in version_made_by field
 pub(crate) const CREATOR_UNIX: u16 = 3;
 pub(crate) const CREATOR_MACOS: u16 = 19;
@@ -18,32 +20,77 @@ impl EntryMode {
         Self(value)
     }
 
+    /// Creates a mode from a Unix mode value (eg: `st_mode`).
+    ///
+    /// Modes without a file type are assumed to be regular files, matching how
+    /// modes are interpreted when reading an archive.
+    ///
+    /// ```rust
+    /// use rawzip::EntryMode;
+    ///
+    /// let mode = EntryMode::from_unix(0o644);
+    /// assert!(mode.is_file());
+    /// assert_eq!(mode.value(), 0o100644);
+    ///
+    /// let mode = EntryMode::from_unix(0o040755);
+    /// assert!(mode.is_dir());
+    /// ```
+    #[must_use]
+    pub const fn from_unix(mode: u32) -> Self {
+        Self(unix_mode_to_file_mode(mode))
+    }
+
     /// Returns the raw mode value
     #[must_use]
     pub const fn value(&self) -> u32 {
         self.0
     }
 
+    /// Returns true if this is a regular file.
+    #[must_use]
+    pub const fn is_file(&self) -> bool {
+        self.0 & S_IFMT == S_IFREG
+    }
+
+    /// Returns true if this is a directory.
+    #[must_use]
+    pub const fn is_dir(&self) -> bool {
+        self.0 & S_IFMT == S_IFDIR
+    }
+
     /// Returns true if this is a symbolic link.
     #[must_use]
     pub const fn is_symlink(&self) -> bool {
         self.0 & S_IFMT == S_IFLNK
     }
 
-    /// Returns the Unix permission bits (e.g., 0o755).
+    /// Returns the Unix permission bits (e.g., 0o755), which are the low 12
+    /// bits of the mode.
+    ///
+    /// The setuid, setgid, and sticky bits are included, while the file type
+    /// is not. Mask with `0o777` to drop the special bits (eg: when extracting
+    /// untrusted archives).
+    ///
+    /// ```rust
+    /// use rawzip::EntryMode;
+    ///
+    /// let mode = EntryMode::from_unix(0o104755);
+    /// assert_eq!(mode.permissions(), 0o4755);
+    /// assert_eq!(mode.permissions() & 0o777, 0o755);
+    /// ```
     #[must_use]
     pub const fn permissions(&self) -> u32 {
-        self.0 & 0o777
+        self.0 & 0o7777
     }
 }
 
 /// Unix file type and permission constants
-const S_IFMT: u32 = 0o170000; // File type mask
+pub(crate) const S_IFMT: u32 = 0o170000; // File type mask
 const S_IFSOCK: u32 = 0o140000; // Socket
-const S_IFLNK: u32 = 0o120000; // Symbolic link
+pub(crate) const S_IFLNK: u32 = 0o120000; // Symbolic link
 const S_IFREG: u32 = 0o100000; // Regular file
 const S_IFBLK: u32 = 0o060000; // Block device
-const S_IFDIR: u32 = 0o040000; // Directory
+pub(crate) const S_IFDIR: u32 = 0o040000; // Directory
 const S_IFCHR: u32 = 0o020000; // Character device
 const S_IFIFO: u32 = 0o010000; // FIFO
 const S_ISUID: u32 = 0o004000; // Set user ID
@@ -55,7 +102,7 @@ const MSDOS_DIR: u32 = 0x10;
 const MSDOS_READONLY: u32 = 0x01;
 
 /// Converts Unix mode to file mode
-pub(crate) fn unix_mode_to_file_mode(m: u32) -> u32 {
+pub(crate) const fn unix_mode_to_file_mode(m: u32) -> u32 {
     let mut mode = m & 0o777; // Basic permissions
 
     // Set file type bits based on Unix mode
@@ -93,3 +140,96 @@ pub(crate) fn msdos_mode_to_file_mode(m: u32) -> u32 {
         S_IFREG | 0o666
     }
 }
+
+// Extra field IDs that carry the Unix user and group IDs
+pub(crate) const UNIX_OWNER_ID: u16 = 0x7875; // "ux" - Info-ZIP Unix, new
+pub(crate) const UNIX_TIMESTAMP_OWNER_ID: u16 = 0x5855; // "UX" - Info-ZIP Unix, original
+
+/// Parses the uid and gid of the 0x7875 extra field: a version byte followed
+/// by the size and value of each ID. IDs that don't fit in 32 bits are
+/// rejected.
+pub(crate) fn parse_unix_owner(data: &[u8]) -> Option<(u32, u32)> {
+    fn id(data: &[u8]) -> Option<(u32, &[u8])> {
+        let (&size, rest) = data.split_first()?;
+        let size = usize::from(size);
+        let (value, rest) = (rest.get(..size)?, &rest[size..]);
+        let value = value.iter().rev().try_fold(0u32, |acc, &b| {
+            acc.checked_mul(256)?.checked_add(u32::from(b))
+        });
+        Some((value?, rest))
+    }
+
+    let (&version, rest) = data.split_first()?;
+    if version != 1 {
+        return None;
+    }
+
+    let (uid, rest) = id(rest)?;
+    let (gid, _) = id(rest)?;
+    Some((uid, gid))
+}
+
+/// Parses the uid and gid of the 0x5855 extra field, which follow its access
+/// and modification times as 16-bit values. The IDs are optional, so the
+/// field may end after the times.
+pub(crate) fn parse_unix_timestamp_owner(data: &[u8]) -> Option<(u32, u32)> {
+    let ids = data.get(8..12)?;
+    Some((u32::from(le_u16(&ids[0..2])), u32::from(le_u16(&ids[2..4]))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0o644, true, false, false)]
+    #[case(0o100755, true, false, false)]
+    #[case(0o040755, false, true, false)]
+    #[case(0o120777, false, false, true)]
+    #[case(0o060644, false, false, false)]
+    #[case(0o010644, false, false, false)]
+    fn test_entry_mode_predicates(
+        #[case] mode: u32,
+        #[case] is_file: bool,
+        #[case] is_dir: bool,
+        #[case] is_symlink: bool,
+    ) {
+        let mode = EntryMode::from_unix(mode);
+        assert_eq!(mode.is_file(), is_file);
+        assert_eq!(mode.is_dir(), is_dir);
+        assert_eq!(mode.is_symlink(), is_symlink);
+    }
+
+    #[test]
+    fn test_entry_mode_from_unix_special_bits() {
+        let mode = EntryMode::from_unix(0o104755);
+        assert_eq!(mode.value(), 0o104755);
+        assert_eq!(mode.permissions(), 0o4755);
+    }
+
+    #[rstest]
+    #[case(&[1, 4, 0xe8, 0x03, 0, 0, 4, 0x64, 0, 0, 0], Some((1000, 100)))]
+    #[case(&[1, 2, 0xe8, 0x03, 1, 0x64], Some((1000, 100)))]
+    #[case(&[1, 0, 0], Some((0, 0)))]
+    #[case(&[1, 8, 1, 0, 0, 0, 0, 0, 0, 0, 1, 5], Some((1, 5)))]
+    #[case(&[1, 8, 0, 0, 0, 0, 1, 0, 0, 0, 1, 5], None)]
+    #[case(&[2, 1, 1, 1, 1], None)]
+    #[case(&[1, 4, 0xe8, 0x03], None)]
+    #[case(&[], None)]
+    fn test_parse_unix_owner(#[case] data: &[u8], #[case] expected: Option<(u32, u32)>) {
+        assert_eq!(parse_unix_owner(data), expected);
+    }
+
+    #[test]
+    fn test_parse_unix_timestamp_owner() {
+        let mut data = vec![];
+        data.extend_from_slice(&0u32.to_le_bytes()); // Access time
+        data.extend_from_slice(&1283652721u32.to_le_bytes()); // Modification time
+        assert_eq!(parse_unix_timestamp_owner(&data), None);
+
+        data.extend_from_slice(&501u16.to_le_bytes()); // uid
+        data.extend_from_slice(&20u16.to_le_bytes()); // gid
+        assert_eq!(parse_unix_timestamp_owner(&data), Some((501, 20)));
+    }
+}
@@ -87,7 +87,7 @@
 //! UTF-8 encoding in ZIP files (beyond the default CP-437 encoding). This
 //! information is used internally when creating ZIP archives.
 
-use crate::{Error, ZipStr};
+use crate::{errors::ErrorKind, Error, ZipStr};
 use std::borrow::Cow;
 
 /// Raw path data directly from a ZIP archive.
@@ -145,6 +145,43 @@ impl AsRef<str> for NormalizedPathBuf {
     }
 }
 
+/// The reason a raw path was rejected by [`ZipFilePath::check_safe()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathViolation {
+    /// The path starts with a separator (`/foo` or `\\server\share`)
+    AbsolutePath,
+
+    /// The path contains a `..` component
+    ParentTraversal,
+
+    /// The path starts with a drive letter (`C:`)
+    DriveLetter,
+
+    /// The path contains a colon elsewhere, which on Windows names an NTFS
+    /// alternate data stream (`file.txt:stream`) or a device (`COM1:`)
+    AlternateDataStream,
+
+    /// The path is not valid UTF-8
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for PathViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathViolation::AbsolutePath => write!(f, "path is absolute"),
+            PathViolation::ParentTraversal => write!(f, "path contains a parent directory"),
+            PathViolation::DriveLetter => write!(f, "path contains a drive letter"),
+            PathViolation::AlternateDataStream => {
+                write!(f, "path contains an alternate data stream")
+            }
+            PathViolation::InvalidUtf8 => write!(f, "path is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for PathViolation {}
+
 /// Type-safe wrapper for ZIP archive file paths.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ZipFilePath<R> {
@@ -199,6 +236,37 @@ impl ZipFilePath<()> {
         }
     }
 
+    /// Creates a normalized path like [`ZipFilePath::from_str`], but first
+    /// rejects paths with more than `max_components` components.
+    ///
+    /// Components are counted before normalization (eg: each `..` counts),
+    /// which bounds the work spent on hostile paths.
+    ///
+    /// ```rust
+    /// use rawzip::path::ZipFilePath;
+    ///
+    /// assert!(ZipFilePath::from_str_limited("a/b/c.txt", 3).is_ok());
+    /// assert!(ZipFilePath::from_str_limited("a/b/../c.txt", 3).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if the path has too many
+    /// components.
+    pub fn from_str_limited(
+        name: &str,
+        max_components: usize,
+    ) -> Result<ZipFilePath<NormalizedPath<'_>>, Error> {
+        let mut components = name.split(['/', '\\']).filter(|c| !c.is_empty());
+        if components.nth(max_components).is_some() {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: format!("path exceeds the maximum of {} components", max_components),
+            }));
+        }
+
+        Ok(Self::from_str(name))
+    }
+
     fn normalize_alloc(s: &str) -> String {
         // 4.4.17.1 All slashes MUST be forward slashes '/'
         let s = s.replace('\\', "/");
@@ -278,6 +346,148 @@ where
 
         false
     }
+
+    /// Returns true if `prefix` is an ancestor of (or equal to) this path,
+    /// comparing whole path components.
+    ///
+    /// Leading and trailing slashes on the prefix are ignored, so `foo` and
+    /// `foo/` are both prefixes of `foo/bar`, but neither is a prefix of
+    /// `foobar`. An empty prefix (or `/`) is the root and matches every path.
+    ///
+    /// ```rust
+    /// use rawzip::path::ZipFilePath;
+    ///
+    /// let path = ZipFilePath::from_str("foo/bar/baz.txt");
+    /// assert!(path.starts_with("foo"));
+    /// assert!(path.starts_with("foo/bar/"));
+    /// assert!(!path.starts_with("fo"));
+    /// assert!(path.starts_with(""));
+    /// ```
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        let prefix = prefix.trim_matches('/');
+        if prefix.is_empty() {
+            return true;
+        }
+
+        match self.data.as_ref().strip_prefix(prefix) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over the components of the path.
+    ///
+    /// As the path is normalized, components are never empty, `.`, or `..`.
+    /// Directory paths do not yield a trailing empty component.
+    ///
+    /// ```rust
+    /// use rawzip::path::ZipFilePath;
+    ///
+    /// let path = ZipFilePath::from_str("foo/bar/");
+    /// assert_eq!(path.components().collect::<Vec<_>>(), ["foo", "bar"]);
+    /// ```
+    pub fn components(&self) -> impl Iterator<Item = &str> + '_ {
+        self.data.as_ref().split('/').filter(|c| !c.is_empty())
+    }
+
+    /// Joins this path onto a base directory, such as the destination of an
+    /// extraction, using the platform's path separator.
+    ///
+    /// Prefer this over [`Path::join`](std::path::Path::join) with the path
+    /// as a string. Each component is appended on its own, and any component
+    /// the platform would interpret as a root or prefix (eg: `C:` on
+    /// Windows) is dropped, so the result always stays under `base`.
+    ///
+    /// ```rust
+    /// use rawzip::path::ZipFilePath;
+    /// use std::path::Path;
+    ///
+    /// let path = ZipFilePath::from_str("../dir/file.txt");
+    /// let joined = path.join_onto(Path::new("out"));
+    /// assert_eq!(joined, Path::new("out").join("dir").join("file.txt"));
+    /// ```
+    pub fn join_onto(&self, base: &std::path::Path) -> std::path::PathBuf {
+        let mut result = base.to_path_buf();
+        for component in self.components() {
+            for part in std::path::Path::new(component).components() {
+                if let std::path::Component::Normal(part) = part {
+                    result.push(part);
+                }
+            }
+        }
+
+        debug_assert!(result.starts_with(base));
+        result
+    }
+
+    /// Returns true if any component of the path is a reserved device name
+    /// on Windows, such as `CON`, `NUL`, `COM1`, or `LPT1`.
+    ///
+    /// Names are compared case-insensitively, and a component is reserved
+    /// even with an extension (eg: `con.txt`). Extracting such entries on
+    /// Windows opens the device instead of creating a file.
+    ///
+    /// ```rust
+    /// use rawzip::path::ZipFilePath;
+    ///
+    /// assert!(ZipFilePath::from_str("logs/nul.txt").has_windows_reserved_name());
+    /// assert!(!ZipFilePath::from_str("logs/null.txt").has_windows_reserved_name());
+    /// ```
+    pub fn has_windows_reserved_name(&self) -> bool {
+        self.components().any(is_windows_reserved_name)
+    }
+
+    /// Returns true if this path equals `other` when ignoring ASCII case.
+    ///
+    /// Entries such as `Foo.txt` and `foo.txt` collide when extracted onto a
+    /// case-insensitive filesystem (eg: the defaults on macOS and Windows).
+    /// Only ASCII letters are folded, so non-ASCII characters must match
+    /// exactly.
+    ///
+    /// ```rust
+    /// use rawzip::path::ZipFilePath;
+    ///
+    /// let path = ZipFilePath::from_str("Docs/README.md");
+    /// assert!(path.eq_ignore_ascii_case("docs/readme.md"));
+    /// assert!(!path.eq_ignore_ascii_case("docs/readme.txt"));
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.data.as_ref().eq_ignore_ascii_case(other)
+    }
+
+    /// Returns an owned copy of the path with ASCII letters lowercased.
+    ///
+    /// Paths that compare equal with
+    /// [`eq_ignore_ascii_case`](ZipFilePath::eq_ignore_ascii_case) fold to
+    /// the same path, so the result can key a set to detect collisions.
+    ///
+    /// ```rust
+    /// use rawzip::path::ZipFilePath;
+    ///
+    /// let path = ZipFilePath::from_str("Docs/README.md");
+    /// assert_eq!(path.fold_case().as_ref(), "docs/readme.md");
+    /// ```
+    pub fn fold_case(&self) -> ZipFilePath<NormalizedPathBuf> {
+        ZipFilePath {
+            data: NormalizedPathBuf(self.data.as_ref().to_ascii_lowercase()),
+        }
+    }
+}
+
+/// Checks a single path component against the Windows reserved device names.
+/// Windows ignores everything after the first dot and any trailing spaces.
+fn is_windows_reserved_name(component: &str) -> bool {
+    let stem = component.split('.').next().unwrap_or_default();
+    let stem = stem.trim_end_matches(' ').as_bytes();
+    match stem {
+        [a, b, c] => ["CON", "PRN", "AUX", "NUL"]
+            .iter()
+            .any(|name| name.as_bytes().eq_ignore_ascii_case(&[*a, *b, *c])),
+        [a, b, c, b'1'..=b'9'] => ["COM", "LPT"]
+            .iter()
+            .any(|name| name.as_bytes().eq_ignore_ascii_case(&[*a, *b, *c])),
+        _ => false,
+    }
 }
 
 impl AsRef<[u8]> for ZipFilePath<RawPath<'_>> {
@@ -301,6 +511,92 @@ impl<'a> ZipFilePath<RawPath<'a>> {
         let name = std::str::from_utf8(raw_data.as_bytes()).map_err(Error::utf8)?;
         Ok(ZipFilePath::from_str(name))
     }
+
+    /// Normalizes this raw path like [`ZipFilePath::try_normalize()`], but
+    /// rejects paths with more than `max_components` components.
+    ///
+    /// See [`ZipFilePath::from_str_limited`] for how components are counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file path contains invalid UTF-8 sequences or
+    /// has too many components.
+    pub fn try_normalize_limited(
+        self,
+        max_components: usize,
+    ) -> Result<ZipFilePath<NormalizedPath<'a>>, Error> {
+        let raw_data = self.data.0;
+        let name = std::str::from_utf8(raw_data.as_bytes()).map_err(Error::utf8)?;
+        ZipFilePath::from_str_limited(name, max_components)
+    }
+
+    /// Returns true if the archive declared this path as absolute, starting
+    /// with a `/` or `\` (including UNC paths like `\\server\share`).
+    ///
+    /// Normalization strips the leading separators, so this reports what the
+    /// archive declared for policy decisions and reporting.
+    ///
+    /// ```rust
+    /// use rawzip::path::ZipFilePath;
+    ///
+    /// assert!(ZipFilePath::from_bytes(b"/etc/passwd").is_absolute());
+    /// assert!(!ZipFilePath::from_bytes(b"etc/passwd").is_absolute());
+    /// ```
+    #[inline]
+    pub fn is_absolute(&self) -> bool {
+        matches!(self.data.0.as_bytes().first(), Some(b'/' | b'\\'))
+    }
+
+    /// Returns true if the archive declared this path with a leading drive
+    /// letter, like `C:\x` or `c:x`.
+    ///
+    /// Normalization strips the drive letter, so this reports what the
+    /// archive declared for policy decisions and reporting.
+    #[inline]
+    pub fn has_drive_letter(&self) -> bool {
+        matches!(self.data.0.as_bytes(), [letter, b':', ..] if letter.is_ascii_alphabetic())
+    }
+
+    /// Validates this raw path, rejecting it instead of sanitizing it when it
+    /// attempts to escape the extraction directory.
+    ///
+    /// Unlike [`ZipFilePath::try_normalize()`], which silently strips
+    /// offending components, this reports what the archive tried to do. Paths
+    /// that pass are normalized as usual (eg: backslashes become forward
+    /// slashes), which never changes where they resolve to.
+    ///
+    /// ```rust
+    /// use rawzip::path::{PathViolation, ZipFilePath};
+    ///
+    /// let path = ZipFilePath::from_bytes(b"dir\\file.txt").check_safe()?;
+    /// assert_eq!(path.as_ref(), "dir/file.txt");
+    ///
+    /// let err = ZipFilePath::from_bytes(b"../etc/passwd").check_safe().unwrap_err();
+    /// assert_eq!(err, PathViolation::ParentTraversal);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn check_safe(&self) -> Result<ZipFilePath<NormalizedPath<'a>>, PathViolation> {
+        let name =
+            std::str::from_utf8(self.data.0.as_bytes()).map_err(|_| PathViolation::InvalidUtf8)?;
+
+        if self.has_drive_letter() {
+            return Err(PathViolation::DriveLetter);
+        }
+
+        if name.contains(':') {
+            return Err(PathViolation::AlternateDataStream);
+        }
+
+        if name.starts_with(['/', '\\']) {
+            return Err(PathViolation::AbsolutePath);
+        }
+
+        if name.split(['/', '\\']).any(|component| component == "..") {
+            return Err(PathViolation::ParentTraversal);
+        }
+
+        Ok(ZipFilePath::from_str(name))
+    }
 }
 
 impl AsRef<str> for ZipFilePath<NormalizedPath<'_>> {
@@ -385,6 +681,89 @@ mod tests {
         assert!(ZipFilePath::from_bytes(input).try_normalize().is_err());
     }
 
+    #[rstest]
+    #[case("a/b/c", 3, true)]
+    #[case("a/b/c/", 3, true)]
+    #[case("/a//b\\c", 3, true)]
+    #[case("a/b/c/d", 3, false)]
+    #[case("a/../b/c", 3, false)]
+    #[case("", 0, true)]
+    #[case("a", 0, false)]
+    fn test_from_str_limited(#[case] input: &str, #[case] max: usize, #[case] ok: bool) {
+        assert_eq!(ZipFilePath::from_str_limited(input, max).is_ok(), ok);
+        let raw = ZipFilePath::from_bytes(input.as_bytes());
+        assert_eq!(raw.try_normalize_limited(max).is_ok(), ok);
+    }
+
+    #[test]
+    fn test_from_str_limited_hostile_depth() {
+        let name = "../".repeat(100_000) + "etc/passwd";
+        let err = ZipFilePath::from_str_limited(&name, 256).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+
+        let name = "a/".repeat(256);
+        let path = ZipFilePath::from_str_limited(&name, 256).unwrap();
+        assert_eq!(path.components().count(), 256);
+    }
+
+    #[rstest]
+    #[case(b"test.txt", "test.txt")]
+    #[case(b"dir\\test.txt", "dir/test.txt")]
+    #[case(b"dir//./test.txt", "dir/test.txt")]
+    #[case(b"dir/..test.txt", "dir/..test.txt")]
+    #[case(b"dir/", "dir/")]
+    fn test_check_safe(#[case] input: &[u8], #[case] expected: &str) {
+        let path = ZipFilePath::from_bytes(input).check_safe().unwrap();
+        assert_eq!(path.as_ref(), expected);
+    }
+
+    #[rstest]
+    #[case(b"/etc/passwd", PathViolation::AbsolutePath)]
+    #[case(b"\\\\server\\share", PathViolation::AbsolutePath)]
+    #[case(b"../test.txt", PathViolation::ParentTraversal)]
+    #[case(b"dir/../test.txt", PathViolation::ParentTraversal)]
+    #[case(b"dir\\..\\..\\test.txt", PathViolation::ParentTraversal)]
+    #[case(b"dir/..", PathViolation::ParentTraversal)]
+    #[case(b"C:\\Windows\\system32", PathViolation::DriveLetter)]
+    #[case(b"C:/test.txt", PathViolation::DriveLetter)]
+    #[case(b"c:test.txt", PathViolation::DriveLetter)]
+    #[case(b"file.txt:stream", PathViolation::AlternateDataStream)]
+    #[case(b"1:test.txt", PathViolation::AlternateDataStream)]
+    #[case(b"dir/C:test.txt", PathViolation::AlternateDataStream)]
+    #[case(b"\xff\xfe.txt", PathViolation::InvalidUtf8)]
+    fn test_check_safe_violation(#[case] input: &[u8], #[case] expected: PathViolation) {
+        let path = ZipFilePath::from_bytes(input);
+        assert_eq!(path.check_safe().unwrap_err(), expected);
+
+        // Sanitizing remains available for the same paths
+        assert_eq!(
+            path.try_normalize().is_err(),
+            expected == PathViolation::InvalidUtf8
+        );
+    }
+
+    #[rstest]
+    #[case(b"/etc/passwd", true, false)]
+    #[case(b"\\x", true, false)]
+    #[case(b"\\\\server\\share", true, false)]
+    #[case(b"C:\\x", false, true)]
+    #[case(b"c:x", false, true)]
+    #[case(b"C:/x", false, true)]
+    #[case(b"1:x", false, false)]
+    #[case(b"dir/C:x", false, false)]
+    #[case(b"dir/file.txt", false, false)]
+    #[case(b"./file.txt", false, false)]
+    #[case(b"", false, false)]
+    fn test_declared_absolute_and_drive_letter(
+        #[case] input: &[u8],
+        #[case] absolute: bool,
+        #[case] drive_letter: bool,
+    ) {
+        let path = ZipFilePath::from_bytes(input);
+        assert_eq!(path.is_absolute(), absolute);
+        assert_eq!(path.has_drive_letter(), drive_letter);
+    }
+
     #[rstest]
     #[case("test.txt", false)]
     #[case("hello_world", false)]
@@ -428,6 +807,127 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("foo/bar", "foo", true)]
+    #[case("foo/bar", "foo/", true)]
+    #[case("foo/bar", "/foo", true)]
+    #[case("foo/bar", "foo/bar", true)]
+    #[case("foo/bar/", "foo/bar", true)]
+    #[case("foo/bar", "foo/bar/", true)]
+    #[case("foo/", "foo", true)]
+    #[case("foo/bar/baz.txt", "foo/bar", true)]
+    #[case("foobar", "foo", false)]
+    #[case("foo/barbaz", "foo/bar", false)]
+    #[case("foo", "foo/bar", false)]
+    #[case("bar/foo", "foo", false)]
+    #[case("foo/bar", "", true)]
+    #[case("foo/bar", "/", true)]
+    #[case("", "", true)]
+    #[case("", "foo", false)]
+    fn test_starts_with(#[case] path: &str, #[case] prefix: &str, #[case] expected: bool) {
+        let path = ZipFilePath::from_str(path);
+        assert_eq!(path.starts_with(prefix), expected);
+        assert_eq!(path.into_owned().starts_with(prefix), expected);
+    }
+
+    #[rstest]
+    #[case("file.txt", &["file.txt"])]
+    #[case("dir/", &["dir"])]
+    #[case("foo/bar/baz.txt", &["foo", "bar", "baz.txt"])]
+    #[case("foo/bar/", &["foo", "bar"])]
+    #[case("/foo/./bar/../baz", &["foo", "baz"])]
+    #[case("", &[])]
+    fn test_components(#[case] path: &str, #[case] expected: &[&str]) {
+        let path = ZipFilePath::from_str(path);
+        assert_eq!(path.components().collect::<Vec<_>>(), expected);
+        assert_eq!(path.into_owned().components().collect::<Vec<_>>(), expected);
+    }
+
+    #[rstest]
+    #[case("con.txt", true)]
+    #[case("nul", true)]
+    #[case("a/COM1/b", true)]
+    #[case("Lpt9.tar.gz", true)]
+    #[case("aux .txt", true)]
+    #[case("prn/", true)]
+    #[case("file.txt", false)]
+    #[case("console.txt", false)]
+    #[case("com0", false)]
+    #[case("com10", false)]
+    #[case("a/b/nul_device", false)]
+    #[case("", false)]
+    fn test_has_windows_reserved_name(#[case] path: &str, #[case] expected: bool) {
+        let path = ZipFilePath::from_str(path);
+        assert_eq!(path.has_windows_reserved_name(), expected);
+    }
+
+    #[rstest]
+    #[case("Foo.txt", "foo.txt", true)]
+    #[case("DIR/Sub/", "dir/sub/", true)]
+    #[case("foo.txt", "foo.txt", true)]
+    #[case("foo.txt", "foo.txt/", false)]
+    #[case("foo.txt", "bar.txt", false)]
+    #[case("Ä.txt", "ä.txt", false)]
+    #[case("ÄB.txt", "Äb.txt", true)]
+    #[case("", "", true)]
+    fn test_eq_ignore_ascii_case(#[case] path: &str, #[case] other: &str, #[case] expected: bool) {
+        let path = ZipFilePath::from_str(path);
+        assert_eq!(path.eq_ignore_ascii_case(other), expected);
+        assert_eq!(path.into_owned().eq_ignore_ascii_case(other), expected);
+    }
+
+    #[rstest]
+    #[case("Foo.TXT", "foo.txt")]
+    #[case("Dir/Sub/", "dir/sub/")]
+    #[case("ÄÖÜ/Straße", "ÄÖÜ/straße")]
+    #[case("日本語.txt", "日本語.txt")]
+    #[case("", "")]
+    fn test_fold_case(#[case] path: &str, #[case] expected: &str) {
+        let path = ZipFilePath::from_str(path);
+        assert_eq!(path.fold_case().as_ref(), expected);
+        assert_eq!(path.into_owned().fold_case().as_ref(), expected);
+    }
+
+    #[rstest]
+    #[case("file.txt", &["file.txt"])]
+    #[case("dir/sub/file.txt", &["dir", "sub", "file.txt"])]
+    #[case("dir/", &["dir"])]
+    #[case("../../etc/passwd", &["etc", "passwd"])]
+    #[case("C:\\Windows\\system32", &["Windows", "system32"])]
+    #[case("\\\\server\\share\\file.txt", &["server", "share", "file.txt"])]
+    #[case("", &[])]
+    fn test_join_onto(#[case] path: &str, #[case] expected: &[&str]) {
+        #[cfg(unix)]
+        let base = std::path::Path::new("/tmp/extract");
+        #[cfg(windows)]
+        let base = std::path::Path::new("C:\\Users\\extract");
+
+        let mut expected_path = base.to_path_buf();
+        expected_path.extend(expected);
+
+        let path = ZipFilePath::from_str(path);
+        let joined = path.join_onto(base);
+        assert_eq!(joined, expected_path);
+        assert!(joined.starts_with(base));
+        assert_eq!(path.into_owned().join_onto(base), expected_path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_join_onto_unix_separator() {
+        let path = ZipFilePath::from_str("dir\\file.txt");
+        let joined = path.join_onto(std::path::Path::new("/tmp/extract"));
+        assert_eq!(joined.to_str(), Some("/tmp/extract/dir/file.txt"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_join_onto_windows_separator() {
+        let path = ZipFilePath::from_str("dir/file.txt");
+        let joined = path.join_onto(std::path::Path::new("C:\\extract"));
+        assert_eq!(joined.to_str(), Some("C:\\extract\\dir\\file.txt"));
+    }
+
     #[test]
     fn test_path_lifetime_test() {
         let normalized_path = ZipFilePath::from_bytes(b"test.txt")
@@ -33,6 +33,61 @@ pub trait ReaderAt {
         }
         Ok(())
     }
+
+    /// Fills each buffer with the bytes at its paired offset.
+    ///
+    /// The default implementation issues a [`read_exact_at`] per buffer.
+    /// Backends where each request is expensive (eg: object storage) can
+    /// override this to fetch all the ranges in a single coalesced request.
+    /// Locating an archive uses it to fetch the rest of the archive comment
+    /// and the ZIP64 locator together when they weren't already read, and the
+    /// wrappers in this crate forward it to the reader they wrap.
+    ///
+    /// [`read_exact_at`]: ReaderAt::read_exact_at
+    ///
+    /// ```rust
+    /// use rawzip::ReaderAt;
+    ///
+    /// let data = b"Hello, world!".to_vec();
+    /// let (mut head, mut tail) = ([0u8; 5], [0u8; 6]);
+    /// data.read_at_vectored(&mut [(&mut head, 0), (&mut tail, 7)])?;
+    /// assert_eq!(&head, b"Hello");
+    /// assert_eq!(&tail, b"world!");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+        for (buf, offset) in bufs.iter_mut() {
+            self.read_exact_at(buf, *offset)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reports the total length of a [`ReaderAt`], when it is known without
+/// seeking.
+///
+/// In-memory data and files know their length, so an archive can be located
+/// in them with [`ZipArchive::from_reader_at`] without first seeking to the
+/// end. Memory maps (eg: `memmap2::Mmap`) dereference to a byte slice, so
+/// they can be read through `&[u8]`.
+///
+/// ```rust
+/// use rawzip::ReaderLen;
+///
+/// let data = b"Hello, world!";
+/// assert_eq!(ReaderLen::len(&&data[..]), Some(13));
+/// ```
+///
+/// [`ZipArchive::from_reader_at`]: crate::ZipArchive::from_reader_at
+pub trait ReaderLen {
+    /// Returns the number of bytes in the reader, or `None` if it is unknown.
+    fn len(&self) -> Option<u64>;
+
+    /// Returns whether the reader has no bytes, or `None` if its length is
+    /// unknown.
+    fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
 }
 
 pub(crate) trait ReaderAtExt {
@@ -109,6 +164,20 @@ impl ReaderAt for FileReader {
     }
 }
 
+impl ReaderLen for FileReader {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        self.0.len()
+    }
+}
+
+impl ReaderLen for std::fs::File {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        self.metadata().ok().map(|metadata| metadata.len())
+    }
+}
+
 impl std::io::Seek for FileReader {
     #[inline]
     fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
@@ -161,6 +230,32 @@ where
         lock.seek(std::io::SeekFrom::Start(original_position))?;
         result
     }
+
+    /// Reads every range under a single lock.
+    fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+        let mut lock = self.0.lock().unwrap();
+        let original_position = lock.stream_position()?;
+        let mut result = Ok(());
+        for (buf, offset) in bufs.iter_mut() {
+            result = lock
+                .seek(std::io::SeekFrom::Start(*offset))
+                .and_then(|_| lock.read_exact(buf));
+            if result.is_err() {
+                break;
+            }
+        }
+        lock.seek(std::io::SeekFrom::Start(original_position))?;
+        result
+    }
+}
+
+impl<R> ReaderLen for MutexReader<R>
+where
+    R: ReaderLen,
+{
+    fn len(&self) -> Option<u64> {
+        self.0.lock().unwrap().len()
+    }
 }
 
 impl<R> std::io::Read for MutexReader<R>
@@ -181,11 +276,534 @@ where
     }
 }
 
+/// A [`ReaderAt`] over a seekable reader that only seeks when a read does
+/// not continue from where the previous one left off.
+///
+/// Unlike the reader used by
+/// [`ZipArchive::from_seekable`](crate::ZipArchive::from_seekable), the
+/// stream position is not restored after each read. Sequential reads, like
+/// iterating the central directory or streaming an entry, then issue no
+/// seeks at all, which keeps the buffer of a [`std::io::BufReader`] intact
+/// instead of discarding it on every read.
+#[derive(Debug)]
+pub struct SeekingReader<R>(std::sync::Mutex<SeekingReaderState<R>>);
+
+#[derive(Debug)]
+struct SeekingReaderState<R> {
+    inner: R,
+
+    /// The stream position of `inner`, if known.
+    position: Option<u64>,
+}
+
+impl<R> SeekingReader<R> {
+    /// Creates a reader whose position will be established on first read.
+    pub fn new(inner: R) -> Self {
+        Self(std::sync::Mutex::new(SeekingReaderState {
+            inner,
+            position: None,
+        }))
+    }
+
+    /// Consumes the reader, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.0.into_inner().unwrap().inner
+    }
+}
+
+impl<R> ReaderAt for SeekingReader<R>
+where
+    R: std::io::Read + std::io::Seek,
+{
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        let mut state = self.0.lock().unwrap();
+        if state.position != Some(offset) {
+            state.position = None;
+            state.inner.seek(std::io::SeekFrom::Start(offset))?;
+        }
+
+        let read = state.inner.read(buf)?;
+        state.position = Some(offset + read as u64);
+        Ok(read)
+    }
+}
+
+/// A [`ReaderAt`] backed by a positioned-read function.
+///
+/// Useful for integrating storage backends (e.g., HTTP range requests or
+/// custom block devices) without writing a newtype and trait impl.
+///
+/// The function may be invoked concurrently when entries are read from
+/// multiple threads, so it must be safe to call through a shared reference.
+/// `FnReaderAt` is only [`Send`] and [`Sync`] when the function is, so a
+/// closure capturing non-thread-safe state (e.g., a `RefCell`) can still be
+/// used on a single thread.
+///
+/// ```rust
+/// let data = std::fs::read("assets/test.zip")?;
+/// let reader = rawzip::FnReaderAt::new(|buf: &mut [u8], offset: u64| {
+///     let data = data.get(offset as usize..).unwrap_or_default();
+///     let len = data.len().min(buf.len());
+///     buf[..len].copy_from_slice(&data[..len]);
+///     Ok(len)
+/// });
+///
+/// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+/// let archive = rawzip::ZipLocator::new()
+///     .locate_in_reader(reader, &mut buffer, data.len() as u64)
+///     .map_err(|(_, e)| e)?;
+/// assert_eq!(archive.entries_hint(), 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FnReaderAt<F>(F);
+
+impl<F> FnReaderAt<F>
+where
+    F: Fn(&mut [u8], u64) -> std::io::Result<usize>,
+{
+    /// Creates a `FnReaderAt` that reads through `f`.
+    pub fn new(f: F) -> Self {
+        Self(f)
+    }
+
+    /// Consumes the reader, returning the wrapped function.
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+impl<F> ReaderAt for FnReaderAt<F>
+where
+    F: Fn(&mut [u8], u64) -> std::io::Result<usize>,
+{
+    #[inline]
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        (self.0)(buf, offset)
+    }
+}
+
+/// A [`ReaderAt`] that stitches several segments into one logical address
+/// space, such as the `.z01`, `.z02`, ..., `.zip` parts of a split archive.
+///
+/// Segments are laid out back to back in the order given, each occupying the
+/// number of bytes it was declared with. The reader itself knows nothing of
+/// zip: [`ZipArchive::from_segments`](crate::ZipArchive::from_segments)
+/// resolves the per-disk offsets recorded in split archives against where
+/// each segment starts.
+///
+/// ```rust
+/// let data = std::fs::read("assets/test.zip")?;
+/// let (head, tail) = data.split_at(100);
+/// let reader = rawzip::SpannedReaderAt::new(vec![
+///     (head.len() as u64, head),
+///     (tail.len() as u64, tail),
+/// ]);
+/// assert_eq!(reader.len(), data.len() as u64);
+///
+/// let mut buf = [0u8; 8];
+/// rawzip::ReaderAt::read_exact_at(&reader, &mut buf, 96)?;
+/// assert_eq!(&buf, &data[96..104]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpannedReaderAt<R> {
+    segments: Vec<R>,
+    /// Logical offset at which each segment starts, followed by the total
+    /// length.
+    starts: Vec<u64>,
+}
+
+impl<R> SpannedReaderAt<R> {
+    /// Creates a reader from `(length, segment)` pairs in archive order.
+    pub fn new(segments: Vec<(u64, R)>) -> Self {
+        let mut starts = Vec::with_capacity(segments.len() + 1);
+        let mut readers = Vec::with_capacity(segments.len());
+        let mut offset = 0u64;
+        for (len, reader) in segments {
+            starts.push(offset);
+            readers.push(reader);
+            offset = offset.saturating_add(len);
+        }
+        starts.push(offset);
+
+        Self {
+            segments: readers,
+            starts,
+        }
+    }
+
+    /// The combined length of all segments.
+    pub fn len(&self) -> u64 {
+        self.starts.last().copied().unwrap_or(0)
+    }
+
+    /// Returns true if the segments contain no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Consumes the reader, returning the segments.
+    pub fn into_inner(self) -> Vec<R> {
+        self.segments
+    }
+
+    /// Logical offset at which each segment starts.
+    pub(crate) fn segment_starts(&self) -> &[u64] {
+        &self.starts[..self.segments.len()]
+    }
+}
+
+impl<R> ReaderLen for SpannedReaderAt<R> {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        Some(SpannedReaderAt::len(self))
+    }
+}
+
+impl<R> ReaderAt for SpannedReaderAt<R>
+where
+    R: ReaderAt,
+{
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        if offset >= self.len() || buf.is_empty() {
+            return Ok(0);
+        }
+
+        // Skip over empty segments by finding the last one starting at or
+        // before the offset.
+        let index = self.starts.partition_point(|&start| start <= offset) - 1;
+        let start = self.starts[index];
+        let remaining = self.starts[index + 1] - offset;
+        let len = buf
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        self.segments[index].read_at(&mut buf[..len], offset - start)
+    }
+
+    /// Splits the ranges at segment boundaries and issues one vectored read
+    /// per segment.
+    fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+        let mut reads: Vec<Vec<(&mut [u8], u64)>> = Vec::new();
+        reads.resize_with(self.segments.len(), Vec::new);
+        for (buf, offset) in bufs.iter_mut() {
+            let mut buf = &mut **buf;
+            let mut offset = *offset;
+            while !buf.is_empty() {
+                if offset >= self.len() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ));
+                }
+
+                let index = self.starts.partition_point(|&start| start <= offset) - 1;
+                let remaining = self.starts[index + 1] - offset;
+                let len = buf
+                    .len()
+                    .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+                let (head, tail) = buf.split_at_mut(len);
+                reads[index].push((head, offset - self.starts[index]));
+                buf = tail;
+                offset += len as u64;
+            }
+        }
+
+        for (segment, mut reads) in self.segments.iter().zip(reads) {
+            if !reads.is_empty() {
+                segment.read_at_vectored(&mut reads)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`ReaderAt`] restricted to a window of the wrapped reader, such as an
+/// archive embedded in a larger file.
+///
+/// Offsets are relative to the start of the window, and no read escapes it:
+/// reads are cut short at the end of the window, and reads that start past
+/// it fail with an [`InvalidInput`](std::io::ErrorKind::InvalidInput) error
+/// rather than returning bytes from outside of the window.
+///
+/// ```rust
+/// let archive = std::fs::read("assets/test.zip")?;
+/// let mut data = vec![0xaa; 100];
+/// data.extend_from_slice(&archive);
+/// data.extend_from_slice(&[0xbb; 100]);
+///
+/// let reader = rawzip::BoundedReaderAt::new(data.as_slice(), 100, archive.len() as u64);
+/// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+/// let archive = rawzip::ZipArchive::from_reader_at(reader, &mut buffer)?;
+/// assert_eq!(archive.entries_hint(), 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoundedReaderAt<R> {
+    inner: R,
+    start: u64,
+    len: u64,
+}
+
+impl<R> BoundedReaderAt<R> {
+    /// Creates a reader of the `len` bytes of `inner` that begin at `start`.
+    pub fn new(inner: R, start: u64, len: u64) -> Self {
+        Self { inner, start, len }
+    }
+
+    /// The offset of the window within the wrapped reader.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// The length of the window.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the window contains no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Consumes the reader, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> ReaderLen for BoundedReaderAt<R> {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        Some(self.len)
+    }
+}
+
+impl<R> ReaderAt for BoundedReaderAt<R>
+where
+    R: ReaderAt,
+{
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        let Some(remaining) = self.len.checked_sub(offset) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "offset {} is beyond the window of {} bytes",
+                    offset, self.len
+                ),
+            ));
+        };
+
+        let Some(inner_offset) = self.start.checked_add(offset) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "window offset overflows",
+            ));
+        };
+
+        let len = buf
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        if len == 0 {
+            return Ok(0);
+        }
+
+        self.inner.read_at(&mut buf[..len], inner_offset)
+    }
+
+    /// Checks that every range lies within the window before forwarding them
+    /// to the wrapped reader in a single vectored read.
+    fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+        let mut reads = Vec::with_capacity(bufs.len());
+        for (buf, offset) in bufs.iter_mut() {
+            let Some(remaining) = self.len.checked_sub(*offset) else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!(
+                        "offset {} is beyond the window of {} bytes",
+                        offset, self.len
+                    ),
+                ));
+            };
+
+            if buf.len() as u64 > remaining {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ));
+            }
+
+            let Some(inner_offset) = self.start.checked_add(*offset) else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "window offset overflows",
+                ));
+            };
+
+            reads.push((&mut **buf, inner_offset));
+        }
+
+        self.inner.read_at_vectored(&mut reads)
+    }
+}
+
+/// A [`ReaderAt`] that caches recently read fixed-size blocks of the wrapped
+/// reader.
+///
+/// Small reads, like those of local file headers and the central directory,
+/// are served from a least recently used cache of blocks. This speeds up
+/// random access over high latency readers (eg: network storage). Reads of at
+/// least a block, like streaming an entry's data, bypass the cache so they
+/// don't evict it.
+///
+/// ```rust
+/// let data = std::fs::read("assets/test.zip")?;
+/// let reader = rawzip::CachingReaderAt::new(data.as_slice())
+///     .block_size(4096)
+///     .capacity(16);
+///
+/// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+/// let archive = rawzip::ZipLocator::new()
+///     .locate_in_reader(reader, &mut buffer, data.len() as u64)
+///     .map_err(|(_, e)| e)?;
+/// assert_eq!(archive.entries_hint(), 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct CachingReaderAt<R> {
+    inner: R,
+    block_size: usize,
+    capacity: usize,
+
+    /// Cached blocks keyed by block index, most recently used first.
+    blocks: std::sync::Mutex<std::collections::VecDeque<(u64, Vec<u8>)>>,
+}
+
+impl<R> CachingReaderAt<R> {
+    /// Creates a cache of 64 blocks of 4 KiB in front of `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            block_size: 4096,
+            capacity: 64,
+            blocks: std::sync::Mutex::new(std::collections::VecDeque::new()),
+        }
+    }
+
+    /// Sets the size of each cached block. Values below 1 are raised to 1.
+    #[must_use]
+    pub fn block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size.max(1);
+        self.blocks.get_mut().unwrap().clear();
+        self
+    }
+
+    /// Sets the maximum number of cached blocks. A capacity of 0 disables
+    /// caching.
+    #[must_use]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self.blocks.get_mut().unwrap().truncate(capacity);
+        self
+    }
+
+    /// Consumes the cache, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Copies the cached block into `buf`, marking it as most recently used.
+    fn read_cached(&self, index: u64, buf: &mut [u8], start: usize) -> Option<usize> {
+        let mut blocks = self.blocks.lock().unwrap();
+        let position = blocks.iter().position(|(i, _)| *i == index)?;
+        let entry = blocks.remove(position)?;
+        let len = copy_from_block(&entry.1, buf, start);
+        blocks.push_front(entry);
+        Some(len)
+    }
+}
+
+impl<R> ReaderAt for CachingReaderAt<R>
+where
+    R: ReaderAt,
+{
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        if buf.len() >= self.block_size || self.capacity == 0 {
+            return self.inner.read_at(buf, offset);
+        }
+
+        let block_size = self.block_size as u64;
+        let index = offset / block_size;
+        let start = (offset % block_size) as usize;
+        if let Some(len) = self.read_cached(index, buf, start) {
+            return Ok(len);
+        }
+
+        // Read the block without holding the lock so that concurrent reads
+        // of other blocks aren't serialized behind this one.
+        let mut block = vec![0u8; self.block_size];
+        let mut len = 0;
+        while len < block.len() {
+            let read = self
+                .inner
+                .read_at(&mut block[len..], index * block_size + len as u64)?;
+            if read == 0 {
+                break;
+            }
+            len += read;
+        }
+        block.truncate(len);
+
+        let result = copy_from_block(&block, buf, start);
+        let mut blocks = self.blocks.lock().unwrap();
+        if !blocks.iter().any(|(i, _)| *i == index) {
+            blocks.truncate(self.capacity - 1);
+            blocks.push_front((index, block));
+        }
+
+        Ok(result)
+    }
+
+    /// Serves the ranges held in a cached block from the cache, and forwards
+    /// the rest to the wrapped reader in a single vectored read.
+    fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+        let block_size = self.block_size as u64;
+        let mut reads = Vec::new();
+        for (buf, offset) in bufs.iter_mut() {
+            let start = (*offset % block_size) as usize;
+            let cached = self.capacity > 0
+                && start + buf.len() <= self.block_size
+                && self.read_cached(*offset / block_size, buf, start) == Some(buf.len());
+            if !cached {
+                reads.push((&mut **buf, *offset));
+            }
+        }
+
+        if reads.is_empty() {
+            return Ok(());
+        }
+        self.inner.read_at_vectored(&mut reads)
+    }
+}
+
+/// Copies the bytes of `block` starting at `start` into `buf`.
+fn copy_from_block(block: &[u8], buf: &mut [u8], start: usize) -> usize {
+    let data = block.get(start..).unwrap_or_default();
+    let len = data.len().min(buf.len());
+    buf[..len].copy_from_slice(&data[..len]);
+    len
+}
+
 impl<T: ReaderAt> ReaderAt for &'_ T {
     #[inline]
     fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
         (*self).read_at(buf, offset)
     }
+
+    #[inline]
+    fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+        (*self).read_at_vectored(bufs)
+    }
 }
 
 impl<T: ReaderAt> ReaderAt for &'_ mut T {
@@ -193,12 +811,17 @@ impl<T: ReaderAt> ReaderAt for &'_ mut T {
     fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
         (**self).read_at(buf, offset)
     }
+
+    #[inline]
+    fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+        (**self).read_at_vectored(bufs)
+    }
 }
 
 impl ReaderAt for &[u8] {
     #[inline]
     fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
-        let skip = self.len().min(offset as usize);
+        let skip = <[u8]>::len(self).min(offset as usize);
         let data = &self[skip..];
         let len = data.len().min(buf.len());
         buf[..len].copy_from_slice(&data[..len]);
@@ -223,3 +846,66 @@ impl ReaderAt for Vec<u8> {
         self.as_slice().read_at(buf, offset)
     }
 }
+
+#[cfg(feature = "bytes")]
+impl ReaderAt for bytes::Bytes {
+    #[inline]
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        self.as_ref().read_at(buf, offset)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl ReaderAt for bytes::BytesMut {
+    #[inline]
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        self.as_ref().read_at(buf, offset)
+    }
+}
+
+impl<T: ReaderLen> ReaderLen for &'_ T {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        (*self).len()
+    }
+}
+
+impl ReaderLen for &[u8] {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        Some(<[u8]>::len(self) as u64)
+    }
+}
+
+impl<R> ReaderLen for std::io::Cursor<R>
+where
+    R: AsRef<[u8]>,
+{
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        Some(<[u8]>::len(self.get_ref().as_ref()) as u64)
+    }
+}
+
+impl ReaderLen for Vec<u8> {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        Some(Vec::len(self) as u64)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl ReaderLen for bytes::Bytes {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        Some(bytes::Bytes::len(self) as u64)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl ReaderLen for bytes::BytesMut {
+    #[inline]
+    fn len(&self) -> Option<u64> {
+        Some(bytes::BytesMut::len(self) as u64)
+    }
+}
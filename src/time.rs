@@ -222,6 +222,46 @@ impl ZipDateTimeKind {
             ZipDateTimeKind::Local(dt) => dt.nanosecond(),
         }
     }
+
+    /// Returns true if the timestamp is in an unknown time zone, so it can't
+    /// be pinned to an instant without guessing the zone it was written in.
+    #[must_use]
+    pub const fn is_ambiguous(&self) -> bool {
+        matches!(self, ZipDateTimeKind::Local(_))
+    }
+
+    /// Compares two timestamps chronologically, treating local timestamps as
+    /// if they were UTC.
+    ///
+    /// The time zone of a local timestamp is unknown, so this is only exact
+    /// when it happens to be UTC and is otherwise off by the zone's offset.
+    /// It does give a total order, which is what's needed to sort entries by
+    /// date when an archive mixes both kinds.
+    ///
+    /// ```rust
+    /// use rawzip::time::{LocalDateTime, UtcDateTime, ZipDateTimeKind};
+    ///
+    /// let utc = ZipDateTimeKind::Utc(UtcDateTime::from_unix(1_000_000_000));
+    /// let local = ZipDateTimeKind::Local(
+    ///     LocalDateTime::from_components(2001, 9, 9, 1, 46, 41, 0).unwrap(),
+    /// );
+    /// assert!(utc.cmp_assuming_utc(&local).is_lt());
+    /// ```
+    #[must_use]
+    pub fn cmp_assuming_utc(&self, other: &ZipDateTimeKind) -> std::cmp::Ordering {
+        let key = |dt: &ZipDateTimeKind| {
+            (
+                dt.year(),
+                dt.month(),
+                dt.day(),
+                dt.hour(),
+                dt.minute(),
+                dt.second(),
+                dt.nanosecond(),
+            )
+        };
+        key(self).cmp(&key(other))
+    }
 }
 
 impl std::fmt::Display for ZipDateTimeKind {
@@ -415,6 +455,98 @@ impl ZipDateTime<Utc> {
         }
     }
 
+    /// Creates a ZipDateTime from a Unix timestamp with sub-second precision.
+    ///
+    /// Returns `None` if `nanoseconds` exceeds 999,999,999.
+    ///
+    /// ```rust
+    /// use rawzip::time::UtcDateTime;
+    ///
+    /// let datetime = UtcDateTime::from_unix_nanos(1_700_000_000, 500_000_000).unwrap();
+    /// assert_eq!(datetime.to_string(), "2023-11-14T22:13:20.500000000Z");
+    /// assert!(UtcDateTime::from_unix_nanos(0, 1_000_000_000).is_none());
+    /// ```
+    pub fn from_unix_nanos(seconds: i64, nanoseconds: u32) -> Option<UtcDateTime> {
+        if nanoseconds > 999_999_999 {
+            return None;
+        }
+
+        Some(ZipDateTime {
+            nanosecond: nanoseconds,
+            ..Self::from_unix(seconds)
+        })
+    }
+
+    /// Creates a ZipDateTime from the time elapsed since the Unix epoch, such
+    /// as the result of [`SystemTime::duration_since`](std::time::SystemTime::duration_since).
+    pub fn from_unix_duration(duration: std::time::Duration) -> UtcDateTime {
+        let seconds = i64::try_from(duration.as_secs()).unwrap_or(i64::MAX);
+        ZipDateTime {
+            nanosecond: duration.subsec_nanos(),
+            ..Self::from_unix(seconds)
+        }
+    }
+
+    /// Creates a ZipDateTime from an MS-DOS timestamp, taking its fields as
+    /// UTC.
+    ///
+    /// MS-DOS timestamps don't record a time zone (see
+    /// [`ZipDateTimeKind::Local`]), so this is for when the fields are known
+    /// or assumed to be UTC, like when copying timestamps between archives.
+    ///
+    /// ```rust
+    /// use rawzip::time::{DosDateTime, UtcDateTime};
+    ///
+    /// let datetime = UtcDateTime::from_dos(DosDateTime::new(0x7d1c, 0x4d0f));
+    /// assert_eq!(datetime.to_string(), "2018-08-15T15:40:56Z");
+    /// ```
+    pub fn from_dos(dos: DosDateTime) -> UtcDateTime {
+        ZipDateTime {
+            year: dos.year(),
+            month: dos.month(),
+            day: dos.day(),
+            hour: dos.hour(),
+            minute: dos.minute(),
+            second: dos.second(),
+            nanosecond: 0,
+            _timezone: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns the current system time.
+    ///
+    /// Useful as the modification time of entries created from in-memory
+    /// data. A system clock set before the Unix epoch is still represented.
+    ///
+    /// ```rust
+    /// use rawzip::time::UtcDateTime;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive
+    ///     .new_file("file.txt")
+    ///     .last_modified(UtcDateTime::now())
+    ///     .store_reader(&b"Hello, world!"[..])?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn now() -> UtcDateTime {
+        match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => Self::from_unix_duration(duration),
+            Err(e) => {
+                let before = e.duration();
+                let seconds = i64::try_from(before.as_secs()).unwrap_or(i64::MAX);
+                match before.subsec_nanos() {
+                    0 => Self::from_unix(-seconds),
+                    nanos => ZipDateTime {
+                        nanosecond: 1_000_000_000 - nanos,
+                        ..Self::from_unix(-seconds - 1)
+                    },
+                }
+            }
+        }
+    }
+
     /// Creates a ZipDateTime from an NTFS timestamp (100ns ticks since 1601)
     pub(crate) fn from_ntfs(ticks: u64) -> UtcDateTime {
         let unix_seconds = (ticks / 10_000_000).saturating_sub(NTFS_EPOCH_OFFSET) as i64;
@@ -447,7 +579,122 @@ impl ZipDateTime<Utc> {
     }
 }
 
+#[cfg(feature = "jiff")]
+impl ZipDateTime<Utc> {
+    /// Creates a UTC datetime from a [`jiff::Timestamp`], preserving
+    /// nanosecond precision.
+    ///
+    /// Timestamps prior to the year 1 saturate to `0001-01-01T00:00:00Z`.
+    ///
+    /// ```
+    /// # use rawzip::time::UtcDateTime;
+    /// let ts = jiff::Timestamp::new(1_700_000_000, 123_456_789).unwrap();
+    /// let dt = UtcDateTime::from_jiff(ts);
+    /// assert_eq!(dt.to_unix(), 1_700_000_000);
+    /// assert_eq!(dt.nanosecond(), 123_456_789);
+    /// assert_eq!(dt.to_jiff(), ts);
+    /// ```
+    #[must_use]
+    pub fn from_jiff(ts: jiff::Timestamp) -> UtcDateTime {
+        let dt = jiff::tz::Offset::UTC.to_datetime(ts);
+        if dt.year() < 1 {
+            return ZipDateTime {
+                year: 1,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+                second: 0,
+                nanosecond: 0,
+                _timezone: std::marker::PhantomData,
+            };
+        }
+
+        ZipDateTime::from_jiff_civil(dt)
+    }
+
+    /// Converts to a [`jiff::Timestamp`], preserving nanosecond precision.
+    ///
+    /// Datetimes beyond jiff's supported range (after the year 9999) saturate
+    /// to [`jiff::Timestamp::MAX`].
+    #[must_use]
+    pub fn to_jiff(&self) -> jiff::Timestamp {
+        self.to_jiff_civil()
+            .and_then(|dt| jiff::tz::Offset::UTC.to_timestamp(dt).ok())
+            .unwrap_or(jiff::Timestamp::MAX)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl ZipDateTime<Local> {
+    /// Creates a local datetime from a [`jiff::civil::DateTime`], preserving
+    /// nanosecond precision.
+    ///
+    /// Returns `None` if the datetime is prior to the year 1.
+    #[must_use]
+    pub fn from_jiff(dt: jiff::civil::DateTime) -> Option<LocalDateTime> {
+        if dt.year() < 1 {
+            return None;
+        }
+
+        Some(ZipDateTime::from_jiff_civil(dt))
+    }
+
+    /// Converts to a [`jiff::civil::DateTime`], preserving nanosecond
+    /// precision.
+    ///
+    /// Returns `None` if the datetime is after the year 9999.
+    #[must_use]
+    pub fn to_jiff(&self) -> Option<jiff::civil::DateTime> {
+        self.to_jiff_civil()
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl<TZ> ZipDateTime<TZ> {
+    /// Caller is responsible for ensuring the year is positive.
+    fn from_jiff_civil(dt: jiff::civil::DateTime) -> Self {
+        ZipDateTime {
+            year: dt.year() as u16,
+            month: dt.month() as u8,
+            day: dt.day() as u8,
+            hour: dt.hour() as u8,
+            minute: dt.minute() as u8,
+            second: dt.second() as u8,
+            nanosecond: dt.subsec_nanosecond() as u32,
+            _timezone: std::marker::PhantomData,
+        }
+    }
+
+    fn to_jiff_civil(&self) -> Option<jiff::civil::DateTime> {
+        jiff::civil::DateTime::new(
+            i16::try_from(self.year).ok()?,
+            self.month as i8,
+            self.day as i8,
+            self.hour as i8,
+            self.minute as i8,
+            self.second as i8,
+            self.nanosecond as i32,
+        )
+        .ok()
+    }
+}
+
 impl ZipDateTime<Local> {
+    /// Takes the fields of this local datetime as UTC.
+    pub(crate) fn assume_utc(&self) -> UtcDateTime {
+        ZipDateTime {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+            hour: self.hour,
+            minute: self.minute,
+            second: self.second,
+            nanosecond: self.nanosecond,
+            _timezone: std::marker::PhantomData,
+        }
+    }
+
     /// Creates a ZipDateTime from a DosDateTime
     pub(crate) fn from_dos(dos: DosDateTime) -> LocalDateTime {
         // Note: DOS timestamps with month=0 and day=0 are a gray area. Some
@@ -477,11 +724,64 @@ pub struct DosDateTime {
 
 impl DosDateTime {
     /// Creates a new MS-DOS datetime from packed date and time values.
+    ///
+    /// Any packed values are accepted, and out of range components are
+    /// clamped by the accessors.
+    ///
+    /// ```rust
+    /// use rawzip::time::DosDateTime;
+    ///
+    /// let datetime = DosDateTime::new(0x7d1c, 0x4d0f);
+    /// assert_eq!(datetime.year(), 2018);
+    /// assert_eq!(datetime.month(), 8);
+    /// assert_eq!(datetime.day(), 15);
+    /// assert_eq!(datetime.hour(), 15);
+    /// assert_eq!(datetime.minute(), 40);
+    /// assert_eq!(datetime.second(), 56);
+    /// ```
     #[must_use]
-    pub(crate) const fn new(time: u16, date: u16) -> Self {
+    pub const fn new(time: u16, date: u16) -> Self {
         Self { time, date }
     }
 
+    /// Packs date and time components into an MS-DOS datetime.
+    ///
+    /// Returns `None` if a component is out of range, including years outside
+    /// of 1980-2107. Odd seconds are rounded down, as MS-DOS timestamps have a
+    /// 2-second precision.
+    ///
+    /// ```rust
+    /// use rawzip::time::DosDateTime;
+    ///
+    /// let datetime = DosDateTime::from_components(2018, 8, 15, 15, 40, 57).unwrap();
+    /// assert_eq!(datetime.into_parts(), (0x7d1c, 0x4d0f));
+    /// assert!(DosDateTime::from_components(1979, 12, 31, 0, 0, 0).is_none());
+    /// ```
+    #[must_use]
+    pub fn from_components(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Option<Self> {
+        let valid = (1980..=2107).contains(&year)
+            && (1..=12).contains(&month)
+            && day >= 1
+            && day <= last_day_of_month(year, month)
+            && hour < 24
+            && minute < 60
+            && second < 60;
+        if !valid {
+            return None;
+        }
+
+        let date = ((year - 1980) << 9) | (u16::from(month) << 5) | u16::from(day);
+        let time = (u16::from(hour) << 11) | (u16::from(minute) << 5) | (u16::from(second) / 2);
+        Some(Self { time, date })
+    }
+
     /// Returns the year (1980-2107).
     #[must_use]
     pub fn year(&self) -> u16 {
@@ -525,7 +825,7 @@ impl DosDateTime {
 
     /// Returns the packed time and date components as (time, date).
     #[must_use]
-    pub(crate) const fn into_parts(self) -> (u16, u16) {
+    pub const fn into_parts(self) -> (u16, u16) {
         (self.time, self.date)
     }
 }
@@ -556,6 +856,25 @@ pub(crate) const EXTENDED_TIMESTAMP_ID: u16 = 0x5455; // "UT" - Extended timesta
 const UNIX_TIMESTAMP_ID: u16 = 0x5855; // "UX" - Unix timestamp (obsolete)
 const NTFS_TIMESTAMP_ID: u16 = 0x000a; // NTFS timestamp
 
+/// Where an entry's modification time was read from
+///
+/// See [`ZipFileHeaderRecord::timestamp_source`](crate::ZipFileHeaderRecord::timestamp_source).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimestampSource {
+    /// The MS-DOS date and time fields of the header, in an unknown local
+    /// time zone
+    Dos,
+
+    /// The extended timestamp extra field (0x5455), in UTC
+    ExtendedUnix,
+
+    /// The NTFS extra field (0x000a), in UTC
+    Ntfs,
+
+    /// The obsolete Info-ZIP Unix extra field (0x5855), in UTC
+    OldUnix,
+}
+
 /// Extracts timestamp from the extra field using "last wins" strategy.
 /// Returns the last valid timestamp found, or falls back to MS-DOS if none found.
 /// This matches Go's zip reader behavior.
@@ -564,6 +883,16 @@ pub(crate) fn extract_best_timestamp(
     dos_time: u16,
     dos_date: u16,
 ) -> ZipDateTimeKind {
+    extract_best_timestamp_with_source(extra_field, dos_time, dos_date).0
+}
+
+/// Like [`extract_best_timestamp`], but also reports where the timestamp
+/// came from.
+pub(crate) fn extract_best_timestamp_with_source(
+    extra_field: &[u8],
+    dos_time: u16,
+    dos_date: u16,
+) -> (ZipDateTimeKind, TimestampSource) {
     let mut pos = 0;
     let mut last_timestamp = None;
 
@@ -581,17 +910,17 @@ pub(crate) fn extract_best_timestamp(
         match field_id {
             NTFS_TIMESTAMP_ID => {
                 if let Some(timestamp) = parse_ntfs_timestamp(field_data) {
-                    last_timestamp = Some(ZipDateTimeKind::Utc(timestamp));
+                    last_timestamp = Some((timestamp, TimestampSource::Ntfs));
                 }
             }
             EXTENDED_TIMESTAMP_ID => {
                 if let Some(timestamp) = parse_extended_timestamp(field_data) {
-                    last_timestamp = Some(ZipDateTimeKind::Utc(timestamp));
+                    last_timestamp = Some((timestamp, TimestampSource::ExtendedUnix));
                 }
             }
             UNIX_TIMESTAMP_ID => {
                 if let Some(timestamp) = parse_unix_timestamp(field_data) {
-                    last_timestamp = Some(ZipDateTimeKind::Utc(timestamp));
+                    last_timestamp = Some((timestamp, TimestampSource::OldUnix));
                 }
             }
             _ => {}
@@ -601,11 +930,16 @@ pub(crate) fn extract_best_timestamp(
     }
 
     // Return the last timestamp found, or fall back to MS-DOS
-    last_timestamp.unwrap_or_else(|| {
-        ZipDateTimeKind::Local(LocalDateTime::from_dos(DosDateTime::new(
-            dos_time, dos_date,
-        )))
-    })
+    match last_timestamp {
+        Some((timestamp, source)) => (ZipDateTimeKind::Utc(timestamp), source),
+        None => {
+            let dos = DosDateTime::new(dos_time, dos_date);
+            (
+                ZipDateTimeKind::Local(LocalDateTime::from_dos(dos)),
+                TimestampSource::Dos,
+            )
+        }
+    }
 }
 
 /// Parses NTFS timestamp extra field (0x000a)
@@ -637,22 +971,100 @@ fn parse_ntfs_timestamp(data: &[u8]) -> Option<UtcDateTime> {
     Some(UtcDateTime::from_ntfs(mtime_ticks))
 }
 
-/// Parses Extended Timestamp extra field (0x5455)
-fn parse_extended_timestamp(data: &[u8]) -> Option<UtcDateTime> {
-    if data.len() < 5 {
-        return None;
+/// The timestamps carried by an Extended Timestamp (0x5455) extra field.
+///
+/// The local file header may carry the modification, access, and creation
+/// times, while the central directory conventionally carries only the
+/// modification time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExtendedTimestamps {
+    modified: Option<UtcDateTime>,
+    accessed: Option<UtcDateTime>,
+    created: Option<UtcDateTime>,
+}
+
+impl ExtendedTimestamps {
+    /// Parses the last Extended Timestamp field found within raw extra field
+    /// data, such as the extra field of a local file header.
+    pub fn from_extra_field(extra_field: &[u8]) -> Option<ExtendedTimestamps> {
+        find_extended_timestamps(extra_field)
     }
 
-    let flags = data[0];
-    let pos = 1;
+    /// The last modification time, if present.
+    #[inline]
+    pub fn modified(&self) -> Option<UtcDateTime> {
+        self.modified
+    }
+
+    /// The last access time, if present.
+    #[inline]
+    pub fn accessed(&self) -> Option<UtcDateTime> {
+        self.accessed
+    }
+
+    /// The creation time, if present.
+    #[inline]
+    pub fn created(&self) -> Option<UtcDateTime> {
+        self.created
+    }
+}
+
+/// Finds the last Extended Timestamp field within an extra field.
+pub(crate) fn find_extended_timestamps(extra_field: &[u8]) -> Option<ExtendedTimestamps> {
+    let mut pos = 0;
+    let mut result = None;
+
+    while pos + 4 <= extra_field.len() {
+        let field_id = le_u16(&extra_field[pos..pos + 2]);
+        let field_size = le_u16(&extra_field[pos + 2..pos + 4]) as usize;
+        pos += 4;
+
+        if pos + field_size > extra_field.len() {
+            break;
+        }
 
-    // Check if modification time is present (bit 0)
-    if flags & 0x01 != 0 && pos + 4 <= data.len() {
-        let mtime_seconds = le_u32(&data[pos..pos + 4]);
-        return Some(UtcDateTime::from_unix(i64::from(mtime_seconds)));
+        if field_id == EXTENDED_TIMESTAMP_ID {
+            if let Some(timestamps) = parse_extended_timestamps(&extra_field[pos..pos + field_size])
+            {
+                result = Some(timestamps);
+            }
+        }
+
+        pos += field_size;
     }
 
-    None
+    result
+}
+
+/// Parses Extended Timestamp extra field (0x5455)
+///
+/// The flags byte indicates which of the modification (bit 0), access (bit
+/// 1), and creation (bit 2) times are present. The central directory keeps
+/// the local header's flags but may only include the modification time, so
+/// timestamps that are flagged but truncated are treated as absent.
+fn parse_extended_timestamps(data: &[u8]) -> Option<ExtendedTimestamps> {
+    let (&flags, mut rest) = data.split_first()?;
+
+    let mut next = |bit: u8| {
+        if flags & bit == 0 || rest.len() < 4 {
+            return None;
+        }
+
+        let seconds = le_u32(&rest[..4]);
+        rest = &rest[4..];
+        Some(UtcDateTime::from_unix(i64::from(seconds)))
+    };
+
+    Some(ExtendedTimestamps {
+        modified: next(0x01),
+        accessed: next(0x02),
+        created: next(0x04),
+    })
+}
+
+/// Parses the modification time from an Extended Timestamp extra field
+fn parse_extended_timestamp(data: &[u8]) -> Option<UtcDateTime> {
+    parse_extended_timestamps(data).and_then(|timestamps| timestamps.modified)
 }
 
 /// Parses Unix timestamp extra field (0x5855) - obsolete format
@@ -755,6 +1167,7 @@ const fn last_day_of_month_common_year(m: usize) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     fn utc_from_components(
         year: u16,
@@ -844,6 +1257,23 @@ mod tests {
         assert_eq!(dos_dt.second(), 44); // Rounded down to even second
     }
 
+    #[test]
+    fn test_utc_from_dos() {
+        let dos_dt = DosDateTime::from_components(2023, 6, 15, 14, 30, 44).unwrap();
+        let utc_dt = UtcDateTime::from_dos(dos_dt);
+        assert_eq!(utc_dt, utc_from_components(2023, 6, 15, 14, 30, 44, 0));
+        assert_eq!(utc_dt.timezone(), TimeZone::Utc);
+        assert_eq!(DosDateTime::from(&utc_dt), dos_dt);
+
+        // Agrees with the local time read from the same fields
+        let local_dt = LocalDateTime::from_dos(dos_dt);
+        assert_eq!(utc_dt.to_string(), format!("{}Z", local_dt));
+
+        // Out of range fields are clamped like the accessors
+        let utc_dt = UtcDateTime::from_dos(DosDateTime::new(0, 0));
+        assert_eq!(utc_dt, utc_from_components(1980, 1, 1, 0, 0, 0, 0));
+    }
+
     #[test]
     fn test_dos_datetime_odd_seconds() {
         // Test that odd seconds are rounded down using the From trait
@@ -906,6 +1336,77 @@ mod tests {
         assert_eq!(datetime.second(), 0);
     }
 
+    #[rstest]
+    #[case(1980, 1, 1, 0, 0, 0)]
+    #[case(2000, 2, 29, 12, 30, 30)]
+    #[case(2024, 12, 31, 23, 59, 58)]
+    #[case(2107, 12, 31, 23, 59, 58)]
+    fn test_dos_datetime_from_components_roundtrip(
+        #[case] year: u16,
+        #[case] month: u8,
+        #[case] day: u8,
+        #[case] hour: u8,
+        #[case] minute: u8,
+        #[case] second: u8,
+    ) {
+        let datetime =
+            DosDateTime::from_components(year, month, day, hour, minute, second).unwrap();
+        let (time, date) = datetime.into_parts();
+        let unpacked = DosDateTime::new(time, date);
+        assert_eq!(unpacked, datetime);
+        assert_eq!(unpacked.year(), year);
+        assert_eq!(unpacked.month(), month);
+        assert_eq!(unpacked.day(), day);
+        assert_eq!(unpacked.hour(), hour);
+        assert_eq!(unpacked.minute(), minute);
+        assert_eq!(unpacked.second(), second);
+    }
+
+    #[test]
+    fn test_dos_datetime_from_components_every_day() {
+        for year in 1980..=2107 {
+            for month in 1..=12 {
+                for day in 1..=last_day_of_month(year, month) {
+                    let datetime = DosDateTime::from_components(year, month, day, 0, 0, 0).unwrap();
+                    let (time, date) = datetime.into_parts();
+                    let unpacked = DosDateTime::new(time, date);
+                    assert_eq!(
+                        (unpacked.year(), unpacked.month(), unpacked.day()),
+                        (year, month, day)
+                    );
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(1979, 12, 31, 0, 0, 0)]
+    #[case(2108, 1, 1, 0, 0, 0)]
+    #[case(2023, 0, 1, 0, 0, 0)]
+    #[case(2023, 13, 1, 0, 0, 0)]
+    #[case(2023, 2, 29, 0, 0, 0)]
+    #[case(2023, 4, 0, 0, 0, 0)]
+    #[case(2023, 4, 31, 0, 0, 0)]
+    #[case(2023, 4, 1, 24, 0, 0)]
+    #[case(2023, 4, 1, 0, 60, 0)]
+    #[case(2023, 4, 1, 0, 0, 60)]
+    fn test_dos_datetime_from_components_invalid(
+        #[case] year: u16,
+        #[case] month: u8,
+        #[case] day: u8,
+        #[case] hour: u8,
+        #[case] minute: u8,
+        #[case] second: u8,
+    ) {
+        assert!(DosDateTime::from_components(year, month, day, hour, minute, second).is_none());
+    }
+
+    #[test]
+    fn test_dos_datetime_from_components_odd_second() {
+        let datetime = DosDateTime::from_components(2023, 4, 1, 10, 20, 31).unwrap();
+        assert_eq!(datetime.second(), 30);
+    }
+
     #[test]
     fn test_zip_datetime_dos() {
         let datetime = local_from_components(2020, 6, 15, 14, 30, 44, 0);
@@ -950,6 +1451,77 @@ mod tests {
         assert_eq!(datetime.timezone(), TimeZone::Utc);
     }
 
+    #[test]
+    fn test_from_unix_nanos() {
+        let datetime = UtcDateTime::from_unix_nanos(1283652721, 123456789).unwrap();
+        assert_eq!(
+            datetime,
+            utc_from_components(2010, 9, 5, 2, 12, 1, 123456789)
+        );
+        assert_eq!(datetime.to_string(), "2010-09-05T02:12:01.123456789Z");
+        assert_eq!(datetime.to_unix(), 1283652721);
+
+        let datetime = UtcDateTime::from_unix_nanos(0, 999_999_999).unwrap();
+        assert_eq!(datetime.to_string(), "1970-01-01T00:00:00.999999999Z");
+
+        assert!(UtcDateTime::from_unix_nanos(1283652721, 1_000_000_000).is_none());
+    }
+
+    #[test]
+    fn test_from_unix_duration() {
+        let duration = std::time::Duration::new(1283652721, 123456789);
+        let datetime = UtcDateTime::from_unix_duration(duration);
+        assert_eq!(datetime.to_string(), "2010-09-05T02:12:01.123456789Z");
+        assert_eq!(
+            UtcDateTime::from_unix_duration(std::time::Duration::ZERO),
+            UtcDateTime::from_unix(0)
+        );
+    }
+
+    #[test]
+    fn test_now() {
+        let now = UtcDateTime::now();
+        assert!(now.year() >= 2024);
+        assert!(now.nanosecond() < 1_000_000_000);
+    }
+
+    #[test]
+    fn test_cmp_assuming_utc() {
+        let utc = |seconds| ZipDateTimeKind::Utc(UtcDateTime::from_unix(seconds));
+        let local = |year, month, day, hour, minute, second| {
+            ZipDateTimeKind::Local(
+                LocalDateTime::from_components(year, month, day, hour, minute, second, 0).unwrap(),
+            )
+        };
+
+        let mut timestamps = [
+            local(2020, 1, 1, 12, 0, 0),
+            utc(1577880000), // 2020-01-01T12:00:00Z
+            utc(1577836800), // 2020-01-01T00:00:00Z
+            local(2019, 12, 31, 23, 59, 58),
+            ZipDateTimeKind::Utc(UtcDateTime::from_unix_nanos(1577836800, 1).unwrap()),
+            local(2021, 6, 1, 0, 0, 0),
+        ];
+        timestamps.sort_by(ZipDateTimeKind::cmp_assuming_utc);
+
+        let actual = timestamps
+            .iter()
+            .map(|dt| dt.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![
+                "2019-12-31T23:59:58",
+                "2020-01-01T00:00:00Z",
+                "2020-01-01T00:00:00.000000001Z",
+                "2020-01-01T12:00:00",
+                "2020-01-01T12:00:00Z",
+                "2021-06-01T00:00:00",
+            ]
+        );
+        assert!(timestamps[3].cmp_assuming_utc(&timestamps[4]).is_eq());
+    }
+
     #[test]
     fn test_to_unix_comprehensive() {
         // Test comprehensive cases including edge cases and leap years
@@ -1091,6 +1663,35 @@ mod tests {
         assert_eq!(result.timezone(), TimeZone::Utc);
     }
 
+    #[test]
+    fn test_parse_extended_timestamps_all_flags() {
+        let mut data = vec![0x07];
+        data.extend_from_slice(&1283652721u32.to_le_bytes());
+        data.extend_from_slice(&1283652722u32.to_le_bytes());
+        data.extend_from_slice(&1283652723u32.to_le_bytes());
+
+        let result = parse_extended_timestamps(&data).unwrap();
+        assert_eq!(result.modified().unwrap().to_unix(), 1283652721);
+        assert_eq!(result.accessed().unwrap().to_unix(), 1283652722);
+        assert_eq!(result.created().unwrap().to_unix(), 1283652723);
+
+        // Central directory variant: all flags set but only mtime present
+        let result = parse_extended_timestamps(&data[..5]).unwrap();
+        assert_eq!(result.modified().unwrap().to_unix(), 1283652721);
+        assert_eq!(result.accessed(), None);
+        assert_eq!(result.created(), None);
+
+        // Only access and creation present
+        let mut data = vec![0x06];
+        data.extend_from_slice(&1283652722u32.to_le_bytes());
+        data.extend_from_slice(&1283652723u32.to_le_bytes());
+        let result = parse_extended_timestamps(&data).unwrap();
+        assert_eq!(result.modified(), None);
+        assert_eq!(result.accessed().unwrap().to_unix(), 1283652722);
+        assert_eq!(result.created().unwrap().to_unix(), 1283652723);
+        assert_eq!(parse_extended_timestamp(&data), None);
+    }
+
     #[test]
     fn test_parse_unix_timestamp() {
         // Unix timestamp format: access time (4 bytes) + modification time (4 bytes)
@@ -1152,6 +1753,46 @@ mod tests {
             "sorting should produce chronological order"
         );
     }
+
+    #[test]
+    #[cfg(feature = "jiff")]
+    fn test_jiff_roundtrip_nanoseconds() {
+        let ts = jiff::Timestamp::new(1_234_567_890, 987_654_321).unwrap();
+        let utc = UtcDateTime::from_jiff(ts);
+        assert_eq!(
+            utc,
+            utc_from_components(2009, 2, 13, 23, 31, 30, 987_654_321)
+        );
+        assert_eq!(utc.to_jiff(), ts);
+
+        // Negative timestamps carry a negative subsecond component in jiff
+        let ts = jiff::Timestamp::new(-1, -500_000_000).unwrap();
+        let utc = UtcDateTime::from_jiff(ts);
+        assert_eq!(
+            utc,
+            utc_from_components(1969, 12, 31, 23, 59, 58, 500_000_000)
+        );
+        assert_eq!(utc.to_jiff(), ts);
+
+        let civil = jiff::civil::date(2023, 6, 15).at(14, 30, 45, 1);
+        let local = LocalDateTime::from_jiff(civil).unwrap();
+        assert_eq!(local, local_from_components(2023, 6, 15, 14, 30, 45, 1));
+        assert_eq!(local.to_jiff(), Some(civil));
+    }
+
+    #[test]
+    #[cfg(feature = "jiff")]
+    fn test_jiff_out_of_range() {
+        let utc = UtcDateTime::from_jiff(jiff::Timestamp::MIN);
+        assert_eq!(utc, utc_from_components(1, 1, 1, 0, 0, 0, 0));
+
+        let far_future = utc_from_components(10000, 1, 1, 0, 0, 0, 0);
+        assert_eq!(far_future.to_jiff(), jiff::Timestamp::MAX);
+
+        assert!(LocalDateTime::from_jiff(jiff::civil::date(-1, 1, 1).at(0, 0, 0, 0)).is_none());
+        let far_future = local_from_components(10000, 1, 1, 0, 0, 0, 0);
+        assert!(far_future.to_jiff().is_none());
+    }
 }
 
 #[cfg(test)]
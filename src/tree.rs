@@ -0,0 +1,250 @@
+use crate::{
+    errors::ErrorKind, mode::S_IFLNK, time::UtcDateTime, CompressionMethod, Error, ZipArchiveWriter,
+};
+use std::fs::Metadata;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Options for [`write_dir_tree`].
+#[derive(Debug, Clone)]
+pub struct DirTreeOptions {
+    compression_method: CompressionMethod,
+    follow_symlinks: bool,
+}
+
+impl DirTreeOptions {
+    /// Creates options that store files uncompressed and store symbolic links
+    /// as links.
+    pub fn new() -> Self {
+        DirTreeOptions {
+            compression_method: CompressionMethod::Store,
+            follow_symlinks: false,
+        }
+    }
+
+    /// Sets the compression method of file entries.
+    ///
+    /// Only [`CompressionMethod::Store`] and, with the `flate2` feature,
+    /// [`CompressionMethod::Deflate`] are supported.
+    #[must_use]
+    pub fn compression_method(mut self, compression_method: CompressionMethod) -> Self {
+        self.compression_method = compression_method;
+        self
+    }
+
+    /// Sets whether symbolic links are followed, adding what they point to
+    /// in their place, rather than stored as links.
+    ///
+    /// Following a link to one of its own ancestor directories is an error.
+    /// Links whose target doesn't exist are stored as links.
+    #[must_use]
+    pub fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.follow_symlinks = follow;
+        self
+    }
+}
+
+impl Default for DirTreeOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes an archive of every file, directory, and symbolic link beneath
+/// `root` to `writer`, returning the writer once the archive is finished.
+///
+/// Entries are named by their path relative to `root` (which itself is not
+/// added) and keep their modification time and, on Unix, permissions.
+/// Directories are visited in name order so the output is reproducible.
+/// Anything that isn't a regular file, directory, or symbolic link, such as
+/// a FIFO, device, or socket, is skipped.
+///
+/// ```rust
+/// let name = format!("rawzip-dir-tree-doctest-{}", std::process::id());
+/// let root = std::env::temp_dir().join(name);
+/// std::fs::create_dir_all(root.join("docs"))?;
+/// std::fs::write(root.join("docs/readme.txt"), "Hello, world!")?;
+///
+/// let options = rawzip::DirTreeOptions::new();
+/// let output = rawzip::write_dir_tree(std::io::Cursor::new(Vec::new()), &root, &options)?;
+/// std::fs::remove_dir_all(&root)?;
+///
+/// let archive = rawzip::ZipArchive::from_slice(output.get_ref())?;
+/// assert_eq!(archive.entries_hint(), 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn write_dir_tree<W>(writer: W, root: &Path, options: &DirTreeOptions) -> Result<W, Error>
+where
+    W: Write,
+{
+    match options.compression_method {
+        CompressionMethod::Store => {}
+        #[cfg(feature = "flate2")]
+        CompressionMethod::Deflate => {}
+        method => {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: format!("unsupported compression method: {:?}", method),
+            }))
+        }
+    }
+
+    let mut archive = ZipArchiveWriter::new(writer);
+    let mut ancestors = vec![root.canonicalize()?];
+    write_dir_entries(&mut archive, root, "", options, &mut ancestors)?;
+    archive.finish()
+}
+
+/// Adds the entries of the directory at `dir`, whose archive name is
+/// `prefix` (empty or ending with a slash).
+fn write_dir_entries<W>(
+    archive: &mut ZipArchiveWriter<W>,
+    dir: &Path,
+    prefix: &str,
+    options: &DirTreeOptions,
+    ancestors: &mut Vec<PathBuf>,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    let mut children = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    children.sort_by_key(|child| child.file_name());
+
+    for child in children {
+        let path = child.path();
+        let file_name = child.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: format!("{} is not valid UTF-8", path.display()),
+            }));
+        };
+        let name = format!("{}{}", prefix, file_name);
+
+        let mut metadata = std::fs::symlink_metadata(&path)?;
+        if metadata.file_type().is_symlink() {
+            if !options.follow_symlinks {
+                write_symlink(archive, &path, &name, &metadata)?;
+                continue;
+            }
+            metadata = match std::fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    write_symlink(archive, &path, &name, &metadata)?;
+                    continue;
+                }
+                Err(e) => return Err(Error::io(e)),
+            };
+        }
+
+        if metadata.is_dir() {
+            let canonical = path.canonicalize()?;
+            if ancestors.contains(&canonical) {
+                return Err(Error::from(ErrorKind::InvalidInput {
+                    msg: format!("{} links to an ancestor directory", path.display()),
+                }));
+            }
+
+            let name = format!("{}/", name);
+            let mut dir = archive.new_dir(&name);
+            if let Some(time) = modification_time(&metadata) {
+                dir = dir.last_modified(time);
+            }
+            if let Some(permissions) = unix_permissions(&metadata) {
+                dir = dir.unix_permissions(permissions);
+            }
+            dir.create()?;
+
+            ancestors.push(canonical);
+            write_dir_entries(archive, &path, &name, options, ancestors)?;
+            ancestors.pop();
+        } else if metadata.is_file() {
+            write_file(archive, &path, &name, &metadata, options)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_file<W>(
+    archive: &mut ZipArchiveWriter<W>,
+    path: &Path,
+    name: &str,
+    metadata: &Metadata,
+    options: &DirTreeOptions,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    let mut reader = std::fs::File::open(path)?;
+    let mut builder = archive
+        .new_file(name)
+        .compression_method(options.compression_method);
+    if let Some(time) = modification_time(metadata) {
+        builder = builder.last_modified(time);
+    }
+    if let Some(permissions) = unix_permissions(metadata) {
+        builder = builder.unix_permissions(permissions);
+    }
+
+    match options.compression_method {
+        #[cfg(feature = "flate2")]
+        CompressionMethod::Deflate => {
+            let mut file = builder.deflate(crate::DeflateLevel::Default)?;
+            std::io::copy(&mut reader, &mut file)?;
+            file.finish()?;
+        }
+        _ => {
+            let mut file = builder.create()?;
+            let mut writer = crate::ZipDataWriter::new(&mut file);
+            std::io::copy(&mut reader, &mut writer)?;
+            let (_, output) = writer.finish()?;
+            file.finish(output)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_symlink<W>(
+    archive: &mut ZipArchiveWriter<W>,
+    path: &Path,
+    name: &str,
+    metadata: &Metadata,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    let target = std::fs::read_link(path)?;
+    let Some(target) = target.to_str() else {
+        return Err(Error::from(ErrorKind::InvalidInput {
+            msg: format!("target of {} is not valid UTF-8", path.display()),
+        }));
+    };
+
+    let permissions = unix_permissions(metadata).unwrap_or(0o777) & 0o7777;
+    let mut builder = archive
+        .new_file(name)
+        .unix_permissions(S_IFLNK | permissions);
+    if let Some(time) = modification_time(metadata) {
+        builder = builder.last_modified(time);
+    }
+    builder.store_reader(target.as_bytes())?;
+    Ok(())
+}
+
+/// The modification time, if the platform reports one after the Unix epoch.
+fn modification_time(metadata: &Metadata) -> Option<UtcDateTime> {
+    let modified = metadata.modified().ok()?;
+    let elapsed = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(UtcDateTime::from_unix_duration(elapsed))
+}
+
+#[cfg(unix)]
+fn unix_permissions(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn unix_permissions(_metadata: &Metadata) -> Option<u32> {
+    None
+}
@@ -0,0 +1,402 @@
+use crate::utils::le_u16;
+use crate::CompressionMethod;
+use std::io::Write;
+
+/// Extra field ID of the WinZip AES extra field
+pub(crate) const AES_EXTRA_FIELD_ID: u16 = 0x9901;
+
+/// The vendor version written for new entries (AE-2)
+#[cfg(feature = "aes")]
+const AE2_VENDOR_VERSION: u16 = 2;
+
+/// The key size of WinZip AES encryption
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AesStrength {
+    Aes128,
+    Aes192,
+    Aes256,
+}
+
+impl AesStrength {
+    fn from_id(id: u8) -> Option<AesStrength> {
+        match id {
+            1 => Some(AesStrength::Aes128),
+            2 => Some(AesStrength::Aes192),
+            3 => Some(AesStrength::Aes256),
+            _ => None,
+        }
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            AesStrength::Aes128 => 1,
+            AesStrength::Aes192 => 2,
+            AesStrength::Aes256 => 3,
+        }
+    }
+
+    /// Returns the length of the encryption key in bytes.
+    pub fn key_len(&self) -> usize {
+        match self {
+            AesStrength::Aes128 => 16,
+            AesStrength::Aes192 => 24,
+            AesStrength::Aes256 => 32,
+        }
+    }
+
+    /// Returns the length of the salt that prefixes the encrypted data.
+    pub fn salt_len(&self) -> usize {
+        self.key_len() / 2
+    }
+}
+
+/// The WinZip AES extra field (0x9901) of an entry with the
+/// [`CompressionMethod::Aes`] compression method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AesExtraField {
+    vendor_version: u16,
+    strength: AesStrength,
+    compression_method: CompressionMethod,
+}
+
+impl AesExtraField {
+    /// Size of the extra field, including its ID and size
+    pub(crate) const SIZE: u16 = 11;
+
+    /// Creates an AE-2 extra field for data compressed with
+    /// `compression_method` before encryption.
+    #[cfg(feature = "aes")]
+    pub(crate) fn new(strength: AesStrength, compression_method: CompressionMethod) -> Self {
+        AesExtraField {
+            vendor_version: AE2_VENDOR_VERSION,
+            strength,
+            compression_method,
+        }
+    }
+
+    pub(crate) fn write<W>(&self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        writer.write_all(&AES_EXTRA_FIELD_ID.to_le_bytes())?;
+        writer.write_all(&(Self::SIZE - 4).to_le_bytes())?;
+        writer.write_all(&self.vendor_version.to_le_bytes())?;
+        writer.write_all(b"AE")?;
+        writer.write_all(&[self.strength.id()])?;
+        writer.write_all(&self.compression_method.as_id().as_u16().to_le_bytes())?;
+        Ok(())
+    }
+
+    /// Finds and parses the AES extra field within raw extra field data.
+    pub(crate) fn find(mut extra_field: &[u8]) -> Option<AesExtraField> {
+        while let (Some(kind), Some(size)) = (
+            extra_field.get(0..2).map(le_u16),
+            extra_field.get(2..4).map(le_u16),
+        ) {
+            extra_field = &extra_field[4..];
+            let end_pos = (size as usize).min(extra_field.len());
+            let (field, rest) = extra_field.split_at(end_pos);
+            extra_field = rest;
+
+            if kind != AES_EXTRA_FIELD_ID || field.len() < 7 || &field[2..4] != b"AE" {
+                continue;
+            }
+
+            return Some(AesExtraField {
+                vendor_version: le_u16(&field[0..2]),
+                strength: AesStrength::from_id(field[4])?,
+                compression_method: CompressionMethod::from(le_u16(&field[5..7])),
+            });
+        }
+
+        None
+    }
+
+    /// The vendor version: 1 for AE-1 and 2 for AE-2.
+    ///
+    /// AE-2 entries don't store a CRC, relying on the authentication code
+    /// instead.
+    pub fn vendor_version(&self) -> u16 {
+        self.vendor_version
+    }
+
+    /// The AES key size.
+    pub fn strength(&self) -> AesStrength {
+        self.strength
+    }
+
+    /// The compression method of the data once decrypted.
+    pub fn compression_method(&self) -> CompressionMethod {
+        self.compression_method
+    }
+}
+
+#[cfg(feature = "aes")]
+pub use self::crypto::AesReader;
+#[cfg(feature = "aes")]
+pub(crate) use self::crypto::{AesEncryptor, AesPassword};
+
+#[cfg(feature = "aes")]
+mod crypto {
+    use super::AesStrength;
+    use crate::errors::{Error, ErrorKind};
+    use aes::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+    use hmac::{Hmac, Mac};
+    use sha1::Sha1;
+    use std::io::Read;
+
+    const PASSWORD_VERIFIER_LEN: usize = 2;
+    const AUTHENTICATION_CODE_LEN: usize = 10;
+    const KEY_DERIVATION_ITERATIONS: u32 = 1000;
+    const BLOCK_SIZE: usize = 16;
+    const MAX_SALT_LEN: usize = 16;
+
+    enum AesCipher {
+        Aes128(aes::Aes128),
+        Aes192(aes::Aes192),
+        Aes256(aes::Aes256),
+    }
+
+    impl AesCipher {
+        fn new(strength: AesStrength, key: &[u8]) -> Self {
+            match strength {
+                AesStrength::Aes128 => AesCipher::Aes128(aes::Aes128::new(key.into())),
+                AesStrength::Aes192 => AesCipher::Aes192(aes::Aes192::new(key.into())),
+                AesStrength::Aes256 => AesCipher::Aes256(aes::Aes256::new(key.into())),
+            }
+        }
+
+        fn encrypt_block(&self, block: &mut [u8; BLOCK_SIZE]) {
+            let block = GenericArray::from_mut_slice(block);
+            match self {
+                AesCipher::Aes128(cipher) => cipher.encrypt_block(block),
+                AesCipher::Aes192(cipher) => cipher.encrypt_block(block),
+                AesCipher::Aes256(cipher) => cipher.encrypt_block(block),
+            }
+        }
+    }
+
+    impl std::fmt::Debug for AesCipher {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("AesCipher")
+        }
+    }
+
+    /// WinZip AES uses CTR mode with a little endian counter starting at 1
+    #[derive(Debug)]
+    struct AesCtr {
+        cipher: AesCipher,
+        counter: u128,
+        keystream: [u8; BLOCK_SIZE],
+        keystream_pos: usize,
+    }
+
+    impl AesCtr {
+        fn apply_keystream(&mut self, data: &mut [u8]) {
+            for byte in data {
+                if self.keystream_pos == BLOCK_SIZE {
+                    self.counter = self.counter.wrapping_add(1);
+                    self.keystream = self.counter.to_le_bytes();
+                    self.cipher.encrypt_block(&mut self.keystream);
+                    self.keystream_pos = 0;
+                }
+
+                *byte ^= self.keystream[self.keystream_pos];
+                self.keystream_pos += 1;
+            }
+        }
+    }
+
+    /// Derives the encryption and authentication keys from the password and
+    /// salt, returning them alongside the password verification value.
+    fn derive_keys(
+        password: &[u8],
+        salt: &[u8],
+        strength: AesStrength,
+    ) -> (AesCtr, Hmac<Sha1>, [u8; PASSWORD_VERIFIER_LEN]) {
+        let key_len = strength.key_len();
+        let mut derived = [0u8; 2 * 32 + PASSWORD_VERIFIER_LEN];
+        let derived = &mut derived[..2 * key_len + PASSWORD_VERIFIER_LEN];
+        pbkdf2::pbkdf2_hmac::<Sha1>(password, salt, KEY_DERIVATION_ITERATIONS, derived);
+
+        let (encryption_key, rest) = derived.split_at(key_len);
+        let (authentication_key, verifier) = rest.split_at(key_len);
+        let ctr = AesCtr {
+            cipher: AesCipher::new(strength, encryption_key),
+            counter: 0,
+            keystream: [0u8; BLOCK_SIZE],
+            keystream_pos: BLOCK_SIZE,
+        };
+        let mac = <Hmac<Sha1> as Mac>::new_from_slice(authentication_key)
+            .expect("HMAC accepts keys of any length");
+        (ctr, mac, [verifier[0], verifier[1]])
+    }
+
+    /// A reader that authenticates and decrypts WinZip AES (AE-1 and AE-2)
+    /// encrypted data.
+    ///
+    /// The decrypted output is the entry's compressed data, to be decompressed
+    /// according to [`AesExtraField::compression_method`](super::AesExtraField::compression_method).
+    /// The authentication code is verified once the last byte has been
+    /// decrypted, and a mismatch is reported as an error from that read.
+    ///
+    /// Created from [`ZipEntry::aes_decrypting_reader`](crate::ZipEntry::aes_decrypting_reader).
+    #[derive(Debug)]
+    pub struct AesReader<R> {
+        reader: R,
+        ctr: AesCtr,
+        mac: Hmac<Sha1>,
+        remaining: u64,
+    }
+
+    impl<R> AesReader<R>
+    where
+        R: Read,
+    {
+        /// Reads the salt and password verifier from `reader`, whose
+        /// `compressed_size` bytes hold the entire encrypted payload.
+        pub(crate) fn new(
+            mut reader: R,
+            password: &[u8],
+            strength: AesStrength,
+            compressed_size: u64,
+        ) -> Result<Self, Error> {
+            let salt_len = strength.salt_len();
+            let overhead = (salt_len + PASSWORD_VERIFIER_LEN + AUTHENTICATION_CODE_LEN) as u64;
+            let Some(remaining) = compressed_size.checked_sub(overhead) else {
+                return Err(Error::from(ErrorKind::Eof));
+            };
+
+            let mut header = [0u8; MAX_SALT_LEN + PASSWORD_VERIFIER_LEN];
+            let header = &mut header[..salt_len + PASSWORD_VERIFIER_LEN];
+            reader.read_exact(header)?;
+            let (salt, verifier) = header.split_at(salt_len);
+
+            let (ctr, mac, expected_verifier) = derive_keys(password, salt, strength);
+            if verifier != expected_verifier {
+                return Err(Error::from(ErrorKind::InvalidPassword));
+            }
+
+            let mut result = AesReader {
+                reader,
+                ctr,
+                mac,
+                remaining,
+            };
+
+            if result.remaining == 0 {
+                result.verify_authentication_code()?;
+            }
+
+            Ok(result)
+        }
+
+        /// Returns the underlying reader.
+        pub fn into_inner(self) -> R {
+            self.reader
+        }
+
+        fn verify_authentication_code(&mut self) -> std::io::Result<()> {
+            let mut code = [0u8; AUTHENTICATION_CODE_LEN];
+            self.reader.read_exact(&mut code)?;
+            self.mac.clone().verify_truncated_left(&code).map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    Error::from(ErrorKind::InvalidAuthenticationCode),
+                )
+            })
+        }
+    }
+
+    impl<R> Read for AesReader<R>
+    where
+        R: Read,
+    {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.remaining == 0 || buf.is_empty() {
+                return Ok(0);
+            }
+
+            let len = (self.remaining.min(buf.len() as u64)) as usize;
+            let read = self.reader.read(&mut buf[..len])?;
+            if read == 0 {
+                return Err(std::io::ErrorKind::UnexpectedEof.into());
+            }
+
+            self.mac.update(&buf[..read]);
+            self.ctr.apply_keystream(&mut buf[..read]);
+            self.remaining -= read as u64;
+
+            // Decompressors may stop reading once they see the end of their
+            // stream, so authenticate eagerly with the final bytes
+            if self.remaining == 0 {
+                self.verify_authentication_code()?;
+            }
+
+            Ok(read)
+        }
+    }
+
+    /// A password and key strength to encrypt an entry with, kept out of
+    /// `Debug` output.
+    #[derive(Clone)]
+    pub(crate) struct AesPassword {
+        pub(crate) password: Vec<u8>,
+        pub(crate) strength: AesStrength,
+    }
+
+    impl std::fmt::Debug for AesPassword {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("AesPassword")
+                .field("strength", &self.strength)
+                .finish_non_exhaustive()
+        }
+    }
+
+    /// Encrypts and authenticates entry data as it is written.
+    #[derive(Debug)]
+    pub(crate) struct AesEncryptor {
+        ctr: AesCtr,
+        mac: Hmac<Sha1>,
+        header: [u8; MAX_SALT_LEN + PASSWORD_VERIFIER_LEN],
+        header_len: usize,
+    }
+
+    impl AesEncryptor {
+        /// Derives keys from the password and a random salt.
+        pub(crate) fn new(password: &AesPassword) -> Result<Self, Error> {
+            let salt_len = password.strength.salt_len();
+            let mut header = [0u8; MAX_SALT_LEN + PASSWORD_VERIFIER_LEN];
+            getrandom::getrandom(&mut header[..salt_len]).map_err(std::io::Error::from)?;
+
+            let (ctr, mac, verifier) =
+                derive_keys(&password.password, &header[..salt_len], password.strength);
+            header[salt_len..salt_len + PASSWORD_VERIFIER_LEN].copy_from_slice(&verifier);
+            Ok(AesEncryptor {
+                ctr,
+                mac,
+                header,
+                header_len: salt_len + PASSWORD_VERIFIER_LEN,
+            })
+        }
+
+        /// The salt and password verification value that precede the
+        /// encrypted data.
+        pub(crate) fn header(&self) -> &[u8] {
+            &self.header[..self.header_len]
+        }
+
+        pub(crate) fn encrypt(&mut self, data: &mut [u8]) {
+            self.ctr.apply_keystream(data);
+            self.mac.update(data);
+        }
+
+        /// The authentication code that follows the encrypted data.
+        pub(crate) fn authentication_code(&self) -> [u8; AUTHENTICATION_CODE_LEN] {
+            let code = self.mac.clone().finalize().into_bytes();
+            let mut result = [0u8; AUTHENTICATION_CODE_LEN];
+            result.copy_from_slice(&code[..AUTHENTICATION_CODE_LEN]);
+            result
+        }
+    }
+}
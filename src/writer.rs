@@ -1,21 +1,47 @@
 use crate::{
-    crc,
+    cp437, crc,
     errors::ErrorKind,
-    mode::CREATOR_UNIX,
+    locator::{
+        find_end_of_central_dir_record_in_reader, EndOfCentralDirectoryRecordFixed,
+        END_OF_CENTRAL_DIR_MAX_OFFSET,
+    },
+    mode::{CREATOR_UNIX, S_IFDIR, S_IFLNK, S_IFMT},
     path::{NormalizedPath, NormalizedPathBuf, ZipFilePath},
-    time::{DosDateTime, UtcDateTime, EXTENDED_TIMESTAMP_ID},
-    CompressionMethod, DataDescriptor, Error, ZipLocalFileHeaderFixed, CENTRAL_HEADER_SIGNATURE,
+    reader_at::MutexReader,
+    time::{
+        extract_best_timestamp, DosDateTime, ExtendedTimestamps, UtcDateTime, ZipDateTimeKind,
+        EXTENDED_TIMESTAMP_ID,
+    },
+    winzip_aes::AesExtraField,
+    CompressionMethod, DataDescriptor, EntryMode, Error, ReaderAt, ZipEntry,
+    ZipLocalFileHeaderFixed, ZipReader, CENTRAL_HEADER_SIGNATURE,
     END_OF_CENTRAL_DIR_LOCATOR_SIGNATURE, END_OF_CENTRAL_DIR_SIGNATURE64,
-    END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES,
+    END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES, RECOMMENDED_BUFFER_SIZE,
+};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+#[cfg(feature = "aes")]
+use crate::{
+    winzip_aes::{AesEncryptor, AesPassword},
+    AesStrength,
 };
-use std::io::{self, Write};
 
 // ZIP64 constants
 const ZIP64_EXTRA_FIELD_ID: u16 = 0x0001;
 const ZIP64_VERSION_NEEDED: u16 = 45; // 4.5
 const ZIP64_EOCD_SIZE: usize = 56;
+// Header, uncompressed size, and compressed size
+const LOCAL_ZIP64_EXTRA_FIELD_SIZE: u16 = 4 + 8 + 8;
+
+// WinZip AES entries need version 5.1 to extract
+const AES_VERSION_NEEDED: u16 = 51;
 
 // General purpose bit flags
+const FLAG_ENCRYPTED: u16 = 0x01; // bit 0: encrypted data
 const FLAG_DATA_DESCRIPTOR: u16 = 0x08; // bit 3: data descriptor present
 const FLAG_UTF8_ENCODING: u16 = 0x800; // bit 11: UTF-8 encoding flag (EFS)
 
@@ -24,15 +50,77 @@ const ZIP64_THRESHOLD_FILE_SIZE: u64 = u32::MAX as u64;
 const ZIP64_THRESHOLD_OFFSET: u64 = u32::MAX as u64;
 const ZIP64_THRESHOLD_ENTRIES: usize = u16::MAX as usize;
 
+/// The points at which the writer switches over to ZIP64 structures.
+///
+/// Defaults to the spec maxima, and can only ever be lowered.
+#[derive(Debug, Clone, Copy)]
+struct Zip64Thresholds {
+    file_size: u64,
+    offset: u64,
+    entries: usize,
+}
+
+impl Zip64Thresholds {
+    /// The value to write in a 32-bit field, substituting the ZIP64 sentinel
+    /// when the actual value will be found in a ZIP64 structure.
+    fn file_size_field(&self, size: u64) -> u32 {
+        if size >= self.file_size {
+            u32::MAX
+        } else {
+            size as u32
+        }
+    }
+
+    /// See [`Zip64Thresholds::file_size_field`]
+    fn offset_field(&self, offset: u64) -> u32 {
+        if offset >= self.offset {
+            u32::MAX
+        } else {
+            offset as u32
+        }
+    }
+
+    /// See [`Zip64Thresholds::file_size_field`]
+    fn entries_field(&self, entries: usize) -> u16 {
+        if entries >= self.entries {
+            u16::MAX
+        } else {
+            entries as u16
+        }
+    }
+}
+
+impl Default for Zip64Thresholds {
+    fn default() -> Self {
+        Zip64Thresholds {
+            file_size: ZIP64_THRESHOLD_FILE_SIZE,
+            offset: ZIP64_THRESHOLD_OFFSET,
+            entries: ZIP64_THRESHOLD_ENTRIES,
+        }
+    }
+}
+
+/// Writes smaller than this are buffered, so that headers, which are written
+/// a field at a time, reach the underlying writer in larger chunks.
+const WRITE_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Tracks the position in the archive while buffering small writes.
+///
+/// The count includes bytes that are still buffered.
 #[derive(Debug)]
 struct CountWriter<W> {
     writer: W,
+    buf: Vec<u8>,
     count: u64,
 }
 
 impl<W> CountWriter<W> {
     fn new(writer: W, count: u64) -> Self {
-        CountWriter { writer, count }
+        CountWriter {
+            writer,
+            buf: Vec::new(),
+            count,
+        }
     }
 
     fn count(&self) -> u64 {
@@ -40,14 +128,47 @@ impl<W> CountWriter<W> {
     }
 }
 
+impl<W: Write> CountWriter<W> {
+    fn flush_buf(&mut self) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            self.writer.write_all(&self.buf)?;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+
+    /// Returns the underlying writer once everything buffered has been
+    /// written to it, so that it can be seeked.
+    fn get_mut(&mut self) -> io::Result<&mut W> {
+        self.flush_buf()?;
+        Ok(&mut self.writer)
+    }
+
+    fn into_inner(mut self) -> io::Result<W> {
+        self.flush_buf()?;
+        Ok(self.writer)
+    }
+}
+
 impl<W: Write> Write for CountWriter<W> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let bytes_written = self.writer.write(buf)?;
+        if self.buf.len() + buf.len() > WRITE_BUFFER_SIZE {
+            self.flush_buf()?;
+        }
+
+        let bytes_written = if buf.len() >= WRITE_BUFFER_SIZE {
+            self.writer.write(buf)?
+        } else {
+            self.buf.extend_from_slice(buf);
+            buf.len()
+        };
+
         self.count += bytes_written as u64;
         Ok(bytes_written)
     }
 
     fn flush(&mut self) -> io::Result<()> {
+        self.flush_buf()?;
         self.writer.flush()
     }
 }
@@ -56,19 +177,205 @@ impl<W: Write> Write for CountWriter<W> {
 #[derive(Debug)]
 pub struct ZipArchiveWriterBuilder {
     count: u64,
+    zip64_thresholds: Zip64Thresholds,
+    auto_create_parents: bool,
+    capacity: usize,
+    spill_path: Option<PathBuf>,
+    reject_duplicate_names: bool,
 }
 
 impl ZipArchiveWriterBuilder {
     /// Creates a new `ZipArchiveWriterBuilder`.
     pub fn new() -> Self {
-        ZipArchiveWriterBuilder { count: 0 }
+        ZipArchiveWriterBuilder {
+            count: 0,
+            zip64_thresholds: Zip64Thresholds::default(),
+            auto_create_parents: false,
+            capacity: 0,
+            spill_path: None,
+            reject_duplicate_names: false,
+        }
+    }
+
+    /// Sets the file size (compressed or uncompressed) at which an entry's
+    /// sizes are written to a ZIP64 extra field.
+    ///
+    /// Values above the spec maximum (`u32::MAX`) are clamped. Lowering the
+    /// threshold is useful to exercise ZIP64 code paths without writing
+    /// gigabytes of data, or to conservatively opt into ZIP64.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let archive = rawzip::ZipArchiveWriter::builder()
+    ///     .zip64_file_size_threshold(10)
+    ///     .build(&mut output);
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn zip64_file_size_threshold(mut self, threshold: u64) -> Self {
+        self.zip64_thresholds.file_size = threshold.min(ZIP64_THRESHOLD_FILE_SIZE);
+        self
+    }
+
+    /// Sets the offset at which local header and central directory offsets
+    /// are written in ZIP64 format.
+    ///
+    /// Values above the spec maximum (`u32::MAX`) are clamped.
+    #[must_use]
+    pub fn zip64_offset_threshold(mut self, threshold: u64) -> Self {
+        self.zip64_thresholds.offset = threshold.min(ZIP64_THRESHOLD_OFFSET);
+        self
+    }
+
+    /// Sets the number of entries at which the archive is written with a ZIP64
+    /// end of central directory record.
+    ///
+    /// Values above the spec maximum (`u16::MAX`) are clamped.
+    #[must_use]
+    pub fn zip64_entries_threshold(mut self, threshold: usize) -> Self {
+        self.zip64_thresholds.entries = threshold.min(ZIP64_THRESHOLD_ENTRIES);
+        self
+    }
+
+    /// Sets whether missing parent directory entries are written
+    /// automatically before each file or directory entry.
+    ///
+    /// Some readers (eg: Windows Explorer) expect an explicit entry for every
+    /// directory. When enabled, adding `a/b/c.txt` first adds `a/` and `a/b/`
+    /// unless they were already added. Parent entries inherit the
+    /// modification time of the entry that created them. Adding a directory
+    /// that was already written, explicitly or as a parent, is a no-op.
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::builder()
+    ///     .auto_create_parents(true)
+    ///     .build(&mut output);
+    /// let mut file = archive.new_file("a/b/c.txt").create()?;
+    /// let mut writer = rawzip::ZipDataWriter::new(&mut file);
+    /// writer.write_all(b"Hello, world!")?;
+    /// let (_, descriptor) = writer.finish()?;
+    /// file.finish(descriptor)?;
+    /// archive.finish()?;
+    ///
+    /// let archive = rawzip::ZipArchive::from_slice(output.get_ref())?;
+    /// assert_eq!(archive.entries_hint(), 3);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn auto_create_parents(mut self, enabled: bool) -> Self {
+        self.auto_create_parents = enabled;
+        self
+    }
+
+    /// Sets whether adding an entry with the same name as an earlier entry
+    /// fails with [`ErrorKind::InvalidInput`].
+    ///
+    /// Readers disagree on which of two same-named entries wins, so a
+    /// duplicate is almost always a bug. Names are compared after
+    /// normalization, and parent directories created automatically count as
+    /// added. Disabled by default.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::builder()
+    ///     .reject_duplicate_names(true)
+    ///     .build(&mut output);
+    /// archive.new_dir("dir/").create()?;
+    /// assert!(archive.new_dir("dir/").create().is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn reject_duplicate_names(mut self, enabled: bool) -> Self {
+        self.reject_duplicate_names = enabled;
+        self
+    }
+
+    /// Pre-allocates room for `entries` entries in the central directory.
+    ///
+    /// The writer keeps a record of every entry until the archive is
+    /// finished, costing roughly 100 bytes per entry plus its name. Reserving
+    /// up front avoids repeated reallocations when writing many entries.
+    #[must_use]
+    pub fn reserve_entries(mut self, entries: usize) -> Self {
+        self.capacity = entries;
+        self
+    }
+
+    /// Writes the central directory record of each finished entry to a
+    /// scratch file at `path` rather than keeping it in memory.
+    ///
+    /// This bounds memory use when writing millions of entries, as the
+    /// records are only streamed back into the archive when it is finished.
+    /// The scratch file is created when the first entry is finished, failing
+    /// if `path` already exists, and removed once the archive is finished or
+    /// dropped.
+    ///
+    /// ```rust
+    /// let name = format!("rawzip-spill-doctest-{}", std::process::id());
+    /// let path = std::env::temp_dir().join(name);
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::builder()
+    ///     .spill_central_directory(&path)
+    ///     .build(&mut output);
+    /// archive.store_reader("file.txt", &b"Hello, world!"[..])?;
+    /// archive.finish()?;
+    /// assert!(!path.exists());
+    ///
+    /// let archive = rawzip::ZipArchive::from_slice(output.get_ref())?;
+    /// assert_eq!(archive.entries_hint(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn spill_central_directory<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.spill_path = Some(path.into());
+        self
     }
 
     /// Builds a `ZipArchiveWriter` that writes to `writer`.
     pub fn build<W>(&self, writer: W) -> ZipArchiveWriter<W> {
         ZipArchiveWriter {
             writer: CountWriter::new(writer, self.count),
-            files: Vec::new(),
+            start_offset: self.count,
+            files: Vec::with_capacity(self.capacity),
+            zip64_thresholds: self.zip64_thresholds,
+            seeker: None,
+            truncator: None,
+            auto_create_parents: self.auto_create_parents,
+            directories: HashSet::new(),
+            names: self.reject_duplicate_names.then(HashSet::new),
+            spill: self.spill_path.clone().map(CentralDirectorySpill::new),
+        }
+    }
+
+    /// Builds a `ZipArchiveWriter` that seeks back to patch each local header
+    /// instead of writing data descriptors.
+    ///
+    /// See [`ZipArchiveWriter::new_seekable`].
+    pub fn build_seekable<W>(&self, writer: W) -> ZipArchiveWriter<W>
+    where
+        W: Write + Seek,
+    {
+        ZipArchiveWriter {
+            seeker: Some(<W as Seek>::seek),
+            ..self.build(writer)
+        }
+    }
+
+    /// Builds a seekable `ZipArchiveWriter` that also truncates the output to
+    /// discard aborted entries.
+    ///
+    /// See [`ZipArchiveWriter::new_truncating`].
+    pub fn build_truncating<W>(&self, writer: W) -> ZipArchiveWriter<W>
+    where
+        W: Write + Seek + Truncate,
+    {
+        ZipArchiveWriter {
+            truncator: Some(<W as Truncate>::truncate),
+            ..self.build_seekable(writer)
         }
     }
 }
@@ -96,14 +403,32 @@ impl Default for ZipArchiveWriterBuilder {
 #[derive(Debug)]
 pub struct ZipArchiveWriter<W> {
     files: Vec<FileHeader>,
+    start_offset: u64,
     writer: CountWriter<W>,
+    zip64_thresholds: Zip64Thresholds,
+    seeker: Option<Seeker<W>>,
+    truncator: Option<Truncator<W>>,
+    auto_create_parents: bool,
+    /// Directory entries written so far, used to avoid duplicate parents.
+    directories: HashSet<String>,
+    /// Names of the entries added so far, when duplicate names are rejected.
+    names: Option<HashSet<String>>,
+    spill: Option<CentralDirectorySpill>,
 }
 
 impl ZipArchiveWriter<()> {
+    /// Creates a `ZipArchiveWriterBuilder` with default settings.
+    pub fn builder() -> ZipArchiveWriterBuilder {
+        ZipArchiveWriterBuilder::new()
+    }
+
     /// Creates a `ZipArchiveWriterBuilder` that starts writing at `offset`.
     /// This is useful when the ZIP archive is appended to an existing file.
     pub fn at_offset(offset: u64) -> ZipArchiveWriterBuilder {
-        ZipArchiveWriterBuilder { count: offset }
+        ZipArchiveWriterBuilder {
+            count: offset,
+            ..ZipArchiveWriterBuilder::new()
+        }
     }
 }
 
@@ -112,6 +437,219 @@ impl<W> ZipArchiveWriter<W> {
     pub fn new(writer: W) -> Self {
         ZipArchiveWriterBuilder::new().build(writer)
     }
+
+    /// Creates a new `ZipArchiveWriter` that writes to a seekable `writer`.
+    ///
+    /// When an entry is finished, the writer seeks back to its local header
+    /// and fills in the CRC and sizes, so no data descriptor is written. This
+    /// maximizes compatibility with readers that don't support data
+    /// descriptors. Entries whose sizes require ZIP64 still fall back to a
+    /// data descriptor, as the local header has no room for a ZIP64 extra
+    /// field, as do entries marked with [`ZipFileBuilder::large_file`].
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new_seekable(&mut output);
+    /// let mut file = archive.new_file("file.txt").create()?;
+    /// let mut writer = rawzip::ZipDataWriter::new(&mut file);
+    /// writer.write_all(b"Hello, world!")?;
+    /// let (_, output) = writer.finish()?;
+    /// file.finish(output)?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_seekable(writer: W) -> Self
+    where
+        W: Write + Seek,
+    {
+        ZipArchiveWriterBuilder::new().build_seekable(writer)
+    }
+
+    /// Creates a new `ZipArchiveWriter` that writes to a seekable `writer`
+    /// whose length can be cut short, such as a [`File`] or a
+    /// `Cursor<Vec<u8>>`.
+    ///
+    /// Behaves like [`ZipArchiveWriter::new_seekable`], except that
+    /// [`ZipEntryWriter::abort`] truncates the output to remove every byte of
+    /// the aborted entry.
+    pub fn new_truncating(writer: W) -> Self
+    where
+        W: Write + Seek + Truncate,
+    {
+        ZipArchiveWriterBuilder::new().build_truncating(writer)
+    }
+
+    /// Returns the number of finished entries, including any parent
+    /// directories that were created automatically.
+    pub fn entry_count(&self) -> usize {
+        self.files.len() + self.spill.as_ref().map_or(0, |spill| spill.entries)
+    }
+
+    /// Records `name` as taken, failing if duplicate names are rejected and
+    /// an earlier entry already has it.
+    fn claim_name(&mut self, name: &str) -> Result<(), Error> {
+        let Some(names) = self.names.as_mut() else {
+            return Ok(());
+        };
+
+        if !names.insert(String::from(name)) {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: format!("duplicate entry name: {name}"),
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Releases a name taken by [`ZipArchiveWriter::claim_name`] for an entry
+    /// that wasn't written after all.
+    fn release_name(&mut self, name: &str) {
+        if let Some(names) = self.names.as_mut() {
+            names.remove(name);
+        }
+    }
+
+    /// Records a finished entry for the central directory.
+    fn push_file(&mut self, file_header: FileHeader) -> Result<(), Error> {
+        match self.spill.as_mut() {
+            Some(spill) => spill.push(&file_header, &self.zip64_thresholds),
+            None => {
+                self.files.push(file_header);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Seeks the underlying writer, to overwrite or discard already written
+/// bytes. Stored as a function pointer so that only seekable archive writers
+/// need `W: Seek`.
+type Seeker<W> = fn(&mut W, SeekFrom) -> io::Result<u64>;
+
+/// Cuts the underlying writer short. See [`Truncate`].
+type Truncator<W> = fn(&mut W, u64) -> io::Result<()>;
+
+/// Storage whose length can be cut short to discard bytes already written.
+///
+/// Used by [`ZipArchiveWriter::new_truncating`] to remove aborted entries and
+/// by [`rewrite_comment`] to drop the tail of a longer comment.
+pub trait Truncate {
+    /// Sets the length of the storage to `len` bytes, discarding any bytes
+    /// past it. The position of the writer is left unchanged.
+    fn truncate(&mut self, len: u64) -> io::Result<()>;
+}
+
+impl Truncate for File {
+    #[inline]
+    fn truncate(&mut self, len: u64) -> io::Result<()> {
+        self.set_len(len)
+    }
+}
+
+impl Truncate for &File {
+    #[inline]
+    fn truncate(&mut self, len: u64) -> io::Result<()> {
+        self.set_len(len)
+    }
+}
+
+impl Truncate for Vec<u8> {
+    #[inline]
+    fn truncate(&mut self, len: u64) -> io::Result<()> {
+        Vec::truncate(self, usize::try_from(len).unwrap_or(usize::MAX));
+        Ok(())
+    }
+}
+
+impl<T> Truncate for io::Cursor<T>
+where
+    T: Truncate,
+{
+    #[inline]
+    fn truncate(&mut self, len: u64) -> io::Result<()> {
+        self.get_mut().truncate(len)
+    }
+}
+
+impl<T> Truncate for &mut T
+where
+    T: Truncate + ?Sized,
+{
+    #[inline]
+    fn truncate(&mut self, len: u64) -> io::Result<()> {
+        (**self).truncate(len)
+    }
+}
+
+/// Overwrites the bytes starting `distance` bytes before the current position
+/// with `data`, and restores the position afterwards.
+fn patch_behind<W>(writer: &mut W, seek: Seeker<W>, distance: u64, data: &[u8]) -> io::Result<()>
+where
+    W: Write,
+{
+    seek(writer, SeekFrom::Current(-(distance as i64)))?;
+    writer.write_all(data)?;
+    seek(
+        writer,
+        SeekFrom::Current(distance as i64 - data.len() as i64),
+    )?;
+    Ok(())
+}
+
+/// The encoding of an entry's name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// Names are written as UTF-8, and flagged as such when they contain
+    /// characters outside the portable subset of CP-437.
+    #[default]
+    Utf8,
+
+    /// Names are written as IBM Code Page 437 and never flagged as UTF-8,
+    /// for tools that predate the UTF-8 flag. Names with characters that
+    /// CP-437 can't represent are rejected.
+    Cp437,
+}
+
+impl Encoding {
+    /// Encodes a name for the file name field of the headers.
+    fn encode_name(self, name: &str) -> Result<Cow<'_, [u8]>, Error> {
+        match self {
+            Encoding::Utf8 => Ok(Cow::Borrowed(name.as_bytes())),
+            Encoding::Cp437 => cp437::encode(name).map(Cow::Owned).map_err(|ch| {
+                Error::from(ErrorKind::InvalidInput {
+                    msg: format!("{ch:?} can't be encoded in CP-437"),
+                })
+            }),
+        }
+    }
+}
+
+/// A compression level preset for [`ZipFileBuilder::deflate`].
+#[cfg(feature = "flate2")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeflateLevel {
+    /// Favors speed over compression ratio (level 1).
+    Fast,
+
+    /// Balances speed and compression ratio (level 6).
+    #[default]
+    Default,
+
+    /// Favors compression ratio over speed (level 9).
+    Best,
+}
+
+#[cfg(feature = "flate2")]
+impl DeflateLevel {
+    fn compression(self) -> flate2::Compression {
+        match self {
+            DeflateLevel::Fast => flate2::Compression::fast(),
+            DeflateLevel::Default => flate2::Compression::default(),
+            DeflateLevel::Best => flate2::Compression::best(),
+        }
+    }
 }
 
 /// A builder for creating a new file entry in a ZIP archive.
@@ -121,7 +659,14 @@ pub struct ZipFileBuilder<'archive, 'name, W> {
     name: &'name str,
     compression_method: CompressionMethod,
     modification_time: Option<UtcDateTime>,
+    access_time: Option<UtcDateTime>,
+    creation_time: Option<UtcDateTime>,
     unix_permissions: Option<u32>,
+    text: bool,
+    encoding: Encoding,
+    large_file: bool,
+    #[cfg(feature = "aes")]
+    aes_password: Option<AesPassword>,
 }
 
 impl<'archive, W> ZipFileBuilder<'archive, '_, W>
@@ -146,6 +691,28 @@ where
         self
     }
 
+    /// Sets the last access time for the file entry.
+    ///
+    /// The access time is written to the Extended Timestamp field of the
+    /// local file header only, as the central directory carries just the
+    /// modification time.
+    #[must_use]
+    #[inline]
+    pub fn access_time(mut self, access_time: UtcDateTime) -> Self {
+        self.access_time = Some(access_time);
+        self
+    }
+
+    /// Sets the creation time for the file entry.
+    ///
+    /// See [`ZipFileBuilder::access_time`] for where it is written.
+    #[must_use]
+    #[inline]
+    pub fn creation_time(mut self, creation_time: UtcDateTime) -> Self {
+        self.creation_time = Some(creation_time);
+        self
+    }
+
     /// Sets the Unix permissions for the file entry.
     ///
     /// Accepts either:
@@ -162,13 +729,340 @@ where
         self
     }
 
+    /// Sets the Unix file type and permissions for the file entry from an
+    /// [`EntryMode`], such as one read from another archive.
+    ///
+    /// Reading the entry back yields an equal [`EntryMode`].
+    ///
+    /// ```rust
+    /// use rawzip::EntryMode;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let mode = EntryMode::from_unix(0o755);
+    /// archive.new_file("run.sh").mode(mode).store_reader(&b"#!/bin/sh"[..])?;
+    /// archive.finish()?;
+    ///
+    /// let archive = rawzip::ZipArchive::from_slice(output.get_ref())?;
+    /// let entry = archive.entries().next_entry()?.unwrap();
+    /// assert_eq!(entry.mode(), mode);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn mode(self, mode: EntryMode) -> Self {
+        self.unix_permissions(mode.value())
+    }
+
+    /// Marks the file entry as text rather than binary data.
+    ///
+    /// Sets bit 0 of the internal file attributes, which some tools use to
+    /// decide whether to convert line endings on extraction.
+    #[must_use]
+    #[inline]
+    pub fn text(mut self, text: bool) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Sets the encoding of the entry's name, which defaults to
+    /// [`Encoding::Utf8`].
+    ///
+    /// Creating the entry fails when the name can't be represented in the
+    /// chosen encoding.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive
+    ///     .new_file("café.txt")
+    ///     .encoding(rawzip::Encoding::Cp437)
+    ///     .store_reader(&b"Hello, world!"[..])?;
+    /// assert!(archive
+    ///     .new_file("日本.txt")
+    ///     .encoding(rawzip::Encoding::Cp437)
+    ///     .create()
+    ///     .is_err());
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Marks the file entry as large, for when its size is known ahead of
+    /// time to reach the ZIP64 threshold (4 GiB unless lowered with
+    /// [`ZipArchiveWriterBuilder::zip64_file_size_threshold`]), or to force
+    /// ZIP64 sizes regardless.
+    ///
+    /// The local file header of a large entry carries a ZIP64 extra field, so
+    /// that streaming readers know to expect 8 byte sizes in the data
+    /// descriptor, which is always written with 8 byte sizes. Without it,
+    /// such readers can only guess at the descriptor's layout when an entry
+    /// turns out to be large. Large entries of seekable archives are finished
+    /// with a data descriptor rather than by patching the local header.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive
+    ///     .new_file("disk.img")
+    ///     .large_file(true)
+    ///     .store_reader(&b"Hello, world!"[..])?;
+    /// archive.finish()?;
+    ///
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let archive = rawzip::ZipArchive::from_reader_at(output.into_inner(), &mut buffer)?;
+    /// let wayfinder = archive.entries(&mut buffer).next_entry()?.unwrap().wayfinder();
+    /// let entry = archive.get_entry(wayfinder)?;
+    /// assert!(entry.data_descriptor()?.unwrap().is_zip64());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn large_file(mut self, large: bool) -> Self {
+        self.large_file = large;
+        self
+    }
+
+    /// Encrypts the file entry with WinZip AES (AE-2) using a key derived
+    /// from `password`.
+    ///
+    /// The entry is recorded with the AES compression method, while the
+    /// configured [`compression_method`](ZipFileBuilder::compression_method)
+    /// is kept in the AES extra field. Data written to the entry should still
+    /// be compressed with that method, and is encrypted as it is written. As
+    /// AE-2 entries store a CRC of zero, readers authenticate the data with
+    /// the appended authentication code instead.
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let mut file = archive
+    ///     .new_file("secret.txt")
+    ///     .encrypt_aes(b"password", rawzip::AesStrength::Aes256)
+    ///     .create()?;
+    /// let mut writer = rawzip::ZipDataWriter::new(&mut file);
+    /// writer.write_all(b"Hello, world!")?;
+    /// let (_, output) = writer.finish()?;
+    /// file.finish(output)?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "aes")]
+    #[must_use]
+    #[inline]
+    pub fn encrypt_aes(mut self, password: &[u8], strength: AesStrength) -> Self {
+        self.aes_password = Some(AesPassword {
+            password: password.to_vec(),
+            strength,
+        });
+        self
+    }
+
+    /// Creates the file entry with the Store method and fills it with the
+    /// contents of `reader`, returning the number of bytes stored.
+    ///
+    /// This is the builder counterpart to [`ZipArchiveWriter::store_reader`]
+    /// for when options like timestamps or permissions are needed.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let written = archive
+    ///     .new_file("script.sh")
+    ///     .unix_permissions(0o755)
+    ///     .store_reader(&b"#!/bin/sh\n"[..])?;
+    /// assert_eq!(written, 10);
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn store_reader<R>(self, mut reader: R) -> Result<u64, Error>
+    where
+        R: io::Read,
+    {
+        let mut file = self.compression_method(CompressionMethod::Store).create()?;
+        let mut writer = ZipDataWriter::new(&mut file);
+        io::copy(&mut reader, &mut writer)?;
+        let (_, output) = writer.finish()?;
+        file.finish(output)
+    }
+
+    /// Creates the file entry from the compressed data of `source`, verifying
+    /// it first, like [`ZipArchiveWriter::copy_entry_verified`].
+    ///
+    /// This is the builder counterpart for when options like permissions are
+    /// needed. Timestamps set on the builder take precedence over those of
+    /// the source, and the builder's compression method is replaced by the
+    /// source's.
+    ///
+    /// ```rust
+    /// use rawzip::{CompressionMethod, Error, ErrorKind, ZipArchive, ZipArchiveWriter};
+    ///
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let source = ZipArchive::from_reader_at(&data[..], &mut buffer)?;
+    /// let wayfinder = source.entries(&mut buffer).next_entry()?.unwrap().wayfinder();
+    /// let entry = source.get_entry(wayfinder)?;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = ZipArchiveWriter::new(&mut output);
+    /// archive
+    ///     .new_file("copy.txt")
+    ///     .unix_permissions(0o644)
+    ///     .copy_verified(&entry, |method, reader| match method {
+    ///         CompressionMethod::Deflate => Ok(flate2::read::DeflateDecoder::new(reader)),
+    ///         _ => Err(Error::from(ErrorKind::InvalidInput {
+    ///             msg: format!("unsupported compression method: {:?}", method),
+    ///         })),
+    ///     })?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn copy_verified<'source, R, F, D>(
+        mut self,
+        source: &ZipEntry<'source, R>,
+        decompressor: F,
+    ) -> Result<(), Error>
+    where
+        R: ReaderAt,
+        F: FnOnce(CompressionMethod, ZipReader<'source, R>) -> Result<D, Error>,
+        D: io::Read,
+    {
+        let local_header = source.local_header();
+        if local_header.flags & FLAG_ENCRYPTED != 0 {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "encrypted entries cannot be verified".to_string(),
+            }));
+        }
+
+        let method = local_header.compression_method.as_method();
+        let decompressed = decompressor(method, source.reader())?;
+        let mut verifier = ZipDataWriter::new(io::sink());
+        io::copy(&mut source.verifying_reader(decompressed), &mut verifier)
+            .map_err(Error::from_io)?;
+        let (_, output) = verifier.finish()?;
+
+        let extra_field = source.local_extra_field()?;
+        if self.modification_time.is_none() {
+            let modified = match extract_best_timestamp(
+                &extra_field,
+                local_header.last_mod_time,
+                local_header.last_mod_date,
+            ) {
+                ZipDateTimeKind::Utc(dt) => dt,
+                ZipDateTimeKind::Local(dt) => dt.assume_utc(),
+            };
+            self.modification_time = Some(modified);
+        }
+        if let Some(timestamps) = ExtendedTimestamps::from_extra_field(&extra_field) {
+            self.access_time = self.access_time.or(timestamps.accessed());
+            self.creation_time = self.creation_time.or(timestamps.created());
+        }
+
+        let mut file = self.compression_method(method).create()?;
+        io::copy(&mut source.reader(), &mut file)?;
+        file.finish(output)?;
+        Ok(())
+    }
+
+    /// Creates the file entry with the Deflate method and returns a writer
+    /// that compresses the file's content at the given `level`.
+    ///
+    /// The returned writer takes care of layering the compressor between a
+    /// [`ZipDataWriter`] and a [`ZipEntryWriter`], so the content is written
+    /// as-is and the entry completed with a single
+    /// [`finish`](DeflateEntryWriter::finish).
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let mut file = archive
+    ///     .new_file("file.txt")
+    ///     .deflate(rawzip::DeflateLevel::Best)?;
+    /// file.write_all(b"Hello, world!")?;
+    /// file.finish()?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "flate2")]
+    pub fn deflate(self, level: DeflateLevel) -> Result<DeflateEntryWriter<'archive, W>, Error> {
+        self.deflate_with(level.compression())
+    }
+
+    /// Like [`ZipFileBuilder::deflate`], but with any flate2 compression
+    /// level.
+    #[cfg(feature = "flate2")]
+    pub(crate) fn deflate_with(
+        self,
+        compression: flate2::Compression,
+    ) -> Result<DeflateEntryWriter<'archive, W>, Error> {
+        let file = self
+            .compression_method(CompressionMethod::Deflate)
+            .create()?;
+        let encoder = flate2::write::DeflateEncoder::new(file, compression);
+        Ok(DeflateEntryWriter {
+            inner: ZipDataWriter::new(encoder),
+        })
+    }
+
+    /// Creates the file entry without any content.
+    ///
+    /// This is the builder counterpart to [`ZipArchiveWriter::add_empty_file`]
+    /// for when options like timestamps or permissions are needed. The entry
+    /// is always stored, as there is no content to compress, whatever the
+    /// builder's compression method.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive.new_file("logs/.keep").unix_permissions(0o644).create_empty()?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn create_empty(self) -> Result<(), Error> {
+        let mut file = self.compression_method(CompressionMethod::Store).create()?;
+        let (_, output) = ZipDataWriter::new(&mut file).finish()?;
+        file.finish(output)?;
+        Ok(())
+    }
+
     /// Creates the file entry and returns a writer for the file's content.
     pub fn create(self) -> Result<ZipEntryWriter<'archive, W>, Error> {
         let options = ZipEntryOptions {
             compression_method: self.compression_method,
             modification_time: self.modification_time,
+            access_time: self.access_time,
+            creation_time: self.creation_time,
             unix_permissions: self.unix_permissions,
+            text: self.text,
+            encoding: self.encoding,
+            aes_extra_field: None,
+            large_file: self.large_file,
         };
+
+        #[cfg(feature = "aes")]
+        if let Some(password) = self.aes_password {
+            let encryptor = AesEncryptor::new(&password)?;
+            let options = ZipEntryOptions {
+                aes_extra_field: Some(AesExtraField::new(
+                    password.strength,
+                    self.compression_method,
+                )),
+                ..options
+            };
+            let mut file = self.archive.new_file_with_options(self.name, options)?;
+            file.write_encryption_header(encryptor)?;
+            return Ok(file);
+        }
+
         self.archive.new_file_with_options(self.name, options)
     }
 }
@@ -180,6 +1074,7 @@ pub struct ZipDirBuilder<'a, W> {
     name: &'a str,
     modification_time: Option<UtcDateTime>,
     unix_permissions: Option<u32>,
+    encoding: Encoding,
 }
 
 impl<W> ZipDirBuilder<'_, W>
@@ -206,12 +1101,38 @@ where
         self
     }
 
+    /// Sets the Unix file type and permissions for the directory entry from
+    /// an [`EntryMode`].
+    ///
+    /// See [`ZipFileBuilder::mode`] for details.
+    #[must_use]
+    #[inline]
+    pub fn mode(self, mode: EntryMode) -> Self {
+        self.unix_permissions(mode.value())
+    }
+
+    /// Sets the encoding of the directory's name.
+    ///
+    /// See [`ZipFileBuilder::encoding`] for details.
+    #[must_use]
+    #[inline]
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
     /// Creates the directory entry.
     pub fn create(self) -> Result<(), Error> {
         let options = ZipEntryOptions {
             compression_method: CompressionMethod::Store, // Directories always use Store
             modification_time: self.modification_time,
+            access_time: None,
+            creation_time: None,
             unix_permissions: self.unix_permissions,
+            text: false,
+            encoding: self.encoding,
+            aes_extra_field: None,
+            large_file: false,
         };
         self.archive.new_dir_with_options(self.name, options)
     }
@@ -229,6 +1150,8 @@ where
         compression_method: CompressionMethod,
         options: &ZipEntryOptions,
     ) -> Result<(), Error> {
+        let name = options.encoding.encode_name(file_path.as_ref())?;
+
         // Get DOS timestamp from options or use 0 as default
         let (dos_time, dos_date) = options
             .modification_time
@@ -236,26 +1159,60 @@ where
             .map(|dt| DosDateTime::from(dt).into_parts())
             .unwrap_or((0, 0));
 
-        let extra_field_len =
-            extended_timestamp_extra_field_size(options.modification_time.as_ref());
+        let timestamp_flags = options.extended_timestamp_flags();
+        let timestamps = [
+            options.modification_time,
+            options.access_time,
+            options.creation_time,
+        ];
+        let aes_extra_field_len = options.aes_extra_field.map_or(0, |_| AesExtraField::SIZE);
+        let zip64_extra_field_len = if options.large_file {
+            LOCAL_ZIP64_EXTRA_FIELD_SIZE
+        } else {
+            0
+        };
+        let extra_field_len = extended_timestamp_extra_field_size(timestamp_flags, &timestamps)
+            + aes_extra_field_len
+            + zip64_extra_field_len;
+
+        let version_needed = if options.aes_extra_field.is_some() {
+            AES_VERSION_NEEDED
+        } else if options.large_file {
+            ZIP64_VERSION_NEEDED
+        } else {
+            20
+        };
+
+        // The sizes of large entries are deferred to the ZIP64 extra field
+        let size = if options.large_file { u32::MAX } else { 0 };
 
         let header = ZipLocalFileHeaderFixed {
             signature: ZipLocalFileHeaderFixed::SIGNATURE,
-            version_needed: 20,
+            version_needed,
             flags,
             compression_method: compression_method.as_id(),
             last_mod_time: dos_time,
             last_mod_date: dos_date,
             crc32: 0,
-            compressed_size: 0,
-            uncompressed_size: 0,
-            file_name_len: file_path.len() as u16,
+            compressed_size: size,
+            uncompressed_size: size,
+            file_name_len: name.len() as u16,
             extra_field_len,
         };
 
         header.write(&mut self.writer)?;
-        self.writer.write_all(file_path.as_ref().as_bytes())?;
-        write_extended_timestamp_field(&mut self.writer, options.modification_time.as_ref())?;
+        self.writer.write_all(&name)?;
+        write_extended_timestamp_field(&mut self.writer, timestamp_flags, &timestamps)?;
+        if let Some(aes_extra_field) = &options.aes_extra_field {
+            aes_extra_field.write(&mut self.writer)?;
+        }
+        if options.large_file {
+            // Both sizes are zero, as the data descriptor carries them
+            self.writer.write_all(&ZIP64_EXTRA_FIELD_ID.to_le_bytes())?;
+            self.writer
+                .write_all(&(LOCAL_ZIP64_EXTRA_FIELD_SIZE - 4).to_le_bytes())?;
+            self.writer.write_all(&[0u8; 16])?;
+        }
 
         Ok(())
     }
@@ -282,6 +1239,7 @@ where
             name,
             modification_time: None,
             unix_permissions: None,
+            encoding: Encoding::Utf8,
         }
     }
 
@@ -302,18 +1260,89 @@ where
             }));
         }
 
+        let file_type = options.unix_permissions.map_or(0, |x| x & S_IFMT);
+        if file_type != 0 && file_type != S_IFDIR {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "directory permissions must not specify another file type".to_string(),
+            }));
+        }
+
+        options.encoding.encode_name(file_path.as_ref())?;
+        if self.auto_create_parents && self.directories.contains(file_path.as_ref()) {
+            return Ok(());
+        }
+
+        self.claim_name(file_path.as_ref())?;
+        let result = self
+            .create_parents(&file_path, options.modification_time, options.encoding)
+            .and_then(|()| self.write_dir(&file_path, options));
+        if result.is_err() {
+            self.release_name(file_path.as_ref());
+        }
+        result
+    }
+
+    /// Writes the directory entries missing for the ancestors of `file_path`
+    /// when the archive is configured to auto create parents.
+    fn create_parents(
+        &mut self,
+        file_path: &ZipFilePath<NormalizedPath>,
+        modification_time: Option<UtcDateTime>,
+        encoding: Encoding,
+    ) -> Result<(), Error> {
+        if !self.auto_create_parents {
+            return Ok(());
+        }
+
+        let path: &str = file_path.as_ref();
+        let parents = path
+            .match_indices('/')
+            .map(|(i, _)| &path[..=i])
+            .filter(|parent| parent.len() < path.len());
+        for parent in parents {
+            if self.directories.contains(parent) {
+                continue;
+            }
+
+            let options = ZipEntryOptions {
+                compression_method: CompressionMethod::Store,
+                modification_time,
+                access_time: None,
+                creation_time: None,
+                unix_permissions: None,
+                text: false,
+                encoding,
+                aes_extra_field: None,
+                large_file: false,
+            };
+            self.claim_name(parent)?;
+            if let Err(e) = self.write_dir(&ZipFilePath::from_str(parent), options) {
+                self.release_name(parent);
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a validated directory entry.
+    fn write_dir(
+        &mut self,
+        file_path: &ZipFilePath<NormalizedPath>,
+        options: ZipEntryOptions,
+    ) -> Result<(), Error> {
         let local_header_offset = self.writer.count();
         let mut flags = 0u16;
-        if file_path.needs_utf8_encoding() {
+        if options.encoding == Encoding::Utf8 && file_path.needs_utf8_encoding() {
             flags |= FLAG_UTF8_ENCODING;
         } else {
             flags &= !FLAG_UTF8_ENCODING;
         }
 
-        self.write_local_header(&file_path, flags, CompressionMethod::Store, &options)?;
+        self.write_local_header(file_path, flags, CompressionMethod::Store, &options)?;
 
         let file_header = FileHeader {
-            name: file_path.into_owned(),
+            name: file_path.clone().into_owned(),
             compression_method: CompressionMethod::Store,
             local_header_offset,
             compressed_size: 0,
@@ -321,11 +1350,15 @@ where
             crc: 0,
             flags,
             modification_time: options.modification_time,
+            extended_timestamp_flags: options.extended_timestamp_flags(),
             unix_permissions: options.unix_permissions,
+            text: false,
+            encoding: options.encoding,
+            aes_extra_field: None,
         };
-        self.files.push(file_header);
-
-        Ok(())
+        self.directories
+            .insert(String::from(file_header.name.as_ref()));
+        self.push_file(file_header)
     }
 
     /// Creates a builder for adding a new file to the archive.
@@ -353,10 +1386,73 @@ where
             name,
             compression_method: CompressionMethod::Store,
             modification_time: None,
+            access_time: None,
+            creation_time: None,
             unix_permissions: None,
+            text: false,
+            encoding: Encoding::Utf8,
+            large_file: false,
+            #[cfg(feature = "aes")]
+            aes_password: None,
         }
     }
 
+    /// Writes `bytes` as-is to the underlying writer, returning the offset
+    /// after them.
+    ///
+    /// The written bytes are accounted for, so the offsets of entries added
+    /// afterwards remain correct. This allows inserting data like a
+    /// self-extracting stub ahead of the first entry.
+    ///
+    /// Nothing validates the bytes, so misuse (for instance, writing while an
+    /// entry is in progress or writing something a reader mistakes for a zip
+    /// record) produces an invalid archive.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let offset = archive.write_raw(b"#!/bin/sh\nexit 0\n")?;
+    /// assert_eq!(offset, 17);
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_raw(&mut self, bytes: &[u8]) -> Result<u64, Error> {
+        self.writer.write_all(bytes)?;
+        Ok(self.writer.count())
+    }
+
+    /// Adds a symbolic link to the archive that points at `target`.
+    ///
+    /// Symlinks are stored as an uncompressed file whose content is the target
+    /// path, with the symlink file type in the Unix permissions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::io::Cursor;
+    /// # let mut output = Cursor::new(Vec::new());
+    /// # let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive.new_symlink("link", "../target")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_symlink(&mut self, name: &str, target: &str) -> Result<(), Error> {
+        if ZipFilePath::from_str(name).is_dir() {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "symlink cannot be a directory".to_string(),
+            }));
+        }
+
+        let mut file = self
+            .new_file(name)
+            .unix_permissions(S_IFLNK | 0o777)
+            .create()?;
+        let mut writer = ZipDataWriter::new(&mut file);
+        writer.write_all(target.as_bytes())?;
+        let (_, output) = writer.finish()?;
+        file.finish(output)?;
+        Ok(())
+    }
+
     /// Adds a new file to the archive with options (internal method).
     fn new_file_with_options(
         &mut self,
@@ -371,120 +1467,219 @@ where
             }));
         }
 
-        let local_header_offset = self.writer.count();
-        let mut flags = FLAG_DATA_DESCRIPTOR;
-        if file_path.needs_utf8_encoding() {
-            flags |= FLAG_UTF8_ENCODING;
-        } else {
-            flags &= !FLAG_UTF8_ENCODING;
-        }
-
-        self.write_local_header(&file_path, flags, options.compression_method, &options)?;
+        options.encoding.encode_name(file_path.as_ref())?;
+        self.claim_name(file_path.as_ref())?;
+        let (local_header_offset, flags) = match self.start_file(&file_path, &options) {
+            Ok(started) => started,
+            Err(e) => {
+                self.release_name(file_path.as_ref());
+                return Err(e);
+            }
+        };
 
         Ok(ZipEntryWriter::new(
             self,
             file_path.into_owned(),
             local_header_offset,
-            options.compression_method,
             flags,
-            options.modification_time,
-            options.unix_permissions,
+            options,
         ))
     }
 
-    /// Finishes writing the archive and returns the underlying writer.
-    ///
-    /// This writes the central directory and the end of central directory
-    /// record. ZIP64 format is used automatically when thresholds are exceeded.
-    pub fn finish(mut self) -> Result<W, Error>
-    where
-        W: Write,
-    {
-        let central_directory_offset = self.writer.count();
-        let total_entries = self.files.len();
-
-        // Determine if we need ZIP64 format
-        let needs_zip64 = total_entries >= ZIP64_THRESHOLD_ENTRIES
-            || central_directory_offset >= ZIP64_THRESHOLD_OFFSET
-            || self.files.iter().any(|f| f.needs_zip64());
-
-        // Write central directory entries
-        for file in &self.files {
-            // Central file header signature
-            self.writer
-                .write_all(&CENTRAL_HEADER_SIGNATURE.to_le_bytes())?;
-
-            // Version made by and version needed to extract
-            let version_needed = if file.needs_zip64() {
-                ZIP64_VERSION_NEEDED
-            } else {
-                20
-            };
-
-            // Set version_made_by to indicate Unix when Unix permissions are present
-            let version_made_by_hi = file.unix_permissions.map(|_| CREATOR_UNIX).unwrap_or(0);
-            let version_made_by = (version_made_by_hi << 8) | version_needed;
-
-            self.writer.write_all(&version_made_by.to_le_bytes())?; // Version made by
-            self.writer.write_all(&version_needed.to_le_bytes())?; // Version needed to extract
-
-            // General purpose bit flag
-            self.writer.write_all(&file.flags.to_le_bytes())?;
-
-            // Compression method
-            self.writer
-                .write_all(&file.compression_method.as_id().as_u16().to_le_bytes())?;
-
-            // Last mod file time and date
-            let (dos_time, dos_date) = file
-                .modification_time
-                .as_ref()
-                .map(|dt| DosDateTime::from(dt).into_parts())
-                .unwrap_or((0, 0));
-            self.writer.write_all(&dos_time.to_le_bytes())?;
-            self.writer.write_all(&dos_date.to_le_bytes())?;
-
-            // CRC-32
-            self.writer.write_all(&file.crc.to_le_bytes())?;
+    /// Writes the missing parents and the local file header of a new file
+    /// entry, returning the offset of the header and the entry's flags.
+    fn start_file(
+        &mut self,
+        file_path: &ZipFilePath<NormalizedPath>,
+        options: &ZipEntryOptions,
+    ) -> Result<(u64, u16), Error> {
+        self.create_parents(file_path, options.modification_time, options.encoding)?;
 
-            // Compressed size - use 0xFFFFFFFF if ZIP64
-            let compressed_size = file.compressed_size.min(ZIP64_THRESHOLD_FILE_SIZE) as u32;
-            self.writer.write_all(&compressed_size.to_le_bytes())?;
+        let local_header_offset = self.writer.count();
+        let mut flags = FLAG_DATA_DESCRIPTOR;
+        if options.encoding == Encoding::Utf8 && file_path.needs_utf8_encoding() {
+            flags |= FLAG_UTF8_ENCODING;
+        } else {
+            flags &= !FLAG_UTF8_ENCODING;
+        }
 
-            // Uncompressed size - use 0xFFFFFFFF if ZIP64
-            let uncompressed_size = file.uncompressed_size.min(ZIP64_THRESHOLD_FILE_SIZE) as u32;
-            self.writer.write_all(&uncompressed_size.to_le_bytes())?;
+        if options.aes_extra_field.is_some() {
+            flags |= FLAG_ENCRYPTED;
+        }
 
-            // File name length
-            self.writer
-                .write_all(&(file.name.len() as u16).to_le_bytes())?;
+        let compression_method = options.header_compression_method();
+        self.write_local_header(file_path, flags, compression_method, options)?;
+        Ok((local_header_offset, flags))
+    }
 
-            // Extra field length
-            let extra_field_length = file.zip64_extra_field_size()
-                + extended_timestamp_extra_field_size(file.modification_time.as_ref());
-            self.writer.write_all(&extra_field_length.to_le_bytes())?;
+    /// Writes `data` as a deflate compressed file entry at the given
+    /// compression `level` (0-9).
+    ///
+    /// This encapsulates the ordering required when layering a compressor
+    /// between a [`ZipDataWriter`] and a [`ZipEntryWriter`]. Returns the
+    /// number of compressed bytes written.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive.write_deflate("file.txt", 9, b"Hello, world!")?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "flate2")]
+    pub fn write_deflate(&mut self, name: &str, level: u32, data: &[u8]) -> Result<u64, Error> {
+        let mut file = self
+            .new_file(name)
+            .deflate_with(flate2::Compression::new(level))?;
+        file.write_all(data)?;
+        file.finish()
+    }
 
-            // File comment length
-            self.writer.write_all(&0u16.to_le_bytes())?;
+    /// Stores the contents of `reader` uncompressed as a new file entry,
+    /// returning the number of bytes stored.
+    ///
+    /// This collapses creating the entry, copying through a
+    /// [`ZipDataWriter`], and finishing the entry into one call. Use
+    /// [`ZipFileBuilder::store_reader`] to also set entry options.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let file = std::fs::File::open("assets/test.zip")?;
+    /// archive.store_reader("test.zip", file)?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn store_reader<R>(&mut self, name: &str, reader: R) -> Result<u64, Error>
+    where
+        R: io::Read,
+    {
+        self.new_file(name).store_reader(reader)
+    }
 
-            // Disk number start, internal file attributes
-            self.writer.write_all(&[0u8; 4])?;
+    /// Adds a file entry without any content, like a marker or placeholder
+    /// file.
+    ///
+    /// The entry is stored with a size and CRC of zero. Use
+    /// [`ZipFileBuilder::create_empty`] to also set entry options.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive.add_empty_file("__init__.py")?;
+    /// archive.finish()?;
+    ///
+    /// let archive = rawzip::ZipArchive::from_slice(output.get_ref())?;
+    /// let entry = archive.entries().next_entry()?.unwrap();
+    /// assert_eq!(entry.uncompressed_size_hint(), 0);
+    /// assert!(archive.get_entry(entry.wayfinder())?.data().is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_empty_file(&mut self, name: &str) -> Result<(), Error> {
+        self.new_file(name).create_empty()
+    }
 
-            // External file attributes
-            let external_attrs = file.unix_permissions.map(|x| x << 16).unwrap_or(0);
-            self.writer.write_all(&external_attrs.to_le_bytes())?;
+    /// Copies the compressed data of `source` into a new file entry named
+    /// `name`, verifying the data along the way.
+    ///
+    /// The `decompressor` is given the entry's compression method and
+    /// compressed data, like [`ZipEntry::read_to_vec`], and the decompressed
+    /// output is checked against the source's size and CRC. Verification
+    /// happens before anything is written, so a corrupt source is never
+    /// propagated into this archive. The compressed data is copied as is,
+    /// without recompressing, which means the source is read twice: once to
+    /// verify it and once to copy it.
+    ///
+    /// The new entry takes its timestamps from the source's local file
+    /// header. Permissions and the text flag are only recorded in the central
+    /// directory, so use [`ZipFileBuilder::copy_verified`] to set them.
+    ///
+    /// ```rust
+    /// use rawzip::{CompressionMethod, Error, ErrorKind, ZipArchive, ZipArchiveWriter};
+    ///
+    /// let data = std::fs::read("assets/test.zip")?;
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let source = ZipArchive::from_reader_at(&data[..], &mut buffer)?;
+    /// let wayfinder = source.entries(&mut buffer).next_entry()?.unwrap().wayfinder();
+    /// let entry = source.get_entry(wayfinder)?;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = ZipArchiveWriter::new(&mut output);
+    /// archive.copy_entry_verified(&entry, |method, reader| match method {
+    ///     CompressionMethod::Deflate => Ok(flate2::read::DeflateDecoder::new(reader)),
+    ///     _ => Err(Error::from(ErrorKind::InvalidInput {
+    ///         msg: format!("unsupported compression method: {:?}", method),
+    ///     })),
+    /// }, "copy.txt")?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source is encrypted, the decompressor fails,
+    /// or the decompressed size or CRC doesn't match the source.
+    pub fn copy_entry_verified<'archive, R, F, D>(
+        &mut self,
+        source: &ZipEntry<'archive, R>,
+        decompressor: F,
+        name: &str,
+    ) -> Result<(), Error>
+    where
+        R: ReaderAt,
+        F: FnOnce(CompressionMethod, ZipReader<'archive, R>) -> Result<D, Error>,
+        D: io::Read,
+    {
+        self.new_file(name).copy_verified(source, decompressor)
+    }
 
-            // Local header offset - use 0xFFFFFFFF if ZIP64
-            let local_header_offset = file.local_header_offset.min(ZIP64_THRESHOLD_OFFSET) as u32;
-            self.writer.write_all(&local_header_offset.to_le_bytes())?;
+    /// Finishes writing the archive and returns the underlying writer.
+    ///
+    /// This writes the central directory and the end of central directory
+    /// record. ZIP64 format is used automatically when thresholds are exceeded.
+    ///
+    /// Small writes, like those of headers, are buffered until an entry's
+    /// data is flushed or the archive is finished, so an archive that is
+    /// dropped before finishing may be missing its last bytes.
+    pub fn finish(self) -> Result<W, Error>
+    where
+        W: Write,
+    {
+        self.finish_with_stats().map(|(writer, _)| writer)
+    }
 
-            // File name
-            self.writer.write_all(file.name.as_ref().as_bytes())?;
+    /// Finishes writing the archive like [`ZipArchiveWriter::finish`], and
+    /// also returns a summary of what was written.
+    ///
+    /// ```rust
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// archive.new_dir("dir/").create()?;
+    /// let (_, stats) = archive.finish_with_stats()?;
+    /// assert_eq!(stats.entries(), 1);
+    /// assert_eq!(stats.bytes_written(), output.get_ref().len() as u64);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn finish_with_stats(mut self) -> Result<(W, ArchiveStats), Error>
+    where
+        W: Write,
+    {
+        let central_directory_offset = self.writer.count();
+        let total_entries = self.entry_count();
+        let thresholds = self.zip64_thresholds;
 
-            // ZIP64 extended information extra field
-            file.write_zip64_extra_field(&mut self.writer)?;
+        // Determine if we need ZIP64 format
+        let needs_zip64 = total_entries >= thresholds.entries
+            || central_directory_offset >= thresholds.offset
+            || self.files.iter().any(|f| f.needs_zip64(&thresholds))
+            || self.spill.as_ref().is_some_and(|spill| spill.zip64);
 
-            write_extended_timestamp_field(&mut self.writer, file.modification_time.as_ref())?;
+        // Write central directory entries
+        for file in &self.files {
+            file.write_central_record(&mut self.writer, &thresholds)?;
+        }
+        if let Some(spill) = self.spill.take() {
+            spill.copy_to(&mut self.writer)?;
         }
 
         let central_directory_end = self.writer.count();
@@ -509,27 +1704,85 @@ where
         // Write regular End of Central Directory Record
         self.writer.write_all(&END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES)?;
 
-        // Disk numbers
-        self.writer.write_all(&[0u8; 4])?;
+        // Number of this disk. Archives are always written to a single disk,
+        // which strict readers expect to be disk 0.
+        self.writer.write_all(&0u16.to_le_bytes())?;
+
+        // Number of the disk with the start of the central directory
+        self.writer.write_all(&0u16.to_le_bytes())?;
 
-        // Number of entries - use 0xFFFF if ZIP64
-        let entries_count = total_entries.min(ZIP64_THRESHOLD_ENTRIES) as u16;
+        // Number of entries on this disk and in total, which are the same
+        // for a single disk archive - use 0xFFFF if ZIP64
+        let entries_count = thresholds.entries_field(total_entries);
         self.writer.write_all(&entries_count.to_le_bytes())?;
         self.writer.write_all(&entries_count.to_le_bytes())?;
 
         // Central directory size - use 0xFFFFFFFF if ZIP64
-        let cd_size = central_directory_size.min(ZIP64_THRESHOLD_OFFSET) as u32;
+        let cd_size = thresholds.offset_field(central_directory_size);
         self.writer.write_all(&cd_size.to_le_bytes())?;
 
-        // Central directory offset - use 0xFFFFFFFF if ZIP64
-        let cd_offset = central_directory_offset.min(ZIP64_THRESHOLD_OFFSET) as u32;
+        // Central directory offset - use 0xFFFFFFFF if ZIP64 so that readers
+        // know to consult the ZIP64 end of central directory record
+        let cd_offset = if needs_zip64 {
+            u32::MAX
+        } else {
+            central_directory_offset as u32
+        };
         self.writer.write_all(&cd_offset.to_le_bytes())?;
 
         // Comment length
         self.writer.write_all(&0u16.to_le_bytes())?;
 
         self.writer.flush()?;
-        Ok(self.writer.writer)
+        let stats = ArchiveStats {
+            bytes_written: self.writer.count() - self.start_offset,
+            central_directory_offset,
+            central_directory_size,
+            entries: total_entries,
+            zip64: needs_zip64,
+        };
+        Ok((self.writer.into_inner()?, stats))
+    }
+}
+
+/// A summary of a finished archive, returned from
+/// [`ZipArchiveWriter::finish_with_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveStats {
+    bytes_written: u64,
+    central_directory_offset: u64,
+    central_directory_size: u64,
+    entries: usize,
+    zip64: bool,
+}
+
+impl ArchiveStats {
+    /// Returns the number of bytes written for the archive.
+    ///
+    /// For writers started [`at_offset`](ZipArchiveWriter::at_offset), this
+    /// excludes the bytes before the offset.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Returns the offset of the central directory.
+    pub fn central_directory_offset(&self) -> u64 {
+        self.central_directory_offset
+    }
+
+    /// Returns the size of the central directory in bytes.
+    pub fn central_directory_size(&self) -> u64 {
+        self.central_directory_size
+    }
+
+    /// Returns the number of entries in the archive.
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Returns true if ZIP64 end of central directory records were written.
+    pub fn zip64(&self) -> bool {
+        self.zip64
     }
 }
 
@@ -544,32 +1797,30 @@ pub struct ZipEntryWriter<'a, W> {
     compressed_bytes: u64,
     name: ZipFilePath<NormalizedPathBuf>,
     local_header_offset: u64,
-    compression_method: CompressionMethod,
     flags: u16,
-    modification_time: Option<UtcDateTime>,
-    unix_permissions: Option<u32>,
+    options: ZipEntryOptions,
+    #[cfg(feature = "aes")]
+    encryptor: Option<AesEncryptor>,
 }
 
 impl<'a, W> ZipEntryWriter<'a, W> {
     /// Creates a new `TrackingWriter` wrapping the given writer.
-    pub(crate) fn new(
+    fn new(
         inner: &'a mut ZipArchiveWriter<W>,
         name: ZipFilePath<NormalizedPathBuf>,
         local_header_offset: u64,
-        compression_method: CompressionMethod,
         flags: u16,
-        modification_time: Option<UtcDateTime>,
-        unix_permissions: Option<u32>,
+        options: ZipEntryOptions,
     ) -> Self {
         ZipEntryWriter {
             inner,
             compressed_bytes: 0,
             name,
             local_header_offset,
-            compression_method,
             flags,
-            modification_time,
-            unix_permissions,
+            options,
+            #[cfg(feature = "aes")]
+            encryptor: None,
         }
     }
 
@@ -578,57 +1829,196 @@ impl<'a, W> ZipEntryWriter<'a, W> {
         self.compressed_bytes
     }
 
+    /// Borrows this writer so that a compressor can wrap it without taking
+    /// ownership.
+    ///
+    /// Compressors like `flate2::write::DeflateEncoder` take their sink by
+    /// value, but the entry writer is still needed afterwards to finish the
+    /// entry. The order of operations is: finish the [`ZipDataWriter`], then
+    /// finish the compressor, and finally finish the entry.
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    /// use flate2::{write::DeflateEncoder, Compression};
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let mut file = archive
+    ///     .new_file("file.txt")
+    ///     .compression_method(rawzip::CompressionMethod::Deflate)
+    ///     .create()?;
+    /// let encoder = DeflateEncoder::new(file.by_ref(), Compression::new(9));
+    /// let mut writer = rawzip::ZipDataWriter::new(encoder);
+    /// writer.write_all(b"Hello, world!")?;
+    /// let (encoder, output) = writer.finish()?;
+    /// encoder.finish()?;
+    /// file.finish(output)?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn by_ref(&mut self) -> &mut Self {
+        self
+    }
+
     /// Finishes writing the file entry.
     ///
-    /// This writes the data descriptor if necessary and adds the file entry to the central directory.
-    pub fn finish(self, mut output: DataDescriptorOutput) -> Result<u64, Error>
+    /// This writes the data descriptor (or, for seekable archives, patches the
+    /// local header) and adds the file entry to the central directory.
+    pub fn finish(mut self, mut output: DataDescriptorOutput) -> Result<u64, Error>
     where
         W: Write,
     {
-        output.compressed_size = self.compressed_bytes;
-
-        // Write data descriptor
-        self.inner
-            .writer
-            .write_all(&DataDescriptor::SIGNATURE.to_le_bytes())?;
+        #[cfg(feature = "aes")]
+        if let Some(encryptor) = self.encryptor.take() {
+            let code = encryptor.authentication_code();
+            self.inner.writer.write_all(&code)?;
+            self.compressed_bytes += code.len() as u64;
+
+            // AE-2 entries rely on the authentication code instead of a CRC
+            output.crc = 0;
+        }
 
-        self.inner.writer.write_all(&output.crc.to_le_bytes())?;
+        output.compressed_size = self.compressed_bytes;
 
-        if output.compressed_size >= ZIP64_THRESHOLD_FILE_SIZE
-            || output.uncompressed_size >= ZIP64_THRESHOLD_FILE_SIZE
-        {
-            // Use 64-bit sizes for ZIP64
-            self.inner
-                .writer
-                .write_all(&output.compressed_size.to_le_bytes())?;
-            self.inner
-                .writer
-                .write_all(&output.uncompressed_size.to_le_bytes())?;
-        } else {
-            // Use 32-bit sizes for standard ZIP
-            self.inner
-                .writer
-                .write_all(&(output.compressed_size as u32).to_le_bytes())?;
-            self.inner
-                .writer
-                .write_all(&(output.uncompressed_size as u32).to_le_bytes())?;
+        let thresholds = self.inner.zip64_thresholds;
+        let needs_zip64_sizes = self.options.large_file
+            || output.compressed_size >= thresholds.file_size
+            || output.uncompressed_size >= thresholds.file_size;
+
+        let mut flags = self.flags;
+        match self.inner.seeker {
+            Some(seek) if !needs_zip64_sizes => {
+                // Fill in the local header fields that were deferred to the
+                // data descriptor.
+                flags &= !FLAG_DATA_DESCRIPTOR;
+                let distance = self.inner.writer.count() - self.local_header_offset;
+                let writer = self.inner.writer.get_mut()?;
+                patch_behind(writer, seek, distance - 6, &flags.to_le_bytes())?;
+
+                let mut fields = [0u8; 12];
+                fields[0..4].copy_from_slice(&output.crc.to_le_bytes());
+                fields[4..8].copy_from_slice(&(output.compressed_size as u32).to_le_bytes());
+                fields[8..12].copy_from_slice(&(output.uncompressed_size as u32).to_le_bytes());
+                patch_behind(writer, seek, distance - 14, &fields)?;
+            }
+            _ => self.write_data_descriptor(&output, needs_zip64_sizes)?,
         }
 
         let file_header = FileHeader {
             name: self.name,
-            compression_method: self.compression_method,
+            compression_method: self.options.header_compression_method(),
             local_header_offset: self.local_header_offset,
             compressed_size: output.compressed_size,
             uncompressed_size: output.uncompressed_size,
             crc: output.crc,
-            flags: self.flags,
-            modification_time: self.modification_time,
-            unix_permissions: self.unix_permissions,
+            flags,
+            modification_time: self.options.modification_time,
+            extended_timestamp_flags: self.options.extended_timestamp_flags(),
+            unix_permissions: self.options.unix_permissions,
+            text: self.options.text,
+            encoding: self.options.encoding,
+            aes_extra_field: self.options.aes_extra_field,
         };
-        self.inner.files.push(file_header);
+        self.inner.push_file(file_header)?;
 
         Ok(self.compressed_bytes)
     }
+
+    /// Discards the file entry instead of finishing it, so that it is left
+    /// out of the central directory.
+    ///
+    /// Returns the number of bytes of the discarded entry that remain in the
+    /// output:
+    ///
+    /// - Archives created with [`ZipArchiveWriter::new_truncating`] truncate
+    ///   the output back to the entry's local header, so nothing remains.
+    /// - Other seekable archives (see [`ZipArchiveWriter::new_seekable`]) are
+    ///   rewound to the entry's local header, and the next entry (or the
+    ///   central directory) overwrites it. Whatever isn't overwritten remains
+    ///   past the end of the finished archive, so truncate the output to the
+    ///   length reported by [`ZipArchiveWriter::finish_with_stats`].
+    /// - Other archives can't take back written bytes, so the local header
+    ///   and data remain as an orphan that no central directory record
+    ///   refers to. Dropping an entry writer without finishing it has the
+    ///   same effect.
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new_truncating(&mut output);
+    /// let mut file = archive.new_file("partial.txt").create()?;
+    /// file.write_all(b"incomplete")?;
+    /// assert_eq!(file.abort()?, 0);
+    /// let (_, stats) = archive.finish_with_stats()?;
+    /// assert_eq!(output.get_ref().len() as u64, stats.bytes_written());
+    ///
+    /// let archive = rawzip::ZipArchive::from_slice(output.get_ref())?;
+    /// assert_eq!(archive.entries_hint(), 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn abort(self) -> Result<u64, Error>
+    where
+        W: Write,
+    {
+        self.inner.release_name(self.name.as_ref());
+
+        let distance = self.inner.writer.count() - self.local_header_offset;
+        let Some(seek) = self.inner.seeker else {
+            return Ok(distance);
+        };
+
+        let writer = self.inner.writer.get_mut()?;
+        let position = seek(writer, SeekFrom::Current(-(distance as i64)))?;
+        self.inner.writer.count = self.local_header_offset;
+
+        match self.inner.truncator {
+            Some(truncate) => {
+                truncate(self.inner.writer.get_mut()?, position)?;
+                Ok(0)
+            }
+            None => Ok(distance),
+        }
+    }
+}
+
+impl<W> ZipEntryWriter<'_, W>
+where
+    W: Write,
+{
+    /// Writes the salt and password verification value, and encrypts all
+    /// data written afterwards.
+    #[cfg(feature = "aes")]
+    fn write_encryption_header(&mut self, encryptor: AesEncryptor) -> Result<(), Error> {
+        let header = encryptor.header();
+        self.inner.writer.write_all(header)?;
+        self.compressed_bytes += header.len() as u64;
+        self.encryptor = Some(encryptor);
+        Ok(())
+    }
+
+    fn write_data_descriptor(
+        &mut self,
+        output: &DataDescriptorOutput,
+        zip64: bool,
+    ) -> Result<(), Error> {
+        let writer = &mut self.inner.writer;
+        writer.write_all(&DataDescriptor::SIGNATURE.to_le_bytes())?;
+        writer.write_all(&output.crc.to_le_bytes())?;
+
+        if zip64 {
+            // Use 64-bit sizes for ZIP64
+            writer.write_all(&output.compressed_size.to_le_bytes())?;
+            writer.write_all(&output.uncompressed_size.to_le_bytes())?;
+        } else {
+            // Use 32-bit sizes for standard ZIP
+            writer.write_all(&(output.compressed_size as u32).to_le_bytes())?;
+            writer.write_all(&(output.uncompressed_size as u32).to_le_bytes())?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<W> Write for ZipEntryWriter<'_, W>
@@ -636,6 +2026,20 @@ where
     W: Write,
 {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[cfg(feature = "aes")]
+        if let Some(encryptor) = self.encryptor.as_mut() {
+            // The keystream can't be rewound, so encrypted chunks are written
+            // in full
+            let mut chunk = [0u8; 4096];
+            let len = buf.len().min(chunk.len());
+            let chunk = &mut chunk[..len];
+            chunk.copy_from_slice(&buf[..len]);
+            encryptor.encrypt(chunk);
+            self.inner.writer.write_all(chunk)?;
+            self.compressed_bytes += len as u64;
+            return Ok(len);
+        }
+
         let bytes_written = self.inner.writer.write(buf)?;
         self.compressed_bytes += bytes_written as u64;
         Ok(bytes_written)
@@ -646,6 +2050,46 @@ where
     }
 }
 
+/// A writer that deflate compresses the content of a file entry, as returned
+/// by [`ZipFileBuilder::deflate`].
+///
+/// After writing all data, call `finish` to complete the entry.
+#[cfg(feature = "flate2")]
+pub struct DeflateEntryWriter<'a, W>
+where
+    W: Write,
+{
+    inner: ZipDataWriter<flate2::write::DeflateEncoder<ZipEntryWriter<'a, W>>>,
+}
+
+#[cfg(feature = "flate2")]
+impl<W> DeflateEntryWriter<'_, W>
+where
+    W: Write,
+{
+    /// Flushes the compressor and finishes writing the file entry, returning
+    /// the number of compressed bytes written.
+    pub fn finish(self) -> Result<u64, Error> {
+        let (encoder, output) = self.inner.finish()?;
+        let file = encoder.finish()?;
+        file.finish(output)
+    }
+}
+
+#[cfg(feature = "flate2")]
+impl<W> Write for DeflateEntryWriter<'_, W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 /// A writer for the uncompressed data of a Zip file entry.
 ///
 /// This writer will keep track of the data necessary to write the data
@@ -697,6 +2141,42 @@ impl<W> ZipDataWriter<W> {
 
         Ok((self.inner, output))
     }
+
+    /// Returns the data descriptor for the data written since creation (or
+    /// the last reset), and zeroes the CRC and byte count so the writer can
+    /// be reused for the next entry.
+    ///
+    /// Unlike [`ZipDataWriter::finish`], the inner writer is kept and not
+    /// flushed. The caller must have already logically finished the entry
+    /// (eg: flushed or reset the wrapped compressor) before resetting.
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let mut writer = rawzip::ZipDataWriter::new(Vec::new());
+    /// for name in ["a.txt", "b.txt"] {
+    ///     writer.write_all(name.as_bytes())?;
+    ///     let descriptor = writer.reset();
+    ///     let mut file = archive.new_file(name).create()?;
+    ///     file.write_all(writer.get_mut())?;
+    ///     file.finish(descriptor)?;
+    ///     writer.get_mut().clear();
+    /// }
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reset(&mut self) -> DataDescriptorOutput {
+        let output = DataDescriptorOutput {
+            crc: self.crc,
+            compressed_size: 0,
+            uncompressed_size: self.uncompressed_bytes,
+        };
+        self.crc = 0;
+        self.uncompressed_bytes = 0;
+        output
+    }
 }
 
 impl<W> Write for ZipDataWriter<W>
@@ -724,6 +2204,51 @@ pub struct DataDescriptorOutput {
 }
 
 impl DataDescriptorOutput {
+    /// Creates the output for data that was compressed elsewhere, from the
+    /// CRC32 checksum and size of the original uncompressed data.
+    ///
+    /// This allows writing already compressed bytes (eg: cached deflate
+    /// blobs) straight to a [`ZipEntryWriter`] without recompressing them.
+    /// Passing them through a [`ZipDataWriter`] instead would checksum the
+    /// compressed bytes.
+    ///
+    /// This takes the place of a dedicated `new_precompressed` constructor on
+    /// [`ZipArchiveWriter`]: the compression method and other entry options
+    /// are set through the [`ZipFileBuilder`] returned by
+    /// [`ZipArchiveWriter::new_file`], and the CRC and size are only needed
+    /// once the entry is finished. A regular [`ZipEntryWriter`] finished with
+    /// this output therefore covers pre-compressed data without a second way
+    /// of creating entries.
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    /// use rawzip::{CompressionMethod, DataDescriptorOutput};
+    ///
+    /// // A raw deflate stream of "Hello, world!"
+    /// let compressed = [
+    ///     0xf3, 0x48, 0xcd, 0xc9, 0xc9, 0xd7, 0x51, 0x28, 0xcf, 0x2f, 0xca, 0x49, 0x51, 0x04, 0x00,
+    /// ];
+    ///
+    /// let mut output = std::io::Cursor::new(Vec::new());
+    /// let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    /// let mut file = archive
+    ///     .new_file("hello.txt")
+    ///     .compression_method(CompressionMethod::Deflate)
+    ///     .create()?;
+    /// file.write_all(&compressed)?;
+    /// let crc = rawzip::crc32(b"Hello, world!");
+    /// file.finish(DataDescriptorOutput::new(crc, 13))?;
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(crc: u32, uncompressed_size: u64) -> Self {
+        DataDescriptorOutput {
+            crc,
+            compressed_size: 0,
+            uncompressed_size,
+        }
+    }
+
     /// Returns the CRC32 checksum of the uncompressed data.
     pub fn crc(&self) -> u32 {
         self.crc
@@ -735,6 +2260,76 @@ impl DataDescriptorOutput {
     }
 }
 
+/// Central directory records of finished entries, written to a scratch file
+/// to bound memory use.
+#[derive(Debug)]
+struct CentralDirectorySpill {
+    path: PathBuf,
+    file: Option<io::BufWriter<File>>,
+    entries: usize,
+    zip64: bool,
+}
+
+impl CentralDirectorySpill {
+    fn new(path: PathBuf) -> Self {
+        CentralDirectorySpill {
+            path,
+            file: None,
+            entries: 0,
+            zip64: false,
+        }
+    }
+
+    fn push(
+        &mut self,
+        file_header: &FileHeader,
+        thresholds: &Zip64Thresholds,
+    ) -> Result<(), Error> {
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => {
+                let file = File::options()
+                    .read(true)
+                    .write(true)
+                    .create_new(true)
+                    .open(&self.path)?;
+                self.file.insert(io::BufWriter::new(file))
+            }
+        };
+
+        file_header.write_central_record(file, thresholds)?;
+        self.entries += 1;
+        self.zip64 |= file_header.needs_zip64(thresholds);
+        Ok(())
+    }
+
+    /// Streams the spilled records into `writer` and removes the scratch
+    /// file.
+    fn copy_to<W: Write>(mut self, writer: &mut W) -> Result<(), Error> {
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+
+        file.flush()?;
+        let file = file.get_mut();
+        file.seek(SeekFrom::Start(0))?;
+        io::copy(file, writer)?;
+        self.file = None;
+        std::fs::remove_file(&self.path)?;
+        Ok(())
+    }
+}
+
+impl Drop for CentralDirectorySpill {
+    /// Removes the scratch file of an archive that wasn't finished.
+    fn drop(&mut self) {
+        if let Some(file) = self.file.take() {
+            drop(file);
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
 #[derive(Debug)]
 struct FileHeader {
     name: ZipFilePath<NormalizedPathBuf>,
@@ -745,50 +2340,151 @@ struct FileHeader {
     crc: u32,
     flags: u16,
     modification_time: Option<UtcDateTime>,
+    extended_timestamp_flags: u8,
     unix_permissions: Option<u32>,
+    text: bool,
+    encoding: Encoding,
+    aes_extra_field: Option<AesExtraField>,
 }
 
 impl FileHeader {
-    fn needs_zip64(&self) -> bool {
-        self.compressed_size >= ZIP64_THRESHOLD_FILE_SIZE
-            || self.uncompressed_size >= ZIP64_THRESHOLD_FILE_SIZE
-            || self.local_header_offset >= ZIP64_THRESHOLD_OFFSET
+    fn needs_zip64(&self, thresholds: &Zip64Thresholds) -> bool {
+        self.compressed_size >= thresholds.file_size
+            || self.uncompressed_size >= thresholds.file_size
+            || self.local_header_offset >= thresholds.offset
+    }
+
+    /// Writes the central directory file header for this entry.
+    fn write_central_record<W>(
+        &self,
+        writer: &mut W,
+        thresholds: &Zip64Thresholds,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        // Central file header signature
+        writer.write_all(&CENTRAL_HEADER_SIGNATURE.to_le_bytes())?;
+
+        // Version made by and version needed to extract
+        let version_needed = if self.aes_extra_field.is_some() {
+            AES_VERSION_NEEDED
+        } else if self.needs_zip64(thresholds) {
+            ZIP64_VERSION_NEEDED
+        } else {
+            20
+        };
+
+        // Set version_made_by to indicate Unix when Unix permissions are present
+        let version_made_by_hi = self.unix_permissions.map(|_| CREATOR_UNIX).unwrap_or(0);
+        let version_made_by = (version_made_by_hi << 8) | version_needed;
+
+        writer.write_all(&version_made_by.to_le_bytes())?; // Version made by
+        writer.write_all(&version_needed.to_le_bytes())?; // Version needed to extract
+
+        // General purpose bit flag
+        writer.write_all(&self.flags.to_le_bytes())?;
+
+        // Compression method
+        writer.write_all(&self.compression_method.as_id().as_u16().to_le_bytes())?;
+
+        // Last mod file time and date
+        let (dos_time, dos_date) = self
+            .modification_time
+            .as_ref()
+            .map(|dt| DosDateTime::from(dt).into_parts())
+            .unwrap_or((0, 0));
+        writer.write_all(&dos_time.to_le_bytes())?;
+        writer.write_all(&dos_date.to_le_bytes())?;
+
+        // CRC-32
+        writer.write_all(&self.crc.to_le_bytes())?;
+
+        // Compressed size - use 0xFFFFFFFF if ZIP64
+        let compressed_size = thresholds.file_size_field(self.compressed_size);
+        writer.write_all(&compressed_size.to_le_bytes())?;
+
+        // Uncompressed size - use 0xFFFFFFFF if ZIP64
+        let uncompressed_size = thresholds.file_size_field(self.uncompressed_size);
+        writer.write_all(&uncompressed_size.to_le_bytes())?;
+
+        // File name length
+        let name = self.encoding.encode_name(self.name.as_ref())?;
+        writer.write_all(&(name.len() as u16).to_le_bytes())?;
+
+        // Extra field length
+        let central_timestamps = [self.modification_time];
+        let extra_field_length = self.zip64_extra_field_size(thresholds)
+            + extended_timestamp_extra_field_size(
+                self.extended_timestamp_flags,
+                &central_timestamps,
+            )
+            + self.aes_extra_field.map_or(0, |_| AesExtraField::SIZE);
+        writer.write_all(&extra_field_length.to_le_bytes())?;
+
+        // File comment length
+        writer.write_all(&0u16.to_le_bytes())?;
+
+        // Disk number start
+        writer.write_all(&0u16.to_le_bytes())?;
+
+        // Internal file attributes, bit 0 marks text data
+        let internal_attrs = u16::from(self.text);
+        writer.write_all(&internal_attrs.to_le_bytes())?;
+
+        // External file attributes
+        let external_attrs = self.unix_permissions.map(|x| x << 16).unwrap_or(0);
+        writer.write_all(&external_attrs.to_le_bytes())?;
+
+        // Local header offset - use 0xFFFFFFFF if ZIP64
+        let local_header_offset = thresholds.offset_field(self.local_header_offset);
+        writer.write_all(&local_header_offset.to_le_bytes())?;
+
+        // File name
+        writer.write_all(&name)?;
+
+        // ZIP64 extended information extra field
+        self.write_zip64_extra_field(writer, thresholds)?;
+
+        // The central directory keeps the local header's flags but only
+        // carries the modification time
+        write_extended_timestamp_field(writer, self.extended_timestamp_flags, &central_timestamps)?;
+
+        if let Some(aes_extra_field) = &self.aes_extra_field {
+            aes_extra_field.write(writer)?;
+        }
+
+        Ok(())
     }
 
     /// Writes the ZIP64 extended information extra field for this file header
-    fn write_zip64_extra_field<W>(&self, writer: &mut W) -> Result<(), Error>
+    fn write_zip64_extra_field<W>(
+        &self,
+        writer: &mut W,
+        thresholds: &Zip64Thresholds,
+    ) -> Result<(), Error>
     where
         W: Write,
     {
-        if !self.needs_zip64() {
+        if !self.needs_zip64(thresholds) {
             return Ok(());
         }
 
         // ZIP64 Extended Information Extra Field header
         writer.write_all(&ZIP64_EXTRA_FIELD_ID.to_le_bytes())?;
 
-        // Calculate size of data portion
-        let mut data_size = 0u16;
-        if self.uncompressed_size >= ZIP64_THRESHOLD_FILE_SIZE {
-            data_size += 8;
-        }
-        if self.compressed_size >= ZIP64_THRESHOLD_FILE_SIZE {
-            data_size += 8;
-        }
-        if self.local_header_offset >= ZIP64_THRESHOLD_OFFSET {
-            data_size += 8;
-        }
-
+        // Size of data portion
+        let data_size = self.zip64_extra_field_size(thresholds) - 4;
         writer.write_all(&data_size.to_le_bytes())?;
 
         // Write the actual data fields in the order specified by the spec
-        if self.uncompressed_size >= ZIP64_THRESHOLD_FILE_SIZE {
+        if self.uncompressed_size >= thresholds.file_size {
             writer.write_all(&self.uncompressed_size.to_le_bytes())?;
         }
-        if self.compressed_size >= ZIP64_THRESHOLD_FILE_SIZE {
+        if self.compressed_size >= thresholds.file_size {
             writer.write_all(&self.compressed_size.to_le_bytes())?;
         }
-        if self.local_header_offset >= ZIP64_THRESHOLD_OFFSET {
+        if self.local_header_offset >= thresholds.offset {
             writer.write_all(&self.local_header_offset.to_le_bytes())?;
         }
 
@@ -796,48 +2492,54 @@ impl FileHeader {
     }
 
     /// Calculates the size of the ZIP64 extra field for this file header
-    fn zip64_extra_field_size(&self) -> u16 {
-        if !self.needs_zip64() {
+    fn zip64_extra_field_size(&self, thresholds: &Zip64Thresholds) -> u16 {
+        if !self.needs_zip64(thresholds) {
             return 0;
         }
 
         let mut size = 4u16; // Header (ID + size)
-        if self.uncompressed_size >= ZIP64_THRESHOLD_FILE_SIZE {
+        if self.uncompressed_size >= thresholds.file_size {
             size += 8;
         }
-        if self.compressed_size >= ZIP64_THRESHOLD_FILE_SIZE {
+        if self.compressed_size >= thresholds.file_size {
             size += 8;
         }
-        if self.local_header_offset >= ZIP64_THRESHOLD_OFFSET {
+        if self.local_header_offset >= thresholds.offset {
             size += 8;
         }
         size
     }
 }
 
-fn extended_timestamp_extra_field_size(modification_time: Option<&UtcDateTime>) -> u16 {
-    if modification_time.is_some() {
-        9 // 2 bytes ID + 2 bytes size + 1 byte flags + 4 bytes timestamp
-    } else {
-        0
+fn extended_timestamp_extra_field_size(flags: u8, timestamps: &[Option<UtcDateTime>]) -> u16 {
+    if flags == 0 {
+        return 0;
     }
+
+    // 2 bytes ID + 2 bytes size + 1 byte flags + 4 bytes per timestamp
+    5 + 4 * timestamps.iter().flatten().count() as u16
 }
 
 fn write_extended_timestamp_field<W>(
     writer: &mut W,
-    datetime: Option<&UtcDateTime>,
+    flags: u8,
+    timestamps: &[Option<UtcDateTime>],
 ) -> Result<(), Error>
 where
     W: Write,
 {
-    let Some(datetime) = datetime else {
+    if flags == 0 {
         return Ok(());
-    };
-    let unix_time = datetime.to_unix().max(0) as u32; // ZIP format uses u32 for Unix timestamps, clamp negatives to 0
+    }
+
+    let size = extended_timestamp_extra_field_size(flags, timestamps) - 4;
     writer.write_all(&EXTENDED_TIMESTAMP_ID.to_le_bytes())?;
-    writer.write_all(&5u16.to_le_bytes())?; // Size: 1 byte flags + 4 bytes timestamp
-    writer.write_all(&1u8.to_le_bytes())?; // Flags: modification time present
-    writer.write_all(&unix_time.to_le_bytes())?; // Unix timestamp
+    writer.write_all(&size.to_le_bytes())?;
+    writer.write_all(&flags.to_le_bytes())?;
+    for datetime in timestamps.iter().flatten() {
+        let unix_time = datetime.to_unix().max(0) as u32; // ZIP format uses u32 for Unix timestamps, clamp negatives to 0
+        writer.write_all(&unix_time.to_le_bytes())?;
+    }
     Ok(())
 }
 
@@ -909,7 +2611,93 @@ where
 struct ZipEntryOptions {
     compression_method: CompressionMethod,
     modification_time: Option<UtcDateTime>,
+    access_time: Option<UtcDateTime>,
+    creation_time: Option<UtcDateTime>,
     unix_permissions: Option<u32>,
+    text: bool,
+    encoding: Encoding,
+    aes_extra_field: Option<AesExtraField>,
+    large_file: bool,
+}
+
+impl ZipEntryOptions {
+    /// The compression method recorded in the headers. AES encrypted entries
+    /// keep their actual compression method in the AES extra field.
+    fn header_compression_method(&self) -> CompressionMethod {
+        if self.aes_extra_field.is_some() {
+            CompressionMethod::Aes
+        } else {
+            self.compression_method
+        }
+    }
+
+    /// The Extended Timestamp flags for the timestamps that are set: bit 0
+    /// for modification, bit 1 for access, and bit 2 for creation time.
+    fn extended_timestamp_flags(&self) -> u8 {
+        u8::from(self.modification_time.is_some())
+            | u8::from(self.access_time.is_some()) << 1
+            | u8::from(self.creation_time.is_some()) << 2
+    }
+}
+
+/// Replaces the comment of an existing archive in place, returning the new
+/// length of the archive.
+///
+/// Only the end of central directory record is rewritten, so the entries are
+/// left untouched. The storage is truncated to the new length, so a shorter
+/// comment leaves no stale bytes behind.
+///
+/// ```rust
+/// let mut output = std::io::Cursor::new(Vec::new());
+/// rawzip::ZipArchiveWriter::new(&mut output).finish()?;
+///
+/// let len = rawzip::rewrite_comment(&mut output, b"built from abc123")?;
+/// assert_eq!(output.get_ref().len() as u64, len);
+///
+/// let archive = rawzip::ZipArchive::from_slice(output.get_ref())?;
+/// assert_eq!(archive.comment().as_bytes(), b"built from abc123");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn rewrite_comment<W>(mut writer: W, comment: &[u8]) -> Result<u64, Error>
+where
+    W: Read + Write + Seek + Truncate,
+{
+    let comment_len = u16::try_from(comment.len()).map_err(|_| {
+        Error::from(ErrorKind::InvalidInput {
+            msg: "comment too long".to_string(),
+        })
+    })?;
+
+    let end_offset = writer.seek(SeekFrom::End(0))?;
+    let eocd_offset = {
+        let reader = MutexReader::new(&mut writer);
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let location = find_end_of_central_dir_record_in_reader(
+            &reader,
+            &mut buffer,
+            END_OF_CENTRAL_DIR_MAX_OFFSET,
+            end_offset,
+        )?;
+        let Some((stream_pos, _, _)) = location else {
+            return Err(Error::from(ErrorKind::MissingEndOfCentralDirectory));
+        };
+
+        let mut record = [0u8; EndOfCentralDirectoryRecordFixed::SIZE];
+        reader.read_exact_at(&mut record, stream_pos)?;
+        EndOfCentralDirectoryRecordFixed::parse(&record).map_err(|e| e.at_offset(stream_pos))?;
+        stream_pos
+    };
+
+    let comment_len_offset = eocd_offset + EndOfCentralDirectoryRecordFixed::SIZE as u64 - 2;
+    writer.seek(SeekFrom::Start(comment_len_offset))?;
+    writer.write_all(&comment_len.to_le_bytes())?;
+    writer.write_all(comment)?;
+    let len = comment_len_offset + 2 + u64::from(comment_len);
+    if len < end_offset {
+        writer.truncate(len)?;
+    }
+    writer.flush()?;
+    Ok(len)
 }
 
 #[cfg(test)]
@@ -936,4 +2724,73 @@ mod tests {
 
         archive.finish().unwrap();
     }
+
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn test_write_deflate_level() {
+        use std::io::Read;
+
+        let data = b"Hello, world! Hello, world! Hello, world! Hello, world!".repeat(20);
+        let mut output = Cursor::new(Vec::new());
+        let mut archive = ZipArchiveWriter::new(&mut output);
+        let compressed = archive.write_deflate("hello.txt", 9, &data).unwrap();
+        archive.finish().unwrap();
+        assert!(compressed < data.len() as u64);
+
+        let bytes = output.into_inner();
+        let archive = crate::ZipArchive::from_slice(&bytes).unwrap();
+        let entry = archive.entries().next_entry().unwrap().unwrap();
+        assert_eq!(entry.compression_method(), CompressionMethod::Deflate);
+        assert_eq!(entry.compressed_size_hint(), compressed);
+
+        let zip_entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let decoder = flate2::read::DeflateDecoder::new(zip_entry.data());
+        let mut reader = zip_entry.verifying_reader(decoder);
+        let mut actual = Vec::new();
+        reader.read_to_end(&mut actual).unwrap();
+        assert_eq!(actual, data);
+    }
+
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn test_deflate_level_best() {
+        use std::io::Read;
+
+        let data = b"Hello, world! Hello, world! Hello, world! Hello, world!".repeat(20);
+        let mut output = Cursor::new(Vec::new());
+        let mut archive = ZipArchiveWriter::new(&mut output);
+        let mut file = archive
+            .new_file("hello.txt")
+            .deflate(DeflateLevel::Best)
+            .unwrap();
+        for chunk in data.chunks(7) {
+            file.write_all(chunk).unwrap();
+        }
+        let compressed = file.finish().unwrap();
+        archive.finish().unwrap();
+        assert!(compressed < data.len() as u64);
+
+        let bytes = output.into_inner();
+        let archive = crate::ZipArchive::from_slice(&bytes).unwrap();
+        let entry = archive.entries().next_entry().unwrap().unwrap();
+        assert_eq!(entry.compression_method(), CompressionMethod::Deflate);
+        assert_eq!(entry.compressed_size_hint(), compressed);
+        assert_eq!(entry.uncompressed_size_hint(), data.len() as u64);
+
+        let zip_entry = archive.get_entry(entry.wayfinder()).unwrap();
+        assert_eq!(zip_entry.claim_verifier().crc(), crate::crc32(&data));
+        let decoder = flate2::read::DeflateDecoder::new(zip_entry.data());
+        let mut reader = zip_entry.verifying_reader(decoder);
+        let mut actual = Vec::new();
+        reader.read_to_end(&mut actual).unwrap();
+        assert_eq!(actual, data);
+
+        // Same output as compressing at level 9 by hand
+        let mut output = Cursor::new(Vec::new());
+        let mut archive = ZipArchiveWriter::new(&mut output);
+        assert_eq!(
+            archive.write_deflate("hello.txt", 9, &data).unwrap(),
+            compressed
+        );
+    }
 }
@@ -0,0 +1,157 @@
+use crate::crc::crc32_byte;
+use crate::errors::{Error, ErrorKind};
+use std::io::Read;
+
+/// Size of the encryption header that precedes the encrypted data
+pub(crate) const ENCRYPTION_HEADER_SIZE: usize = 12;
+
+/// The key state of the traditional PKWARE stream cipher
+#[derive(Debug, Clone)]
+struct ZipCryptoKeys {
+    key0: u32,
+    key1: u32,
+    key2: u32,
+}
+
+impl ZipCryptoKeys {
+    fn new(password: &[u8]) -> Self {
+        let mut keys = ZipCryptoKeys {
+            key0: 0x12345678,
+            key1: 0x23456789,
+            key2: 0x34567890,
+        };
+
+        for &byte in password {
+            keys.update(byte);
+        }
+
+        keys
+    }
+
+    #[inline]
+    fn update(&mut self, byte: u8) {
+        self.key0 = crc32_byte(self.key0, byte);
+        self.key1 = self
+            .key1
+            .wrapping_add(self.key0 & 0xff)
+            .wrapping_mul(134775813)
+            .wrapping_add(1);
+        self.key2 = crc32_byte(self.key2, (self.key1 >> 24) as u8);
+    }
+
+    #[inline]
+    fn stream_byte(&self) -> u8 {
+        let temp = (self.key2 | 2) & 0xffff;
+        (temp.wrapping_mul(temp ^ 1) >> 8) as u8
+    }
+
+    #[inline]
+    fn decrypt(&mut self, byte: u8) -> u8 {
+        let plain = byte ^ self.stream_byte();
+        self.update(plain);
+        plain
+    }
+
+    #[cfg(test)]
+    fn encrypt(&mut self, byte: u8) -> u8 {
+        let cipher = byte ^ self.stream_byte();
+        self.update(byte);
+        cipher
+    }
+}
+
+/// A reader that decrypts data protected with the traditional PKWARE
+/// encryption (also known as ZipCrypto).
+///
+/// The decrypted output is the entry's compressed data, so a decompressor
+/// should be layered on top for entries that aren't stored.
+///
+/// Created from [`ZipEntry::decrypting_reader`](crate::ZipEntry::decrypting_reader).
+///
+/// # Security
+///
+/// ZipCrypto is cryptographically broken. It is vulnerable to known-plaintext
+/// attacks that recover the keys from as little as 12 bytes of known
+/// content, and the single check byte means roughly 1 in 256 wrong passwords
+/// is accepted, only to produce garbage that fails the CRC check. Support is
+/// provided for reading legacy archives and not as a means of protecting
+/// data.
+#[derive(Debug, Clone)]
+pub struct ZipCryptoReader<R> {
+    reader: R,
+    keys: ZipCryptoKeys,
+}
+
+impl<R> ZipCryptoReader<R>
+where
+    R: Read,
+{
+    /// Consumes and decrypts the encryption header, verifying that its last
+    /// byte matches `check_byte`.
+    pub(crate) fn new(mut reader: R, password: &[u8], check_byte: u8) -> Result<Self, Error> {
+        let mut keys = ZipCryptoKeys::new(password);
+        let mut header = [0u8; ENCRYPTION_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+
+        let decrypted = header.map(|byte| keys.decrypt(byte));
+        if decrypted[ENCRYPTION_HEADER_SIZE - 1] != check_byte {
+            return Err(Error::from(ErrorKind::InvalidPassword));
+        }
+
+        Ok(ZipCryptoReader { reader, keys })
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> Read for ZipCryptoReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        for byte in &mut buf[..read] {
+            *byte = self.keys.decrypt(*byte);
+        }
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encrypt(password: &[u8], check_byte: u8, data: &[u8]) -> Vec<u8> {
+        let mut keys = ZipCryptoKeys::new(password);
+        let mut header = [0x5au8; ENCRYPTION_HEADER_SIZE];
+        header[ENCRYPTION_HEADER_SIZE - 1] = check_byte;
+        header
+            .iter()
+            .chain(data)
+            .map(|&byte| keys.encrypt(byte))
+            .collect()
+    }
+
+    #[test]
+    fn test_decrypt_roundtrip() {
+        let data = b"Hello, world!";
+        let crc = crate::crc32(data);
+        let check_byte = (crc >> 24) as u8;
+        let encrypted = encrypt(b"secret", check_byte, data);
+
+        let mut reader = ZipCryptoReader::new(&encrypted[..], b"secret", check_byte).unwrap();
+        let mut actual = Vec::new();
+        reader.read_to_end(&mut actual).unwrap();
+        assert_eq!(actual, data);
+    }
+
+    #[test]
+    fn test_wrong_password() {
+        let encrypted = encrypt(b"secret", 0xab, b"data");
+        let err = ZipCryptoReader::new(&encrypted[..], b"wrong", 0xab).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPassword));
+    }
+}
@@ -0,0 +1,299 @@
+use rawzip::{AesStrength, CompressionMethod, EncryptionMethod, ZipArchive};
+
+#[test]
+fn test_aes_extra_field() {
+    let data = std::fs::read("assets/aes.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+
+    let mut actual = Vec::new();
+    for entry in archive.entries() {
+        let entry = entry.unwrap();
+        assert!(entry.is_encrypted());
+        assert_eq!(entry.compression_method(), CompressionMethod::Aes);
+        let aes = entry.aes_extra_field().unwrap();
+        actual.push((
+            aes.vendor_version(),
+            aes.strength(),
+            aes.compression_method(),
+        ));
+    }
+
+    assert_eq!(
+        actual,
+        vec![
+            (2, AesStrength::Aes128, CompressionMethod::Deflate),
+            (2, AesStrength::Aes192, CompressionMethod::Store),
+            (2, AesStrength::Aes256, CompressionMethod::Deflate),
+            (1, AesStrength::Aes256, CompressionMethod::Deflate),
+        ]
+    );
+}
+
+#[test]
+fn test_aes_encryption_method() {
+    let data = std::fs::read("assets/aes.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let actual = archive
+        .entries()
+        .map(|entry| entry.unwrap().encryption())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        actual,
+        vec![
+            Some(EncryptionMethod::Aes(AesStrength::Aes128)),
+            Some(EncryptionMethod::Aes(AesStrength::Aes192)),
+            Some(EncryptionMethod::Aes(AesStrength::Aes256)),
+            Some(EncryptionMethod::Aes(AesStrength::Aes256)),
+        ]
+    );
+}
+
+#[test]
+fn test_aes_extra_field_missing() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next().unwrap().unwrap();
+    assert!(entry.aes_extra_field().is_none());
+}
+
+#[cfg(feature = "aes")]
+mod decryption {
+    use rawzip::{
+        AesStrength, CompressionMethod, ErrorKind, ZipArchive, ZipArchiveWriter, ZipDataWriter,
+        ZipLocator, RECOMMENDED_BUFFER_SIZE,
+    };
+    use rstest::rstest;
+    use std::io::{Cursor, Read, Write};
+
+    fn read_entries(data: Vec<u8>, password: &[u8]) -> Vec<(String, std::io::Result<String>)> {
+        let len = data.len() as u64;
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let archive = ZipLocator::new()
+            .locate_in_reader(data, &mut buffer, len)
+            .map_err(|(_, e)| e)
+            .unwrap();
+        let mut entries = archive.entries(&mut buffer);
+
+        let mut actual = Vec::new();
+        while let Some(entry) = entries.next_entry().unwrap() {
+            let name = entry
+                .file_path()
+                .try_normalize()
+                .unwrap()
+                .as_ref()
+                .to_string();
+            let aes = entry.aes_extra_field().unwrap();
+            let entry = archive.get_entry(entry.wayfinder()).unwrap();
+            let decrypted = entry.aes_decrypting_reader(password).unwrap();
+
+            let mut contents = String::new();
+            let result = match aes.compression_method() {
+                CompressionMethod::Store => {
+                    let mut reader = decrypted;
+                    reader.read_to_string(&mut contents)
+                }
+                CompressionMethod::Deflate => {
+                    flate2::read::DeflateDecoder::new(decrypted).read_to_string(&mut contents)
+                }
+                method => panic!("unexpected compression method {method:?}"),
+            };
+            actual.push((name, result.map(|_| contents)));
+        }
+        actual
+    }
+
+    #[test]
+    fn test_aes_decrypt() {
+        let data = std::fs::read("assets/aes.zip").unwrap();
+        let actual = read_entries(data, b"password")
+            .into_iter()
+            .map(|(name, contents)| (name, contents.unwrap()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    "aes128.txt".to_string(),
+                    "AES-128 encrypted and deflated. ".repeat(20)
+                ),
+                (
+                    "aes192.txt".to_string(),
+                    "AES-192 encrypted and stored.\n".to_string()
+                ),
+                (
+                    "aes256.txt".to_string(),
+                    "AES-256 encrypted and deflated. ".repeat(20)
+                ),
+                ("ae1.txt".to_string(), "AE-1 keeps the CRC. ".repeat(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aes_ae1_crc() {
+        let file = std::fs::File::open("assets/aes.zip").unwrap();
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let archive = ZipArchive::from_file(file, &mut buffer).unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        let mut last = None;
+        while let Some(entry) = entries.next_entry().unwrap() {
+            last = Some(entry.wayfinder());
+        }
+
+        let entry = archive.get_entry(last.unwrap()).unwrap();
+        let decrypted = entry.aes_decrypting_reader(b"password").unwrap();
+        let mut reader = entry.verifying_reader(flate2::read::DeflateDecoder::new(decrypted));
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "AE-1 keeps the CRC. ".repeat(20));
+    }
+
+    #[test]
+    fn test_aes_wrong_password() {
+        let file = std::fs::File::open("assets/aes.zip").unwrap();
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let archive = ZipArchive::from_file(file, &mut buffer).unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        let entry = entries.next_entry().unwrap().unwrap();
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let err = entry
+            .aes_decrypting_reader(b"wrong")
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPassword));
+    }
+
+    #[test]
+    fn test_aes_tampered_data() {
+        let mut data = std::fs::read("assets/aes.zip").unwrap();
+        let archive = ZipArchive::from_slice(&data).unwrap();
+        let entry = archive.entries().nth(1).unwrap().unwrap();
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let offset = entry.data().as_ptr() as usize - data.as_ptr() as usize;
+
+        // Flip a byte after the salt and password verifier of the AES-192 entry
+        data[offset + 12 + 2] ^= 0x01;
+
+        let actual = read_entries(data, b"password");
+        assert!(actual[0].1.is_ok());
+        let err = actual[1].1.as_ref().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(actual[2].1.is_ok());
+    }
+
+    #[test]
+    fn test_aes_unencrypted_entry() {
+        let file = std::fs::File::open("assets/test.zip").unwrap();
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let archive = ZipArchive::from_file(file, &mut buffer).unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        let entry = entries.next_entry().unwrap().unwrap();
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let err = entry
+            .aes_decrypting_reader(b"password")
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+    }
+
+    #[rstest]
+    #[case(AesStrength::Aes128, false)]
+    #[case(AesStrength::Aes192, false)]
+    #[case(AesStrength::Aes256, false)]
+    #[case(AesStrength::Aes128, true)]
+    #[case(AesStrength::Aes256, true)]
+    fn test_aes_roundtrip(#[case] strength: AesStrength, #[case] seekable: bool) {
+        let stored = b"Hello, world!".to_vec();
+        let deflated = b"Encrypted and deflated. ".repeat(500);
+
+        let mut output = Cursor::new(Vec::new());
+        let mut archive = if seekable {
+            ZipArchiveWriter::new_seekable(&mut output)
+        } else {
+            ZipArchiveWriter::new(&mut output)
+        };
+
+        let mut file = archive
+            .new_file("stored.txt")
+            .encrypt_aes(b"password", strength)
+            .create()
+            .unwrap();
+        let mut writer = ZipDataWriter::new(&mut file);
+        writer.write_all(&stored).unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        file.finish(descriptor).unwrap();
+
+        let mut file = archive
+            .new_file("deflated.txt")
+            .compression_method(CompressionMethod::Deflate)
+            .encrypt_aes(b"password", strength)
+            .create()
+            .unwrap();
+        let encoder =
+            flate2::write::DeflateEncoder::new(file.by_ref(), flate2::Compression::default());
+        let mut writer = ZipDataWriter::new(encoder);
+        writer.write_all(&deflated).unwrap();
+        let (encoder, descriptor) = writer.finish().unwrap();
+        encoder.finish().unwrap();
+        file.finish(descriptor).unwrap();
+
+        let mut file = archive.new_file("plain.txt").create().unwrap();
+        let mut writer = ZipDataWriter::new(&mut file);
+        writer.write_all(b"not encrypted").unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        file.finish(descriptor).unwrap();
+        archive.finish().unwrap();
+
+        let data = output.into_inner();
+        let archive = ZipArchive::from_slice(&data).unwrap();
+        let mut entries = archive.entries();
+        for method in [CompressionMethod::Store, CompressionMethod::Deflate] {
+            let entry = entries.next_entry().unwrap().unwrap();
+            assert!(entry.is_encrypted());
+            assert_eq!(entry.compression_method(), CompressionMethod::Aes);
+            let aes = entry.aes_extra_field().unwrap();
+            assert_eq!(aes.vendor_version(), 2);
+            assert_eq!(aes.strength(), strength);
+            assert_eq!(aes.compression_method(), method);
+        }
+        let entry = entries.next_entry().unwrap().unwrap();
+        assert!(!entry.is_encrypted());
+        assert!(entry.aes_extra_field().is_none());
+
+        let mut encrypted = Vec::new();
+        let len = data.len() as u64;
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let archive = ZipLocator::new()
+            .locate_in_reader(data, &mut buffer, len)
+            .map_err(|(_, e)| e)
+            .unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        while let Some(entry) = entries.next_entry().unwrap() {
+            if entry.is_encrypted() {
+                encrypted.push(archive.get_entry(entry.wayfinder()).unwrap());
+            }
+        }
+
+        let mut actual = Vec::new();
+        encrypted[0]
+            .aes_decrypting_reader(b"password")
+            .unwrap()
+            .read_to_end(&mut actual)
+            .unwrap();
+        assert_eq!(actual, stored);
+
+        let decrypted = encrypted[1].aes_decrypting_reader(b"password").unwrap();
+        let mut actual = Vec::new();
+        flate2::read::DeflateDecoder::new(decrypted)
+            .read_to_end(&mut actual)
+            .unwrap();
+        assert_eq!(actual, deflated);
+
+        let err = encrypted[0]
+            .aes_decrypting_reader(b"wrong")
+            .map(|_| ())
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPassword));
+    }
+}
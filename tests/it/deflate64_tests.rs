@@ -0,0 +1,36 @@
+use rawzip::{CompressionMethod, Deflate64Decoder, ZipArchive};
+use std::io::Read;
+
+// A stored block of 40000 random bytes followed by matches that only
+// Deflate64 can express: 300 bytes at distance 1 and 10 bytes at distance
+// 40300, outside of deflate's 32 KiB window.
+#[test]
+fn test_deflate64_decompress() {
+    let data = std::fs::read("assets/deflate64.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(entry.compression_method(), CompressionMethod::Deflate64);
+
+    let wayfinder = entry.wayfinder();
+    let entry = archive.get_entry(wayfinder).unwrap();
+    let decoder = Deflate64Decoder::new(entry.data());
+    let mut reader = entry.verifying_reader(decoder);
+    let mut actual = Vec::new();
+    reader.read_to_end(&mut actual).unwrap();
+
+    assert_eq!(actual.len(), 40310);
+    assert!(actual[40000..40300].iter().all(|&x| x == actual[39999]));
+    assert_eq!(actual[40300..], actual[..10]);
+}
+
+#[test]
+fn test_deflate64_is_not_deflate() {
+    let data = std::fs::read("assets/deflate64.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+
+    let decoder = flate2::read::DeflateDecoder::new(entry.data());
+    let mut reader = entry.verifying_reader(decoder);
+    assert!(reader.read_to_end(&mut Vec::new()).is_err());
+}
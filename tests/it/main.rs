@@ -1,15 +1,21 @@
 use quickcheck_macros::quickcheck;
 use rawzip::time::{LocalDateTime, UtcDateTime, ZipDateTimeKind};
-use rawzip::{Error, ErrorKind};
+use rawzip::{Error, ErrorKind, ReaderAt};
 use std::fs::File;
-use std::io::{Cursor, Write};
+use std::io::{Cursor, Read, Write};
 use std::path::Path;
 
+mod aes_tests;
 mod concatenated_zip_tests;
+#[cfg(feature = "deflate64")]
+mod deflate64_tests;
 mod modification_time_tests;
 mod permission_tests;
+#[cfg(feature = "tokio")]
+mod tokio_tests;
 mod utf8_tests;
 mod zip64_tests;
+mod zipcrypto_tests;
 
 macro_rules! zip_test_case {
     ($name:expr, $case:expr) => {
@@ -776,6 +782,170 @@ fn zip_integration_tests_vec() {
     assert_eq!(count, 1);
 }
 
+#[test]
+fn test_slice_reader_archive() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let reader_archive = archive.reader_archive();
+    assert_eq!(reader_archive.comment(), archive.comment());
+
+    let mut buf = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let mut entries = reader_archive.entries(&mut buf);
+    let mut slice_entries = archive.entries();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let slice_entry = slice_entries.next_entry().unwrap().unwrap();
+        assert_eq!(entry.file_path(), slice_entry.file_path());
+        if entry.is_dir() {
+            continue;
+        }
+
+        let expected = archive
+            .get_entry(slice_entry.wayfinder())
+            .unwrap()
+            .data()
+            .to_vec();
+        let zip_entry = reader_archive.get_entry(entry.wayfinder()).unwrap();
+        let mut actual = Vec::new();
+        zip_entry.reader().read_to_end(&mut actual).unwrap();
+        assert_eq!(actual, expected);
+    }
+    assert!(slice_entries.next_entry().unwrap().is_none());
+
+    // The slice archive remains usable after the reader archive is dropped
+    drop(reader_archive);
+    assert!(archive.entries_hint() > 0);
+}
+
+#[test]
+fn test_reader_for_scoped_threads() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let slice_archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let mut expected = Vec::new();
+    let mut entries = slice_archive.entries();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let entry = slice_archive.get_entry(entry.wayfinder()).unwrap();
+        expected.push(entry.data().to_vec());
+    }
+
+    let file = File::open("assets/test.zip").unwrap();
+    let mut buf = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_file(file, &mut buf).unwrap();
+    let mut wayfinders = Vec::new();
+    let mut entries = archive.entries(&mut buf);
+    while let Some(entry) = entries.next_entry().unwrap() {
+        wayfinders.push(entry.wayfinder());
+    }
+
+    // Each entry is read by several threads at once
+    let archive = &archive;
+    let actual = std::thread::scope(|scope| {
+        let handles = wayfinders
+            .iter()
+            .cycle()
+            .take(wayfinders.len() * 4)
+            .map(|&wayfinder| {
+                scope.spawn(move || {
+                    let mut data = Vec::new();
+                    let mut reader = archive.reader_for(wayfinder).unwrap();
+                    reader.read_to_end(&mut data).unwrap();
+                    data
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(actual.len(), expected.len() * 4);
+    for (i, data) in actual.iter().enumerate() {
+        assert_eq!(data, &expected[i % expected.len()]);
+    }
+}
+
+#[test]
+fn test_add_empty_file() {
+    for seekable in [false, true] {
+        let mut output = Cursor::new(Vec::new());
+        let mut archive = if seekable {
+            rawzip::ZipArchiveWriter::new_seekable(&mut output)
+        } else {
+            rawzip::ZipArchiveWriter::new(&mut output)
+        };
+        archive.add_empty_file("empty.txt").unwrap();
+        archive
+            .new_file("marker")
+            .unix_permissions(0o600)
+            .compression_method(rawzip::CompressionMethod::Deflate)
+            .create_empty()
+            .unwrap();
+        archive.finish().unwrap();
+
+        let mut buf = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+        let archive = rawzip::ZipArchive::from_reader_at(output.into_inner(), &mut buf).unwrap();
+        let mut records = Vec::new();
+        let mut entries = archive.entries(&mut buf);
+        while let Some(entry) = entries.next_entry().unwrap() {
+            assert_eq!(entry.compression_method(), rawzip::CompressionMethod::Store);
+            records.push((
+                entry.file_path().as_ref().to_vec(),
+                entry.mode().permissions(),
+                entry.wayfinder(),
+            ));
+        }
+
+        let names: Vec<_> = records.iter().map(|(name, ..)| name.as_slice()).collect();
+        assert_eq!(names, [&b"empty.txt"[..], &b"marker"[..]]);
+        assert_eq!(records[1].1, 0o600);
+
+        for (_, _, wayfinder) in records {
+            assert_eq!(wayfinder.uncompressed_size_hint(), 0);
+            assert_eq!(wayfinder.compressed_size_hint(), 0);
+
+            let entry = archive.get_entry(wayfinder).unwrap();
+            assert_eq!(entry.stored_crc().unwrap(), 0);
+            assert_eq!(entry.data_descriptor().unwrap().is_some(), !seekable);
+
+            let mut data = Vec::new();
+            entry
+                .verifying_reader(entry.reader())
+                .read_to_end(&mut data)
+                .unwrap();
+            assert!(data.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_entry_bytes_owned_slice_archive() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let expected = {
+        let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+        let mut entries = archive.entries();
+        let mut expected = Vec::new();
+        while let Some(entry) = entries.next_entry().unwrap() {
+            let entry = archive.get_entry(entry.wayfinder()).unwrap();
+            expected.push(entry.data().to_vec());
+        }
+        expected
+    };
+
+    let archive = rawzip::ZipArchive::from_slice(data).unwrap();
+    let mut entries = archive.entries();
+    let mut borrowed = Vec::new();
+    let mut owned = Vec::new();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        borrowed.push(archive.entry_bytes(entry.wayfinder()).unwrap());
+        owned.push(archive.entry_bytes_owned(entry.wayfinder()).unwrap());
+    }
+    assert_eq!(borrowed, expected);
+
+    // Owned copies outlive the archive
+    drop(archive);
+    assert_eq!(owned, expected);
+}
+
 #[quickcheck]
 fn test_read_what_we_write_slice(data: Vec<u8>) {
     let mut output = Vec::new();
@@ -850,3 +1020,2520 @@ fn test_zip_with_prepended_data() {
     let ent1 = archive.get_entry(wayfinder1).unwrap();
     assert_eq!(ent1.data(), b"Hello, world!");
 }
+
+#[test]
+fn test_check_overlapping_entries() {
+    let file = File::open("assets/test.zip").unwrap();
+    let mut buf = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_file(file, &mut buf).unwrap();
+    let overlaps = archive.check_overlapping_entries(&mut buf).unwrap();
+    assert!(overlaps.is_none());
+
+    // Create an archive and then point the second central directory record at
+    // the first file's local header
+    let mut output = Vec::new();
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        let mut file = archive.new_file(name).create().unwrap();
+        let mut writer = rawzip::ZipDataWriter::new(&mut file);
+        writer.write_all(b"Hello, world!").unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        file.finish(descriptor).unwrap();
+    }
+    archive.finish().unwrap();
+
+    let signature = 0x02014b50u32.to_le_bytes();
+    let second_record = output
+        .windows(4)
+        .enumerate()
+        .filter(|(_, w)| *w == signature)
+        .nth(1)
+        .map(|(i, _)| i)
+        .unwrap();
+    output[second_record + 42..second_record + 46].copy_from_slice(&0u32.to_le_bytes());
+
+    let archive = rawzip::ZipArchive::from_slice(&output)
+        .unwrap()
+        .into_reader();
+    let mut wayfinders = Vec::new();
+    let mut entries = archive.entries(&mut buf);
+    while let Some(entry) = entries.next_entry().unwrap() {
+        wayfinders.push(entry.wayfinder());
+    }
+    let overlaps = archive.check_overlapping_entries(&mut buf).unwrap();
+    assert_eq!(overlaps, Some((wayfinders[0], wayfinders[1])));
+    assert_eq!(overlaps.unwrap().1.local_header_offset(), 0);
+}
+
+#[test]
+fn test_max_entries() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+
+    let archive = rawzip::ZipLocator::new()
+        .max_entries(1)
+        .locate_in_slice(&data)
+        .unwrap();
+    let mut entries = archive.entries();
+    assert!(entries.next_entry().unwrap().is_some());
+    let err = entries.next_entry().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+
+    let archive = archive.into_reader();
+    let mut buf = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let mut entries = archive.entries(&mut buf);
+    assert!(entries.next_entry().unwrap().is_some());
+    let err = entries.next_entry().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+
+    // A cap equal to the number of entries is not an error
+    let archive = rawzip::ZipLocator::new()
+        .max_entries(2)
+        .locate_in_slice(&data)
+        .unwrap();
+    assert_eq!(archive.entries().count(), 2);
+    assert!(archive.entries().all(|x| x.is_ok()));
+}
+
+#[test]
+fn test_seekable_writer_patches_local_header() {
+    let mut output = std::io::Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new_seekable(&mut output);
+    for (name, data) in [("a.txt", &b"Hello"[..]), ("b.txt", &b"world!"[..])] {
+        let mut file = archive.new_file(name).create().unwrap();
+        let mut writer = rawzip::ZipDataWriter::new(&mut file);
+        writer.write_all(data).unwrap();
+        let (_, output) = writer.finish().unwrap();
+        file.finish(output).unwrap();
+    }
+    archive.finish().unwrap();
+    let data = output.into_inner();
+
+    let descriptor_sig = 0x08074b50u32.to_le_bytes();
+    assert!(!data.windows(4).any(|w| w == descriptor_sig));
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    for entry in archive.entries() {
+        let entry = entry.unwrap();
+        assert!(!entry.has_data_descriptor());
+        let wayfinder = entry.wayfinder();
+        let local_offset = entry.local_header_offset() as usize;
+        let header = &data[local_offset..];
+        let flags = u16::from_le_bytes([header[6], header[7]]);
+        let crc = u32::from_le_bytes(header[14..18].try_into().unwrap());
+        let compressed = u32::from_le_bytes(header[18..22].try_into().unwrap());
+        let uncompressed = u32::from_le_bytes(header[22..26].try_into().unwrap());
+        assert_eq!(flags & 0x08, 0);
+        assert_eq!(u64::from(compressed), entry.compressed_size_hint());
+        assert_eq!(u64::from(uncompressed), entry.uncompressed_size_hint());
+
+        let entry = archive.get_entry(wayfinder).unwrap();
+        assert_eq!(crc, rawzip::crc32(entry.data()));
+        let mut contents = Vec::new();
+        entry
+            .verifying_reader(entry.data())
+            .read_to_end(&mut contents)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_fn_reader_at() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let reader =
+        rawzip::FnReaderAt::new(|buf: &mut [u8], offset: u64| (&data[..]).read_at(buf, offset));
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipLocator::new()
+        .locate_in_reader(reader, &mut buffer, data.len() as u64)
+        .map_err(|(_, e)| e)
+        .unwrap();
+    let expected = rawzip::ZipArchive::from_slice(&data).unwrap();
+
+    let mut names = Vec::new();
+    let mut entries = archive.entries(&mut buffer);
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let name = entry.file_path().as_ref().to_vec();
+        let wayfinder = entry.wayfinder();
+        let mut contents = Vec::new();
+        let zip_entry = archive.get_entry(wayfinder).unwrap();
+        zip_entry.reader().read_to_end(&mut contents).unwrap();
+        let expected_entry = expected.get_entry(wayfinder).unwrap();
+        assert_eq!(contents, expected_entry.data());
+        names.push(name);
+    }
+
+    let expected_names: Vec<_> = expected
+        .entries()
+        .map(|e| e.unwrap().file_path().as_ref().to_vec())
+        .collect();
+    assert_eq!(names, expected_names);
+}
+
+#[test]
+fn test_error_offset_for_corrupt_central_header() {
+    let mut data = std::fs::read("assets/test.zip").unwrap();
+    let second_offset = data
+        .windows(4)
+        .enumerate()
+        .filter(|(_, w)| *w == [0x50, 0x4b, 0x01, 0x02])
+        .nth(1)
+        .map(|(i, _)| i as u64)
+        .unwrap();
+
+    // Corrupt the signature of the second central directory header
+    data[second_offset as usize] = 0;
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let mut entries = archive.entries();
+    entries.next_entry().unwrap();
+    let err = entries.next_entry().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidSignature { .. }));
+    assert_eq!(err.offset(), Some(second_offset));
+    assert!(err
+        .to_string()
+        .ends_with(&format!("at offset {second_offset}")));
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    entries.next_entry().unwrap();
+    let err = entries.next_entry().unwrap_err();
+    assert_eq!(err.offset(), Some(second_offset));
+}
+
+#[test]
+fn test_finish_with_stats() {
+    let mut output = std::io::Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    for (name, data) in [("a.txt", &b"Hello"[..]), ("b.txt", &b"world!"[..])] {
+        let mut file = archive.new_file(name).create().unwrap();
+        let mut writer = rawzip::ZipDataWriter::new(&mut file);
+        writer.write_all(data).unwrap();
+        let (_, output) = writer.finish().unwrap();
+        file.finish(output).unwrap();
+    }
+    let (_, stats) = archive.finish_with_stats().unwrap();
+    let data = output.into_inner();
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let (cd_start, cd_end) = archive.central_directory_range();
+    assert_eq!(stats.bytes_written(), data.len() as u64);
+    assert_eq!(stats.central_directory_offset(), cd_start);
+    assert_eq!(stats.central_directory_size(), cd_end - cd_start);
+    assert_eq!(stats.entries(), 2);
+    assert!(!stats.zip64());
+
+    let mut output = std::io::Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::at_offset(100)
+        .zip64_entries_threshold(1)
+        .build(&mut output);
+    archive.new_dir("dir/").create().unwrap();
+    let (_, stats) = archive.finish_with_stats().unwrap();
+    assert_eq!(stats.bytes_written(), output.get_ref().len() as u64);
+    assert_eq!(stats.central_directory_offset(), 100 + 30 + 4);
+    assert_eq!(stats.entries(), 1);
+    assert!(stats.zip64());
+}
+
+#[test]
+fn test_stored_crc_from_data_descriptor() {
+    let mut data = std::fs::read("assets/go-with-datadesc-sig.zip").unwrap();
+    let expected = [rawzip::crc32(b"foo\n"), rawzip::crc32(b"bar\n")];
+
+    // Zero the central directory CRCs so that they must come from the
+    // trailing data descriptors
+    let central_headers: Vec<_> = data
+        .windows(4)
+        .enumerate()
+        .filter(|(_, w)| *w == [0x50, 0x4b, 0x01, 0x02])
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(central_headers.len(), 2);
+
+    for zero_central_crc in [false, true] {
+        if zero_central_crc {
+            for &offset in &central_headers {
+                data[offset + 16..offset + 20].fill(0);
+            }
+        }
+
+        let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+        let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        let mut actual = Vec::new();
+        while let Some(entry) = entries.next_entry().unwrap() {
+            assert!(entry.has_data_descriptor());
+            let entry = archive.get_entry(entry.wayfinder()).unwrap();
+            actual.push(entry.stored_crc().unwrap());
+        }
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_seek_in() {
+    use std::io::{Seek, SeekFrom};
+
+    struct Stored;
+
+    impl<'a, R: ReaderAt> rawzip::SeekableDecompressor<rawzip::ZipReader<'a, R>> for Stored {
+        type Reader = rawzip::ZipReader<'a, R>;
+
+        fn decompress(
+            self,
+            method: rawzip::CompressionMethod,
+            compressed: rawzip::ZipReader<'a, R>,
+        ) -> Result<Self::Reader, Error> {
+            match method {
+                rawzip::CompressionMethod::Store => Ok(compressed),
+                _ => Err(Error::from(ErrorKind::InvalidInput {
+                    msg: format!("can't seek in {:?} data", method),
+                })),
+            }
+        }
+    }
+
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    archive
+        .store_reader("stored.txt", &b"0123456789"[..])
+        .unwrap();
+    let mut file = archive
+        .new_file("deflated.txt")
+        .compression_method(rawzip::CompressionMethod::Deflate)
+        .create()
+        .unwrap();
+    let encoder = flate2::write::DeflateEncoder::new(file.by_ref(), flate2::Compression::default());
+    let mut writer = rawzip::ZipDataWriter::new(encoder);
+    writer.write_all(b"0123456789").unwrap();
+    let (encoder, descriptor) = writer.finish().unwrap();
+    encoder.finish().unwrap();
+    file.finish(descriptor).unwrap();
+    archive.finish().unwrap();
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(output, &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let stored = entries.next_entry().unwrap().unwrap().wayfinder();
+    let deflated = entries.next_entry().unwrap().unwrap().wayfinder();
+
+    let entry = archive.get_entry(stored).unwrap();
+    let mut reader = entry.seek_in(Stored).unwrap();
+    let mut actual = [0u8; 3];
+    assert_eq!(reader.seek(SeekFrom::Start(2)).unwrap(), 2);
+    reader.read_exact(&mut actual).unwrap();
+    assert_eq!(&actual, b"234");
+    assert_eq!(reader.seek(SeekFrom::End(-3)).unwrap(), 7);
+    reader.read_exact(&mut actual).unwrap();
+    assert_eq!(&actual, b"789");
+    assert_eq!(reader.seek(SeekFrom::Current(-6)).unwrap(), 4);
+    reader.read_exact(&mut actual).unwrap();
+    assert_eq!(&actual, b"456");
+    assert!(reader.seek(SeekFrom::Current(-8)).is_err());
+
+    // Reads past the end of the data are EOF
+    assert_eq!(reader.seek(SeekFrom::Start(20)).unwrap(), 20);
+    assert_eq!(reader.read(&mut actual).unwrap(), 0);
+
+    let entry = archive.get_entry(deflated).unwrap();
+    let err = entry.seek_in(Stored).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+}
+
+#[test]
+fn test_get_entry_offset_past_central_directory() {
+    let mut data = std::fs::read("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let (cd_offset, _) = archive.central_directory_range();
+
+    // Point the first entry's local header at the central directory itself
+    let field = cd_offset as usize + 42;
+    data[field..field + 4].copy_from_slice(&(cd_offset as u32).to_le_bytes());
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let wayfinder = archive.entries().next_entry().unwrap().unwrap().wayfinder();
+    let err = archive.get_entry(wayfinder).unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::InconsistentArchive { .. }),
+        "{err}"
+    );
+    let err = archive.get_entry_checked(wayfinder).unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::InconsistentArchive { .. }),
+        "{err}"
+    );
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    let wayfinder = archive
+        .entries(&mut buffer)
+        .next_entry()
+        .unwrap()
+        .unwrap()
+        .wayfinder();
+    let err = archive.get_entry(wayfinder).map(|_| ()).unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::InconsistentArchive { .. }),
+        "{err}"
+    );
+    assert!(err.is_corruption());
+    assert!(err.to_string().contains("central directory"), "{err}");
+}
+
+#[test]
+fn test_zip_reader_bytes_remaining() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    archive
+        .store_reader("digits.txt", &b"0123456789"[..])
+        .unwrap();
+    archive.store_reader("empty.txt", &b""[..]).unwrap();
+    archive.finish().unwrap();
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(output, &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let digits = entries.next_entry().unwrap().unwrap().wayfinder();
+    let empty = entries.next_entry().unwrap().unwrap().wayfinder();
+
+    let entry = archive.get_entry(digits).unwrap();
+    let mut reader = entry.reader();
+    assert_eq!(reader.len(), 10);
+    assert!(!reader.is_empty());
+    assert_eq!(reader.bytes_remaining(), 10);
+
+    let mut half = [0u8; 5];
+    reader.read_exact(&mut half).unwrap();
+    assert_eq!(&half, b"01234");
+    assert_eq!(reader.len(), 10);
+    assert_eq!(reader.bytes_remaining(), 5);
+
+    std::io::copy(&mut reader, &mut std::io::sink()).unwrap();
+    assert_eq!(reader.bytes_remaining(), 0);
+
+    let entry = archive.get_entry(empty).unwrap();
+    let reader = entry.reader();
+    assert!(reader.is_empty());
+    assert_eq!(reader.bytes_remaining(), 0);
+}
+
+#[test]
+fn test_read_data_descriptor() {
+    let data = std::fs::read("assets/go-with-datadesc-sig.zip").unwrap();
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let mut actual = Vec::new();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let descriptor = entry.data_descriptor().unwrap().unwrap();
+        assert!(descriptor.had_signature());
+        assert!(!descriptor.is_zip64());
+        actual.push((
+            descriptor.crc(),
+            descriptor.compressed_size(),
+            descriptor.uncompressed_size(),
+        ));
+    }
+    assert_eq!(
+        actual,
+        [
+            (rawzip::crc32(b"foo\n"), 4, 4),
+            (rawzip::crc32(b"bar\n"), 4, 4)
+        ]
+    );
+
+    // Entries without a data descriptor have nothing to report
+    let archive = rawzip::ZipArchive::from_seekable(
+        Cursor::new(std::fs::read("assets/test.zip").unwrap()),
+        &mut buffer,
+    )
+    .unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert!(!entry.has_data_descriptor());
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    assert_eq!(entry.data_descriptor().unwrap(), None);
+}
+
+#[test]
+fn test_comment_with_end_of_central_directory_signature() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let mut file = archive.new_file("file.txt").create().unwrap();
+    let mut writer = rawzip::ZipDataWriter::new(&mut file);
+    writer.write_all(b"Hello, world!").unwrap();
+    let (_, descriptor) = writer.finish().unwrap();
+    file.finish(descriptor).unwrap();
+    archive.finish().unwrap();
+    let mut data = output.into_inner();
+
+    // A comment that embeds a bogus end of central directory record
+    let mut comment = b"see PK".to_vec();
+    comment.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06]);
+    comment.extend_from_slice(&[0u8; 18]);
+    comment.extend_from_slice(b" for details");
+    let comment_len_pos = data.len() - 2;
+    data[comment_len_pos..].copy_from_slice(&(comment.len() as u16).to_le_bytes());
+    data.extend_from_slice(&comment);
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.comment().as_bytes(), &comment[..]);
+    assert_eq!(archive.entries_hint(), 1);
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(entry.file_path().as_ref(), b"file.txt");
+
+    for buffer_size in [rawzip::RECOMMENDED_BUFFER_SIZE, 64] {
+        let mut buffer = vec![0u8; buffer_size];
+        let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+        assert_eq!(archive.comment().as_bytes(), &comment[..]);
+        let mut entries = archive.entries(&mut buffer);
+        let entry = entries.next_entry().unwrap().unwrap();
+        assert_eq!(entry.file_path().as_ref(), b"file.txt");
+        assert!(entries.next_entry().unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_write_raw_stub_before_entries() {
+    let stub = b"#!/bin/sh\necho self-extracting stub\nexit 0\n";
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let offset = archive.write_raw(stub).unwrap();
+    assert_eq!(offset, stub.len() as u64);
+
+    let mut file = archive.new_file("file.txt").create().unwrap();
+    let mut writer = rawzip::ZipDataWriter::new(&mut file);
+    writer.write_all(b"Hello, world!").unwrap();
+    let (_, descriptor) = writer.finish().unwrap();
+    file.finish(descriptor).unwrap();
+    archive.finish().unwrap();
+    let data = output.into_inner();
+    assert!(data.starts_with(stub));
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.base_offset(), 0);
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(entry.local_header_offset(), stub.len() as u64);
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    assert_eq!(entry.data(), b"Hello, world!");
+}
+
+#[test]
+fn test_slice_get_entry_by_name() {
+    let data = std::fs::read("assets/zip64-2.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+
+    let entry = archive.get_entry_by_name("README").unwrap().unwrap();
+    let mut reader = entry.verifying_reader(flate2::read::DeflateDecoder::new(entry.data()));
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, "This small file is in ZIP64 format.\n");
+
+    // Names are normalized before comparison
+    assert!(archive.get_entry_by_name("./README").unwrap().is_some());
+    assert!(archive.get_entry_by_name("readme").unwrap().is_none());
+}
+
+#[test]
+fn test_verify_all_names_corrupt_entry() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    for (name, data) in [("a.txt", &b"Hello"[..]), ("b.txt", &b"world!"[..])] {
+        let mut file = archive.new_file(name).create().unwrap();
+        let mut writer = rawzip::ZipDataWriter::new(&mut file);
+        writer.write_all(data).unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        file.finish(descriptor).unwrap();
+    }
+    archive.finish().unwrap();
+    let mut data = output.into_inner();
+
+    fn decompressor<'a, R: ReaderAt>(
+        method: rawzip::CompressionMethod,
+        reader: rawzip::ZipReader<'a, R>,
+    ) -> Result<Box<dyn Read + 'a>, Error> {
+        assert_eq!(method, rawzip::CompressionMethod::Store);
+        Ok(Box::new(reader))
+    }
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    archive.verify_all(&mut buffer, decompressor).unwrap();
+
+    let pos = data.windows(6).position(|w| w == b"world!").unwrap();
+    data[pos] = b'W';
+
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    let err = archive.verify_all(&mut buffer, decompressor).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidChecksum { .. }));
+    assert_eq!(err.entry_name(), Some("b.txt"));
+    assert!(err.to_string().contains("\"b.txt\""));
+}
+
+#[test]
+fn test_extract_entry_to() {
+    fn decompressor<R: ReaderAt>(
+        method: rawzip::CompressionMethod,
+        reader: rawzip::ZipReader<'_, R>,
+    ) -> Result<flate2::read::DeflateDecoder<rawzip::ZipReader<'_, R>>, Error> {
+        assert_eq!(method, rawzip::CompressionMethod::Deflate);
+        Ok(flate2::read::DeflateDecoder::new(reader))
+    }
+
+    fn first_entry(
+        data: &[u8],
+    ) -> (
+        rawzip::ZipArchive<Vec<u8>>,
+        rawzip::ZipArchiveEntryWayfinder,
+    ) {
+        let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+        let archive = rawzip::ZipArchive::from_reader_at(data.to_vec(), &mut buffer).unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        let entry = entries.next_entry().unwrap().unwrap();
+        assert_eq!(entry.file_path().as_ref(), b"test.txt");
+        let wayfinder = entry.wayfinder();
+        (archive, wayfinder)
+    }
+
+    let mut data = std::fs::read("assets/test.zip").unwrap();
+    let (archive, wayfinder) = first_entry(&data);
+    let mut out = Vec::new();
+    let written = archive
+        .extract_entry_to(wayfinder, decompressor, &mut out)
+        .unwrap();
+    assert_eq!(out, b"This is a test text file.\n");
+    assert_eq!(written, out.len() as u64);
+
+    // Flip a bit of the stored CRC in the central directory
+    let (cd_offset, _) = archive.central_directory_range();
+    data[cd_offset as usize + 16] ^= 1;
+    let (archive, wayfinder) = first_entry(&data);
+    let err = archive
+        .extract_entry_to(wayfinder, decompressor, Vec::new())
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidChecksum { .. }));
+}
+
+#[test]
+fn test_verifying_reader_limited() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+
+    // Below the declared size of the first entry, so the limit trips before
+    // the declared size is checked
+    let limit = 10;
+
+    let assert_limited = |err: std::io::Error| {
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        let err = err.into_inner().unwrap().downcast::<Error>().unwrap();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::LimitExceeded { limit: max, actual } if *max == limit && *actual > limit
+        ));
+        assert!(!err.is_corruption());
+    };
+
+    // A decompressor that never stops producing output
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    let mut reader = entry.verifying_reader_limited(std::io::repeat(b'x'), limit);
+    let mut output = Vec::new();
+    assert_limited(reader.read_to_end(&mut output).unwrap_err());
+    assert!(output.len() as u64 <= limit);
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let entry = entries.next_entry().unwrap().unwrap();
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    let mut reader = entry.verifying_reader_limited(std::io::repeat(b'x'), limit);
+    let mut output = Vec::new();
+    assert_limited(reader.read_to_end(&mut output).unwrap_err());
+    assert!(output.len() as u64 <= limit);
+
+    // Output exactly at the limit verifies as usual
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert_eq!(entry.compression_method(), rawzip::CompressionMethod::Store);
+    let size = entry.uncompressed_size_hint();
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    let mut reader = entry.verifying_reader_limited(entry.reader(), size);
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output).unwrap();
+    assert_eq!(output.len() as u64, size);
+}
+
+#[test]
+fn test_from_segments() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let files = [
+        ("a.txt", b"first file ".repeat(40)),
+        ("b.txt", b"second file ".repeat(40)),
+        ("c.txt", b"third file ".repeat(40)),
+    ];
+    for (name, contents) in &files {
+        let mut file = archive.new_file(name).create().unwrap();
+        let mut writer = rawzip::ZipDataWriter::new(&mut file);
+        writer.write_all(contents).unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        file.finish(descriptor).unwrap();
+    }
+    archive.finish().unwrap();
+    let data = output.into_inner();
+
+    // Split mid-way through the second entry's data
+    let (head, tail) = data.split_at(data.len() / 2);
+    let segments = vec![
+        (head.len() as u64, head.to_vec()),
+        (0, Vec::new()),
+        (tail.len() as u64, tail.to_vec()),
+    ];
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_segments(segments, &mut buffer).unwrap();
+    assert_eq!(archive.get_ref().len(), data.len() as u64);
+
+    let mut entries = archive.entries(&mut buffer);
+    let mut actual = Vec::new();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let name = entry
+            .file_path()
+            .try_normalize()
+            .unwrap()
+            .as_ref()
+            .to_string();
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let mut contents = Vec::new();
+        entry
+            .verifying_reader(entry.reader())
+            .read_to_end(&mut contents)
+            .unwrap();
+        actual.push((name, contents));
+    }
+
+    let expected = files
+        .iter()
+        .map(|(name, contents)| (name.to_string(), contents.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_from_segments_info_zip_split() {
+    // Created with `zip -s 64k split.zip rand.bin hello.txt`, which records
+    // offsets relative to each disk and starts with a spanning marker.
+    let segments = ["assets/split.z01", "assets/split.zip"]
+        .iter()
+        .map(|path| {
+            let data = std::fs::read(path).unwrap();
+            (data.len() as u64, data)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(&segments[0].1[..4], b"PK\x07\x08");
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_segments(segments, &mut buffer).unwrap();
+    assert_eq!(archive.base_offset(), 0);
+
+    let mut entries = archive.entries(&mut buffer);
+    let mut actual = Vec::new();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let name = entry
+            .file_path()
+            .try_normalize()
+            .unwrap()
+            .as_ref()
+            .to_string();
+        let method = entry.compression_method();
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let mut contents = Vec::new();
+        match method {
+            rawzip::CompressionMethod::Deflate => entry
+                .verifying_reader(flate2::read::DeflateDecoder::new(entry.reader()))
+                .read_to_end(&mut contents)
+                .unwrap(),
+            _ => entry
+                .verifying_reader(entry.reader())
+                .read_to_end(&mut contents)
+                .unwrap(),
+        };
+        actual.push((name, contents.len()));
+        if method == rawzip::CompressionMethod::Store {
+            assert_eq!(contents, b"hello split world\n");
+        }
+    }
+
+    assert_eq!(
+        actual,
+        vec![
+            (String::from("rand.bin"), 70000),
+            (String::from("hello.txt"), 18)
+        ]
+    );
+}
+
+#[test]
+fn test_auto_create_parents() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::builder()
+        .auto_create_parents(true)
+        .build(&mut output);
+    let modified = rawzip::time::UtcDateTime::from_components(2024, 1, 2, 3, 4, 6, 0).unwrap();
+
+    archive.new_dir("a/").create().unwrap();
+    for name in ["a/b/c.txt", "a/b/d.txt", "e/f.txt"] {
+        let mut file = archive
+            .new_file(name)
+            .last_modified(modified)
+            .create()
+            .unwrap();
+        let mut writer = rawzip::ZipDataWriter::new(&mut file);
+        writer.write_all(name.as_bytes()).unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        file.finish(descriptor).unwrap();
+    }
+    // Directories already written as parents aren't written again
+    archive.new_dir("a/b/").create().unwrap();
+    archive.new_dir("e/").create().unwrap();
+    archive.new_dir("g/h/").create().unwrap();
+    archive.new_dir("g/").create().unwrap();
+    archive.finish().unwrap();
+
+    let data = output.into_inner();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let mut entries = archive.entries();
+    let mut actual = Vec::new();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let name = entry
+            .file_path()
+            .try_normalize()
+            .unwrap()
+            .as_ref()
+            .to_string();
+        if entry.is_dir() {
+            assert_eq!(entry.compression_method(), rawzip::CompressionMethod::Store);
+            assert_eq!(entry.uncompressed_size_hint(), 0);
+        }
+        if name == "a/b/" {
+            assert_eq!(entry.last_modified().to_string(), "2024-01-02T03:04:06Z");
+        }
+        actual.push(name);
+    }
+
+    assert_eq!(
+        actual,
+        [
+            "a/",
+            "a/b/",
+            "a/b/c.txt",
+            "a/b/d.txt",
+            "e/",
+            "e/f.txt",
+            "g/",
+            "g/h/"
+        ]
+    );
+}
+
+#[test]
+fn test_auto_create_parents_disabled_by_default() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let mut file = archive.new_file("a/b/c.txt").create().unwrap();
+    let writer = rawzip::ZipDataWriter::new(&mut file);
+    let (_, descriptor) = writer.finish().unwrap();
+    file.finish(descriptor).unwrap();
+    archive.finish().unwrap();
+
+    let data = output.into_inner();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.entries_hint(), 1);
+}
+
+#[test]
+fn test_reject_duplicate_names() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::builder()
+        .reject_duplicate_names(true)
+        .auto_create_parents(true)
+        .build(&mut output);
+    archive.store_reader("a/file.txt", &b"first"[..]).unwrap();
+
+    let err = archive
+        .store_reader("a/file.txt", &b"second"[..])
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+    // The parent directory was written automatically, so adding it is a no-op
+    archive.new_dir("a/").create().unwrap();
+    archive.store_reader("a/other.txt", &b"other"[..]).unwrap();
+    archive.finish().unwrap();
+
+    let data = output.into_inner();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.entries_hint(), 3);
+
+    // Duplicates are allowed unless opted out
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    archive.store_reader("file.txt", &b"first"[..]).unwrap();
+    archive.store_reader("file.txt", &b"second"[..]).unwrap();
+    archive.finish().unwrap();
+}
+
+#[test]
+fn test_reject_duplicate_names_after_failed_create() {
+    struct FlakyWriter<'a> {
+        fail: &'a std::cell::Cell<bool>,
+    }
+
+    impl Write for FlakyWriter<'_> {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.fail.get() {
+                return Err(std::io::Error::new(std::io::ErrorKind::Other, "flaky"));
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let fail = std::cell::Cell::new(true);
+    let mut archive = rawzip::ZipArchiveWriter::builder()
+        .reject_duplicate_names(true)
+        .build(FlakyWriter { fail: &fail });
+
+    // Names too long to be buffered are written straight through
+    let name = "a".repeat(10_000);
+    let err = archive.store_reader(&name, &b"data"[..]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::IO(_)));
+    let err = archive.new_dir(&format!("{name}/")).create().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::IO(_)));
+
+    // The names of entries that failed to be created are free again
+    fail.set(false);
+    archive.store_reader(&name, &b"data"[..]).unwrap();
+    archive.new_dir(&format!("{name}/")).create().unwrap();
+    let err = archive.store_reader(&name, &b"data"[..]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+}
+
+#[test]
+fn test_read_at_vectored() {
+    struct BatchingReader {
+        data: Vec<u8>,
+        batches: std::cell::RefCell<Vec<Vec<(usize, u64)>>>,
+    }
+
+    impl ReaderAt for BatchingReader {
+        fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+            self.data.read_at(buf, offset)
+        }
+
+        fn read_at_vectored(&self, bufs: &mut [(&mut [u8], u64)]) -> std::io::Result<()> {
+            let ranges = bufs.iter().map(|(buf, offset)| (buf.len(), *offset));
+            self.batches.borrow_mut().push(ranges.collect());
+            for (buf, offset) in bufs.iter_mut() {
+                self.data.read_exact_at(buf, *offset)?;
+            }
+            Ok(())
+        }
+    }
+
+    let reader = BatchingReader {
+        data: std::fs::read("assets/test.zip").unwrap(),
+        batches: Default::default(),
+    };
+
+    let (mut signature, mut eocd) = ([0u8; 4], [0u8; 22]);
+    let comment_len = 26;
+    let eocd_offset = reader.data.len() as u64 - 22 - comment_len;
+
+    // Overrides are used through references
+    let by_ref = &&reader;
+    by_ref
+        .read_at_vectored(&mut [(&mut signature, 0), (&mut eocd, eocd_offset)])
+        .unwrap();
+    assert_eq!(
+        *reader.batches.borrow(),
+        vec![vec![(4, 0), (22, eocd_offset)]]
+    );
+    assert_eq!(signature, [b'P', b'K', 3, 4]);
+    assert_eq!(eocd[..4], [b'P', b'K', 5, 6]);
+
+    // The default implementation reads each range in turn
+    let (mut signature, mut eocd) = ([0u8; 4], [0u8; 22]);
+    reader
+        .data
+        .read_at_vectored(&mut [(&mut signature, 0), (&mut eocd, eocd_offset)])
+        .unwrap();
+    assert_eq!(signature, [b'P', b'K', 3, 4]);
+    assert_eq!(eocd[..4], [b'P', b'K', 5, 6]);
+
+    let mut past_end = [0u8; 4];
+    let err = reader
+        .data
+        .read_at_vectored(&mut [(&mut past_end, reader.data.len() as u64 - 2)])
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    // Wrappers forward the ranges, translated to the wrapped reader
+    reader.batches.borrow_mut().clear();
+    let (mut head, mut tail) = ([0u8; 4], [0u8; 4]);
+    let bounded = rawzip::BoundedReaderAt::new(&reader, 10, 100);
+    bounded
+        .read_at_vectored(&mut [(&mut head, 0), (&mut tail, 20)])
+        .unwrap();
+    assert_eq!(head, reader.data[10..14]);
+    assert_eq!(tail, reader.data[30..34]);
+    assert_eq!(*reader.batches.borrow(), vec![vec![(4, 10), (4, 30)]]);
+    let err = bounded
+        .read_at_vectored(&mut [(&mut head, 98)])
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    reader.batches.borrow_mut().clear();
+    let spanned = rawzip::SpannedReaderAt::new(vec![(8, &reader), (100, &reader)]);
+    let mut across = [0u8; 6];
+    spanned
+        .read_at_vectored(&mut [(&mut across, 5), (&mut tail, 20)])
+        .unwrap();
+    assert_eq!(across[..3], reader.data[5..8]);
+    assert_eq!(across[3..], reader.data[..3]);
+    assert_eq!(tail, reader.data[12..16]);
+    assert_eq!(
+        *reader.batches.borrow(),
+        vec![vec![(3, 5)], vec![(3, 0), (4, 12)]]
+    );
+
+    reader.batches.borrow_mut().clear();
+    let caching = rawzip::CachingReaderAt::new(&reader).block_size(16);
+    rawzip::ReaderAt::read_exact_at(&caching, &mut head, 0).unwrap();
+    caching
+        .read_at_vectored(&mut [(&mut head, 4), (&mut tail, 20)])
+        .unwrap();
+    assert_eq!(head, reader.data[4..8]);
+    assert_eq!(tail, reader.data[20..24]);
+    assert_eq!(*reader.batches.borrow(), vec![vec![(4, 20)]]);
+
+    // Locating a zip64 archive whose end of central directory record crosses
+    // read boundaries fetches the rest of the comment and the zip64 locator
+    // in a single batch
+    let mut data = std::fs::read("assets/zip64.zip").unwrap();
+    let eocd_offset = data.len() - 22;
+    data[eocd_offset + 20..].copy_from_slice(&500u16.to_le_bytes());
+    data.extend(std::iter::repeat(b'c').take(500));
+    let reader = BatchingReader {
+        data,
+        batches: Default::default(),
+    };
+    let mut buffer = vec![0u8; 256];
+    let len = reader.data.len() as u64;
+    let archive = rawzip::ZipLocator::new()
+        .locate_in_reader(&reader, &mut buffer, len)
+        .map_err(|(_, e)| e)
+        .unwrap();
+    assert_eq!(archive.comment().as_bytes(), &[b'c'; 500][..]);
+    assert_eq!(archive.entries_hint(), 1);
+    let comment_read = eocd_offset as u64 + 256;
+    assert_eq!(
+        *reader.batches.borrow(),
+        vec![vec![(266, comment_read), (20, eocd_offset as u64 - 20)]]
+    );
+}
+
+#[test]
+fn test_rewrite_comment() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let mut file = archive.new_file("file.txt").create().unwrap();
+    let mut writer = rawzip::ZipDataWriter::new(&mut file);
+    writer.write_all(b"Hello, world!").unwrap();
+    let (_, descriptor) = writer.finish().unwrap();
+    file.finish(descriptor).unwrap();
+    archive.finish().unwrap();
+    let original_len = output.get_ref().len() as u64;
+
+    let comment = b"build 1234 from commit abcdef";
+    let len = rawzip::rewrite_comment(&mut output, comment).unwrap();
+    assert_eq!(len, original_len + comment.len() as u64);
+    assert_eq!(output.get_ref().len() as u64, len);
+
+    // Shrinking drops the stale tail
+    let len = rawzip::rewrite_comment(&mut output, b"short").unwrap();
+    assert_eq!(len, original_len + 5);
+    assert_eq!(output.get_ref().len() as u64, len);
+
+    let data = output.into_inner();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.comment().as_bytes(), b"short");
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    assert_eq!(entry.data(), b"Hello, world!");
+
+    let mut output = Cursor::new(data);
+    let err = rawzip::rewrite_comment(&mut output, &vec![b'x'; 70_000]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+
+    let mut output = Cursor::new(b"not a zip file".to_vec());
+    let err = rawzip::rewrite_comment(&mut output, b"comment").unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::MissingEndOfCentralDirectory
+    ));
+    assert_eq!(output.into_inner(), b"not a zip file");
+}
+
+#[test]
+fn test_rewrite_comment_file() {
+    let path = std::env::temp_dir().join(format!("rawzip-comment-{}.zip", std::process::id()));
+    let mut file = std::fs::File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap();
+    rawzip::ZipArchiveWriter::new(&mut file).finish().unwrap();
+
+    rawzip::rewrite_comment(&mut file, &[b'x'; 100]).unwrap();
+    let len = rawzip::rewrite_comment(&mut file, b"short").unwrap();
+    assert_eq!(file.metadata().unwrap().len(), len);
+    drop(file);
+
+    let data = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.comment().as_bytes(), b"short");
+}
+
+#[test]
+fn test_entry_is_zip64() {
+    let is_zip64 = |path: &str| {
+        let data = std::fs::read(path).unwrap();
+        let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+        let entry = archive.entries().next_entry().unwrap().unwrap();
+        entry.is_zip64()
+    };
+
+    assert!(is_zip64("assets/zip64.zip"));
+    assert!(!is_zip64("assets/test.zip"));
+}
+
+#[test]
+fn test_from_buffered_seekable_seeks() {
+    struct SeekCounter<R> {
+        inner: R,
+        seeks: std::rc::Rc<std::cell::Cell<usize>>,
+    }
+
+    impl<R: Read> Read for SeekCounter<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl<R: std::io::Seek> std::io::Seek for SeekCounter<R> {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            self.seeks.set(self.seeks.get() + 1);
+            self.inner.seek(pos)
+        }
+    }
+
+    fn read_all<R: ReaderAt>(archive: rawzip::ZipArchive<R>) -> Vec<Vec<u8>> {
+        // A small buffer forces the central directory to be read in pieces
+        let mut buffer = vec![0u8; 64];
+        let mut entries = archive.entries(&mut buffer);
+        let mut contents = Vec::new();
+        while let Some(entry) = entries.next_entry().unwrap() {
+            let entry = archive.get_entry(entry.wayfinder()).unwrap();
+            let mut data = Vec::new();
+            let mut reader = entry.reader();
+            let mut chunk = [0u8; 16];
+            loop {
+                let read = reader.read(&mut chunk).unwrap();
+                if read == 0 {
+                    break;
+                }
+                data.extend_from_slice(&chunk[..read]);
+            }
+            contents.push(data);
+        }
+        contents
+    }
+
+    let data = std::fs::read("assets/zip64-2.zip").unwrap();
+    let open = || {
+        let seeks = std::rc::Rc::new(std::cell::Cell::new(0));
+        let reader = SeekCounter {
+            inner: Cursor::new(data.clone()),
+            seeks: seeks.clone(),
+        };
+        (std::io::BufReader::new(reader), seeks)
+    };
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let (reader, seeks) = open();
+    let archive = rawzip::ZipArchive::from_seekable(reader, &mut buffer).unwrap();
+    let expected = read_all(archive);
+    let unbuffered_seeks = seeks.get();
+
+    let (reader, seeks) = open();
+    let archive = rawzip::ZipArchive::from_buffered_seekable(reader, &mut buffer).unwrap();
+    assert_eq!(read_all(archive), expected);
+    let buffered_seeks = seeks.get();
+
+    assert!(
+        buffered_seeks * 3 < unbuffered_seeks,
+        "{buffered_seeks} seeks is not less than {unbuffered_seeks}"
+    );
+}
+
+#[test]
+fn test_wayfinder_compression_method() {
+    let data = std::fs::read("assets/zip64-2.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(
+        entry.wayfinder().compression_method(),
+        entry.compression_method()
+    );
+    assert_eq!(
+        entry.wayfinder().compression_method(),
+        rawzip::CompressionMethod::Deflate
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_wayfinder_serde_roundtrip() {
+    let file = File::open("assets/test.zip").unwrap();
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_file(file, &mut buffer).unwrap();
+
+    let mut index = Vec::new();
+    let mut entries = archive.entries(&mut buffer);
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let name = entry
+            .file_path()
+            .try_normalize()
+            .unwrap()
+            .as_ref()
+            .to_string();
+        index.push((name, entry.wayfinder()));
+    }
+    let json = serde_json::to_string(&index).unwrap();
+
+    // Reopen the archive and use the persisted index instead of the central directory
+    let file = File::open("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_file(file, &mut buffer).unwrap();
+    let restored: Vec<(String, rawzip::ZipArchiveEntryWayfinder)> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, index);
+
+    let mut actual = Vec::new();
+    for (name, wayfinder) in restored {
+        let entry = archive.get_entry(wayfinder).unwrap();
+        let mut contents = Vec::new();
+        match wayfinder.compression_method() {
+            rawzip::CompressionMethod::Store => {
+                let mut reader = entry.verifying_reader(entry.reader());
+                reader.read_to_end(&mut contents).unwrap();
+            }
+            rawzip::CompressionMethod::Deflate => {
+                let inflater = flate2::read::DeflateDecoder::new(entry.reader());
+                let mut reader = entry.verifying_reader(inflater);
+                reader.read_to_end(&mut contents).unwrap();
+            }
+            method => panic!("unexpected compression method {method:?}"),
+        }
+        actual.push((name, contents.len()));
+    }
+
+    assert_eq!(actual.len(), 2);
+    assert_eq!(actual[0].0, "test.txt");
+    assert!(actual.iter().all(|(_, len)| *len > 0));
+}
+
+#[test]
+fn test_store_reader() {
+    let data = (0..1 << 20).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let modified = rawzip::time::UtcDateTime::from_components(2024, 5, 6, 7, 8, 10, 0).unwrap();
+
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let written = archive.store_reader("big.bin", data.as_slice()).unwrap();
+    assert_eq!(written, data.len() as u64);
+    let written = archive
+        .new_file("small.txt")
+        .compression_method(rawzip::CompressionMethod::Deflate)
+        .last_modified(modified)
+        .store_reader(&b"Hello, world!"[..])
+        .unwrap();
+    assert_eq!(written, 13);
+    archive.finish().unwrap();
+
+    let output = output.into_inner();
+    let archive = rawzip::ZipArchive::from_slice(&output).unwrap();
+    let mut entries = archive.entries();
+
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert_eq!(entry.compression_method(), rawzip::CompressionMethod::Store);
+    let wayfinder = entry.wayfinder();
+    let entry = archive.get_entry(wayfinder).unwrap();
+    let mut actual = Vec::new();
+    entry
+        .verifying_reader(entry.data())
+        .read_to_end(&mut actual)
+        .unwrap();
+    assert_eq!(actual, data);
+
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert_eq!(entry.compression_method(), rawzip::CompressionMethod::Store);
+    assert_eq!(entry.last_modified().to_string(), "2024-05-06T07:08:10Z");
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    assert_eq!(entry.data(), b"Hello, world!");
+}
+
+#[test]
+fn test_reader_stops_at_compressed_boundary() {
+    let contents = b"streamed through a deflate encoder ".repeat(100);
+    for zip64 in [false, true] {
+        let mut output = Cursor::new(Vec::new());
+        let builder = rawzip::ZipArchiveWriter::builder();
+        let builder = if zip64 {
+            builder.zip64_file_size_threshold(10)
+        } else {
+            builder
+        };
+        let mut archive = builder.build(&mut output);
+        let mut file = archive
+            .new_file("file.txt")
+            .compression_method(rawzip::CompressionMethod::Deflate)
+            .create()
+            .unwrap();
+        let encoder =
+            flate2::write::DeflateEncoder::new(file.by_ref(), flate2::Compression::default());
+        let mut writer = rawzip::ZipDataWriter::new(encoder);
+        writer.write_all(&contents).unwrap();
+        let (encoder, descriptor) = writer.finish().unwrap();
+        encoder.finish().unwrap();
+        file.finish(descriptor).unwrap();
+        archive.finish().unwrap();
+        let data = output.into_inner();
+
+        let len = data.len() as u64;
+        let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+        let archive = rawzip::ZipLocator::new()
+            .locate_in_reader(data.as_slice(), &mut buffer, len)
+            .map_err(|(_, e)| e)
+            .unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        let entry = entries.next_entry().unwrap().unwrap();
+        assert!(entry.has_data_descriptor());
+        assert_eq!(entry.is_zip64(), zip64);
+        let compressed_size = entry.compressed_size_hint();
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+
+        // Read in small chunks so that the boundary falls mid-buffer
+        let mut compressed = Vec::new();
+        let mut reader = entry.reader();
+        let mut chunk = [0u8; 7];
+        loop {
+            let read = reader.read(&mut chunk).unwrap();
+            if read == 0 {
+                break;
+            }
+            compressed.extend_from_slice(&chunk[..read]);
+        }
+        assert_eq!(reader.read(&mut chunk).unwrap(), 0);
+        assert_eq!(compressed.len() as u64, compressed_size);
+
+        let (start, end) = entry.compressed_data_range();
+        assert_eq!(compressed, data[start as usize..end as usize]);
+        assert_eq!(data[end as usize..end as usize + 4], [b'P', b'K', 7, 8]);
+
+        let mut inflated = Vec::new();
+        flate2::read::DeflateDecoder::new(compressed.as_slice())
+            .read_to_end(&mut inflated)
+            .unwrap();
+        assert_eq!(inflated, contents);
+    }
+}
+
+#[test]
+fn test_reader_truncated_data() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    let body = archive.get_entry(entry.wayfinder()).unwrap();
+    let body_start = body.compressed_data_range().0;
+    let cutoff = body_start + 5;
+
+    // Pretend the archive ends partway through the entry's data when it is
+    // read from the start of the entry's data
+    let reader = rawzip::FnReaderAt::new(|buf: &mut [u8], offset: u64| {
+        let end = match offset {
+            _ if offset == cutoff => return Ok(0),
+            _ if (body_start..cutoff).contains(&offset) => cutoff as usize,
+            _ => data.len(),
+        };
+        let available = data.get(offset as usize..end).unwrap_or_default();
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        Ok(len)
+    });
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipLocator::new()
+        .locate_in_reader(reader, &mut buffer, data.len() as u64)
+        .map_err(|(_, e)| e)
+        .unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let entry = entries.next_entry().unwrap().unwrap();
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+
+    let mut compressed = Vec::new();
+    let err = entry.reader().read_to_end(&mut compressed).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(compressed.len(), 5);
+}
+
+#[test]
+fn test_total_uncompressed_size() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.total_uncompressed_size().unwrap(), 811);
+
+    let file = std::fs::File::open("assets/test.zip").unwrap();
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_file(file, &mut buffer).unwrap();
+    assert_eq!(archive.total_uncompressed_size(&mut buffer).unwrap(), 811);
+}
+
+#[test]
+fn test_validate_entry_count() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let eocd = data
+        .windows(4)
+        .rposition(|w| w == [0x50, 0x4b, 0x05, 0x06])
+        .unwrap();
+
+    for (hint, expected) in [(2u16, Some(2)), (1000, Some(2)), (1, None)] {
+        let mut data = data.clone();
+        data[eocd + 8..eocd + 10].copy_from_slice(&hint.to_le_bytes());
+        data[eocd + 10..eocd + 12].copy_from_slice(&hint.to_le_bytes());
+
+        let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+        assert_eq!(archive.entries_hint(), u64::from(hint));
+        assert_eq!(archive.validate_entry_count().ok(), expected);
+
+        let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+        let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+        assert_eq!(archive.entries_hint(), u64::from(hint));
+        assert_eq!(archive.validate_entry_count(&mut buffer).ok(), expected);
+    }
+}
+
+#[test]
+fn test_unix_uid_gid() {
+    let owners = |path: &str| {
+        let data = std::fs::read(path).unwrap();
+        let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+        archive
+            .entries()
+            .map(|entry| entry.unwrap().unix_uid_gid())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(owners("assets/test.zip"), [Some((501, 20)); 2]);
+    assert_eq!(owners("assets/unix.zip"), [Some((1000, 1000)); 4]);
+
+    // The central directory's 0x5855 field only has timestamps
+    assert_eq!(owners("assets/time-osx.zip"), [None]);
+}
+
+#[test]
+fn test_walk() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let mut count = 0;
+    archive
+        .walk(|_| {
+            count += 1;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let file = std::fs::File::open("assets/test.zip").unwrap();
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_file(file, &mut buffer).unwrap();
+    let mut count = 0;
+    archive
+        .walk(&mut buffer, |_| {
+            count += 1;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(count, 2);
+
+    // The first error stops the walk
+    let mut count = 0;
+    let err = archive
+        .walk(&mut buffer, |_| {
+            count += 1;
+            Err(rawzip::Error::from(ErrorKind::InvalidInput {
+                msg: "stop".to_string(),
+            }))
+        })
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+    assert_eq!(count, 1);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_bytes_archive() {
+    let data = bytes::Bytes::from(std::fs::read("assets/test.zip").unwrap());
+
+    let archive = rawzip::ZipArchive::from_slice(data.clone()).unwrap();
+    let entry = archive.get_entry_by_name("test.txt").unwrap().unwrap();
+    let mut contents = Vec::new();
+    flate2::read::DeflateDecoder::new(entry.data())
+        .read_to_end(&mut contents)
+        .unwrap();
+    assert_eq!(contents, b"This is a test text file.\n");
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let len = data.len() as u64;
+    let archive = rawzip::ZipLocator::new()
+        .locate_in_reader(data, &mut buffer, len)
+        .map_err(|(_, e)| e)
+        .unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let header = entries.next_entry().unwrap().unwrap();
+    assert_eq!(header.file_path().as_ref(), b"test.txt");
+    let wayfinder = header.wayfinder();
+    let entry = archive.get_entry(wayfinder).unwrap();
+    let mut contents = Vec::new();
+    flate2::read::DeflateDecoder::new(entry.reader())
+        .read_to_end(&mut contents)
+        .unwrap();
+    assert_eq!(contents, b"This is a test text file.\n");
+}
+
+#[test]
+fn test_reserve_entries() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::builder()
+        .reserve_entries(10_000)
+        .build(&mut output);
+    for i in 0..10_000 {
+        archive
+            .store_reader(&format!("file{}.txt", i), &b"data"[..])
+            .unwrap();
+    }
+    assert_eq!(archive.entry_count(), 10_000);
+    archive.finish().unwrap();
+
+    let archive = rawzip::ZipArchive::from_slice(output.get_ref()).unwrap();
+    assert_eq!(archive.entries_hint(), 10_000);
+}
+
+#[test]
+fn test_text_attribute_roundtrip() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    for (name, text) in [("notes.txt", true), ("image.bin", false)] {
+        let mut file = archive.new_file(name).text(text).create().unwrap();
+        let mut writer = rawzip::ZipDataWriter::new(&mut file);
+        writer.write_all(b"data").unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        file.finish(descriptor).unwrap();
+    }
+    archive.finish().unwrap();
+
+    let archive = rawzip::ZipArchive::from_slice(output.get_ref()).unwrap();
+    let mut entries = archive.entries();
+    assert!(entries.next_entry().unwrap().unwrap().is_text());
+    assert!(!entries.next_entry().unwrap().unwrap().is_text());
+}
+
+#[test]
+fn test_write_precompressed() {
+    for seekable in [false, true] {
+        write_precompressed(seekable);
+    }
+}
+
+fn write_precompressed(seekable: bool) {
+    let data = b"Hello, world! Hello, world! Hello, world!".repeat(100);
+    let mut encoder =
+        flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&data).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = if seekable {
+        rawzip::ZipArchiveWriter::new_seekable(&mut output)
+    } else {
+        rawzip::ZipArchiveWriter::new(&mut output)
+    };
+    let mut file = archive
+        .new_file("hello.txt")
+        .compression_method(rawzip::CompressionMethod::Deflate)
+        .create()
+        .unwrap();
+    file.write_all(&compressed).unwrap();
+    let descriptor = rawzip::DataDescriptorOutput::new(rawzip::crc32(&data), data.len() as u64);
+    let written = file.finish(descriptor).unwrap();
+    assert_eq!(written, compressed.len() as u64);
+    archive.finish().unwrap();
+
+    let output = output.into_inner();
+    let archive = rawzip::ZipArchive::from_slice(&output).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(
+        entry.compression_method(),
+        rawzip::CompressionMethod::Deflate
+    );
+    assert_eq!(entry.compressed_size_hint(), compressed.len() as u64);
+    assert_eq!(entry.uncompressed_size_hint(), data.len() as u64);
+
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    assert_eq!(entry.data(), &compressed[..]);
+    let decoder = flate2::read::DeflateDecoder::new(entry.data());
+    let mut reader = entry.verifying_reader(decoder);
+    let mut actual = Vec::new();
+    reader.read_to_end(&mut actual).unwrap();
+    assert_eq!(actual, data);
+}
+
+#[test]
+fn test_writer_coalesces_small_writes() {
+    #[derive(Default)]
+    struct CountingSink {
+        data: Vec<u8>,
+        writes: usize,
+    }
+
+    impl Write for CountingSink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.writes += 1;
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut sink = CountingSink::default();
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut sink);
+    for i in 0..20 {
+        let name = format!("file-{i}.txt");
+        archive.store_reader(&name, name.as_bytes()).unwrap();
+    }
+    let (_, stats) = archive.finish_with_stats().unwrap();
+
+    // Headers are written a field at a time, which would otherwise reach the
+    // sink as hundreds of tiny writes. Finishing each entry's data flushes,
+    // so expect a write per entry and one for the central directory.
+    assert!(sink.writes <= 21, "{} writes", sink.writes);
+    assert_eq!(stats.bytes_written(), sink.data.len() as u64);
+
+    let archive = rawzip::ZipArchive::from_slice(&sink.data).unwrap();
+    assert_eq!(archive.entries_hint(), 20);
+    for (i, entry) in archive.entries().enumerate() {
+        let entry = entry.unwrap();
+        let expected = format!("file-{i}.txt");
+        assert_eq!(entry.file_path().as_ref(), expected.as_bytes());
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+        assert_eq!(entry.data(), expected.as_bytes());
+    }
+
+    // Large writes go straight through
+    let data = vec![b'a'; 100_000];
+    let mut sink = CountingSink::default();
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut sink);
+    archive.store_reader("large.txt", &data[..]).unwrap();
+    archive.finish().unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&sink.data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    assert_eq!(entry.data(), &data[..]);
+}
+
+#[test]
+fn test_write_dir_tree() {
+    let root = std::env::temp_dir().join(format!("rawzip-tree-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("b/empty")).unwrap();
+    std::fs::write(root.join("a.txt"), "alpha").unwrap();
+    std::fs::write(root.join("b/c.txt"), "charlie").unwrap();
+    let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+    filetime::set_file_mtime(root.join("a.txt"), mtime.into()).unwrap();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let permissions = std::fs::Permissions::from_mode(0o755);
+        std::fs::set_permissions(root.join("a.txt"), permissions).unwrap();
+        std::os::unix::fs::symlink("b/c.txt", root.join("link")).unwrap();
+    }
+
+    let read_back = |options: &rawzip::DirTreeOptions| {
+        let output = rawzip::write_dir_tree(Cursor::new(Vec::new()), &root, options).unwrap();
+        let data = output.into_inner();
+        let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+        let mut entries = Vec::new();
+        for entry in archive.entries() {
+            let entry = entry.unwrap();
+            let name = String::from_utf8(entry.file_path().as_ref().to_vec()).unwrap();
+            let zip_entry = archive.get_entry(entry.wayfinder()).unwrap();
+            let mut contents = Vec::new();
+            match entry.compression_method() {
+                rawzip::CompressionMethod::Deflate => {
+                    let decoder = flate2::read::DeflateDecoder::new(zip_entry.data());
+                    let mut reader = zip_entry.verifying_reader(decoder);
+                    reader.read_to_end(&mut contents).unwrap();
+                }
+                _ => contents.extend_from_slice(zip_entry.data()),
+            }
+            entries.push((name, entry.mode(), entry.last_modified(), contents));
+        }
+        entries
+    };
+
+    let entries = read_back(&rawzip::DirTreeOptions::new());
+    let names = entries.iter().map(|x| x.0.as_str()).collect::<Vec<_>>();
+    #[cfg(unix)]
+    assert_eq!(names, ["a.txt", "b/", "b/c.txt", "b/empty/", "link"]);
+    #[cfg(not(unix))]
+    assert_eq!(names, ["a.txt", "b/", "b/c.txt", "b/empty/"]);
+
+    assert_eq!(entries[0].2.to_string(), "2023-11-14T22:13:20Z");
+    assert_eq!(entries[0].3, b"alpha");
+    assert_eq!(entries[2].3, b"charlie");
+    assert!(entries[1].1.is_dir());
+    assert!(entries[3].1.is_dir());
+
+    #[cfg(unix)]
+    {
+        assert_eq!(entries[0].1.permissions(), 0o755);
+        assert!(entries[4].1.is_symlink());
+        assert_eq!(entries[4].3, b"b/c.txt");
+
+        // Followed links are stored as what they point to
+        let method = if cfg!(feature = "flate2") {
+            rawzip::CompressionMethod::Deflate
+        } else {
+            rawzip::CompressionMethod::Store
+        };
+        let options = rawzip::DirTreeOptions::new()
+            .follow_symlinks(true)
+            .compression_method(method);
+        let entries = read_back(&options);
+        assert!(entries[4].1.is_file());
+        assert_eq!(entries[4].3, b"charlie");
+
+        // Following a link back up the tree would never end
+        std::os::unix::fs::symlink("..", root.join("b/up")).unwrap();
+        let err = rawzip::write_dir_tree(Cursor::new(Vec::new()), &root, &options).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn test_write_dir_tree_special_files() {
+    let root = std::env::temp_dir().join(format!("rawzip-tree-special-{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("a.txt"), "alpha").unwrap();
+    std::os::unix::fs::symlink("missing", root.join("dangling")).unwrap();
+    let _socket = std::os::unix::net::UnixListener::bind(root.join("socket")).unwrap();
+
+    for follow in [false, true] {
+        let options = rawzip::DirTreeOptions::new().follow_symlinks(follow);
+        let output = rawzip::write_dir_tree(Cursor::new(Vec::new()), &root, &options).unwrap();
+        let archive = rawzip::ZipArchive::from_slice(output.get_ref()).unwrap();
+        let mut entries = Vec::new();
+        for entry in archive.entries() {
+            let entry = entry.unwrap();
+            let name = String::from_utf8(entry.file_path().as_ref().to_vec()).unwrap();
+            let data = archive
+                .get_entry(entry.wayfinder())
+                .unwrap()
+                .data()
+                .to_vec();
+            entries.push((name, entry.mode().is_symlink(), data));
+        }
+
+        assert_eq!(
+            entries,
+            [
+                (String::from("a.txt"), false, b"alpha".to_vec()),
+                (String::from("dangling"), true, b"missing".to_vec()),
+            ]
+        );
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_spill_central_directory_cleanup() {
+    let dir = std::env::temp_dir().join(format!("rawzip-spill-cleanup-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let spill = dir.join("central-directory");
+
+    // The scratch file is removed when the archive is never finished
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::builder()
+        .spill_central_directory(&spill)
+        .build(&mut output);
+    archive.store_reader("a.txt", &b"alpha"[..]).unwrap();
+    assert!(spill.exists());
+    drop(archive);
+    assert!(!spill.exists());
+
+    // An existing file is never overwritten
+    std::fs::write(&spill, "precious").unwrap();
+    let mut archive = rawzip::ZipArchiveWriter::builder()
+        .spill_central_directory(&spill)
+        .build(&mut output);
+    let err = archive.store_reader("a.txt", &b"alpha"[..]).unwrap_err();
+    assert!(err.is_io());
+    drop(archive);
+    assert_eq!(std::fs::read(&spill).unwrap(), b"precious");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_spill_central_directory() {
+    let dir = std::env::temp_dir().join(format!("rawzip-spill-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let spill = dir.join("central-directory");
+
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::builder()
+        .spill_central_directory(&spill)
+        .build(&mut output);
+    for i in 0..100_000 {
+        archive
+            .store_reader(&format!("file{}.txt", i), std::io::empty())
+            .unwrap();
+    }
+    assert_eq!(archive.entry_count(), 100_000);
+    assert!(spill.exists());
+    archive.finish().unwrap();
+    assert!(!spill.exists());
+    std::fs::remove_dir(&dir).unwrap();
+
+    let archive = rawzip::ZipArchive::from_slice(output.get_ref()).unwrap();
+    assert_eq!(archive.entries_hint(), 100_000);
+    let mut entries = archive.entries();
+    let mut count = 0;
+    while let Some(entry) = entries.next_entry().unwrap() {
+        assert_eq!(
+            entry.file_path().as_ref(),
+            format!("file{}.txt", count).as_bytes()
+        );
+        assert_eq!(entry.uncompressed_size_hint(), 0);
+        count += 1;
+    }
+    assert_eq!(count, 100_000);
+}
+
+#[test]
+fn test_get_entry_checked_crc_mismatch() {
+    let mut data = std::fs::read("assets/test.zip").unwrap();
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let wayfinder = archive.entries().next_entry().unwrap().unwrap().wayfinder();
+    assert!(archive.get_entry_checked(wayfinder).is_ok());
+
+    // Corrupt the CRC of the first local file header
+    data[14] ^= 0xff;
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert!(archive.get_entry(wayfinder).is_ok());
+    let err = archive.get_entry_checked(wayfinder).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::LocalHeaderMismatch { field: "crc", .. }
+    ));
+    assert!(err.is_corruption());
+    assert_eq!(err.offset(), Some(0));
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    assert!(archive.get_entry(wayfinder).is_ok());
+    let err = archive.get_entry_checked(wayfinder).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::LocalHeaderMismatch { .. }));
+}
+
+#[test]
+fn test_manifest() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let manifest = archive.entries().manifest().unwrap();
+    let names = manifest
+        .iter()
+        .map(|(path, _)| path.as_ref())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["test.txt", "gophercolor16x16.png"]);
+    assert_eq!(manifest[1].1.uncompressed_size_hint(), 785);
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let file = File::open("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_file(file, &mut buffer).unwrap();
+    let reader_manifest = archive.entries(&mut buffer).manifest().unwrap();
+    assert_eq!(reader_manifest.len(), manifest.len());
+    for ((a, a_way), (b, b_way)) in manifest.iter().zip(reader_manifest.iter()) {
+        assert_eq!(a.as_ref(), b.as_ref());
+        assert_eq!(a_way, b_way);
+    }
+
+    let entry = archive.get_entry(reader_manifest[0].1).unwrap();
+    assert_eq!(
+        entry.compressed_data_range().1 - entry.compressed_data_range().0,
+        25
+    );
+}
+
+#[test]
+fn test_read_to_vec() {
+    fn decompress<'a, R: Read + 'a>(
+        method: rawzip::CompressionMethod,
+        reader: R,
+    ) -> Result<Box<dyn Read + 'a>, Error> {
+        match method {
+            rawzip::CompressionMethod::Store => Ok(Box::new(reader)),
+            rawzip::CompressionMethod::Deflate => {
+                Ok(Box::new(flate2::read::DeflateDecoder::new(reader)))
+            }
+            _ => Err(Error::from(ErrorKind::InvalidInput {
+                msg: format!("unsupported compression method: {:?}", method),
+            })),
+        }
+    }
+
+    // Deflate
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let file = File::open("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_file(file, &mut buffer).unwrap();
+    let wayfinder = archive
+        .entries(&mut buffer)
+        .next_entry()
+        .unwrap()
+        .unwrap()
+        .wayfinder();
+    let entry = archive.get_entry(wayfinder).unwrap();
+    let contents = entry.read_to_vec(decompress).unwrap();
+    assert_eq!(contents, b"This is a test text file.\n");
+
+    // Store
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    archive
+        .store_reader("stored.txt", &b"stored data"[..])
+        .unwrap();
+    archive.finish().unwrap();
+    let mut data = output.into_inner();
+
+    let archive =
+        rawzip::ZipArchive::from_seekable(Cursor::new(data.clone()), &mut buffer).unwrap();
+    let wayfinder = archive
+        .entries(&mut buffer)
+        .next_entry()
+        .unwrap()
+        .unwrap()
+        .wayfinder();
+    let entry = archive.get_entry(wayfinder).unwrap();
+    assert_eq!(entry.read_to_vec(decompress).unwrap(), b"stored data");
+
+    // Corrupted data fails CRC verification
+    let pos = data.windows(11).position(|w| w == b"stored data").unwrap();
+    data[pos] ^= 0xff;
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(data), &mut buffer).unwrap();
+    let entry = archive.get_entry(wayfinder).unwrap();
+    let err = entry.read_to_vec(decompress).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidChecksum { .. }));
+}
+
+#[test]
+fn test_verifying_reader_strict() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let mut file = archive.new_file("file.txt").create().unwrap();
+    let mut writer = rawzip::ZipDataWriter::new(&mut file);
+    writer.write_all(b"Hello, world!").unwrap();
+    let (_, descriptor) = writer.finish().unwrap();
+    file.finish(descriptor).unwrap();
+    archive.finish().unwrap();
+    let mut data = output.into_inner();
+
+    let read_all = |data: &[u8], strict: bool| {
+        let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+        let archive = rawzip::ZipArchive::from_seekable(Cursor::new(data), &mut buffer).unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        let header = entries.next_entry().unwrap().unwrap();
+        assert!(header.has_data_descriptor());
+        let entry = archive.get_entry(header.wayfinder()).unwrap();
+        let mut contents = Vec::new();
+        let reader_result = if strict {
+            entry
+                .verifying_reader_strict(entry.reader())
+                .read_to_end(&mut contents)
+        } else {
+            entry
+                .verifying_reader(entry.reader())
+                .read_to_end(&mut contents)
+        };
+
+        let archive = rawzip::ZipArchive::from_slice(data).unwrap();
+        let wayfinder = archive.entries().next_entry().unwrap().unwrap().wayfinder();
+        let entry = archive.get_entry(wayfinder).unwrap();
+        let mut contents = Vec::new();
+        let slice_result = if strict {
+            entry
+                .verifying_reader_strict(entry.data())
+                .read_to_end(&mut contents)
+        } else {
+            entry
+                .verifying_reader(entry.data())
+                .read_to_end(&mut contents)
+        };
+
+        assert_eq!(reader_result.is_ok(), slice_result.is_ok());
+        reader_result.is_ok()
+    };
+
+    // The data descriptor's CRC is authoritative and validated
+    assert!(read_all(&data, false));
+    assert!(read_all(&data, true));
+
+    // A lazily zeroed CRC is only caught when strict
+    let descriptor = data.windows(4).position(|w| w == b"PK\x07\x08").unwrap();
+    data[descriptor + 4..descriptor + 8].fill(0);
+    assert!(read_all(&data, false));
+    assert!(!read_all(&data, true));
+}
+
+#[test]
+fn test_verifying_reader_checks_central_crc() {
+    let mut data = std::fs::read("assets/test.zip").unwrap();
+    let central = data.windows(4).position(|w| w == b"PK\x01\x02").unwrap();
+    data[central + 16] ^= 0xff;
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_seekable(Cursor::new(data), &mut buffer).unwrap();
+    let wayfinder = archive
+        .entries(&mut buffer)
+        .next_entry()
+        .unwrap()
+        .unwrap()
+        .wayfinder();
+    let entry = archive.get_entry(wayfinder).unwrap();
+    let decoder = flate2::read::DeflateDecoder::new(entry.reader());
+    let err = entry
+        .verifying_reader(decoder)
+        .read_to_end(&mut Vec::new())
+        .unwrap_err();
+    let err = err.into_inner().unwrap().downcast::<Error>().unwrap();
+    assert!(matches!(err.kind(), ErrorKind::InvalidChecksum { .. }));
+}
+
+#[test]
+fn test_from_file_buffered() {
+    let file = File::open("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_file_buffered(file).unwrap();
+    assert_eq!(archive.comment().as_bytes(), b"This is a zipfile comment.");
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let mut entries = archive.entries(&mut buffer);
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert_eq!(entry.file_path().as_ref(), b"test.txt");
+}
+
+#[test]
+fn test_archive_is_zip64() {
+    let is_zip64 = |path: &str| {
+        let data = std::fs::read(path).unwrap();
+        let slice_zip64 = rawzip::ZipArchive::from_slice(&data).unwrap().is_zip64();
+
+        let file = File::open(path).unwrap();
+        let archive = rawzip::ZipArchive::from_file_buffered(file).unwrap();
+        assert_eq!(archive.is_zip64(), slice_zip64);
+        slice_zip64
+    };
+
+    assert!(is_zip64("assets/zip64.zip"));
+    assert!(!is_zip64("assets/test.zip"));
+}
+
+#[test]
+fn test_abort_entry_seekable() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new_seekable(&mut output);
+    archive.store_reader("a.txt", &b"first"[..]).unwrap();
+
+    let mut file = archive.new_file("b.txt").create().unwrap();
+    file.write_all(&[b'x'; 10_000]).unwrap();
+    assert!(file.abort().unwrap() > 10_000);
+
+    archive.store_reader("c.txt", &b"third"[..]).unwrap();
+    let (_, stats) = archive.finish_with_stats().unwrap();
+    assert_eq!(stats.entries(), 2);
+
+    let mut data = output.into_inner();
+    assert!(data.len() as u64 > stats.bytes_written());
+    data.truncate(stats.bytes_written() as usize);
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let mut entries = archive.entries();
+    let mut contents = Vec::new();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let name = entry.file_path().try_normalize().unwrap().into_owned();
+        let data = archive.get_entry_checked(entry.wayfinder()).unwrap().data();
+        contents.push((String::from(name), data.to_vec()));
+    }
+    assert_eq!(
+        contents,
+        [
+            (String::from("a.txt"), b"first".to_vec()),
+            (String::from("c.txt"), b"third".to_vec()),
+        ]
+    );
+    assert!(!data.windows(3).any(|w| w == b"xxx"));
+}
+
+#[test]
+fn test_abort_entry_truncating() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new_truncating(&mut output);
+    archive.store_reader("a.txt", &b"first"[..]).unwrap();
+
+    let mut file = archive.new_file("b.txt").create().unwrap();
+    file.write_all(&[b'x'; 10_000]).unwrap();
+    assert_eq!(file.abort().unwrap(), 0);
+
+    let (_, stats) = archive.finish_with_stats().unwrap();
+    assert_eq!(stats.entries(), 1);
+
+    let data = output.into_inner();
+    assert_eq!(data.len() as u64, stats.bytes_written());
+    assert!(!data.windows(3).any(|w| w == b"xxx"));
+
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let manifest = archive.entries().manifest().unwrap();
+    assert_eq!(manifest.len(), 1);
+    assert_eq!(manifest[0].0.as_ref(), "a.txt");
+}
+
+#[test]
+fn test_abort_entry_streaming() {
+    let mut output = Vec::new();
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let mut file = archive.new_file("orphan.txt").create().unwrap();
+    file.write_all(b"partial").unwrap();
+    assert!(file.abort().unwrap() > b"partial".len() as u64);
+    archive.store_reader("kept.txt", &b"kept"[..]).unwrap();
+    archive.finish().unwrap();
+
+    let archive = rawzip::ZipArchive::from_slice(&output).unwrap();
+    let manifest = archive.entries().manifest().unwrap();
+    assert_eq!(manifest.len(), 1);
+    assert_eq!(manifest[0].0.as_ref(), "kept.txt");
+}
+
+#[test]
+fn test_reader_len() {
+    use rawzip::ReaderLen;
+
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let expected = Some(data.len() as u64);
+    assert_eq!(ReaderLen::len(&data.as_slice()), expected);
+    assert_eq!(ReaderLen::len(&data), expected);
+    assert_eq!(ReaderLen::len(&Cursor::new(&data)), expected);
+    assert_eq!(ReaderLen::is_empty(&&data[..0]), Some(true));
+
+    let file = rawzip::FileReader::from(File::open("assets/test.zip").unwrap());
+    assert_eq!(ReaderLen::len(&file), expected);
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_reader_at(data.as_slice(), &mut buffer).unwrap();
+    assert_eq!(archive.entries_hint(), 2);
+    let archive = rawzip::ZipArchive::from_reader_at(file, &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert_eq!(entry.file_path().as_ref(), b"test.txt");
+}
+
+#[test]
+fn test_from_reader_at_unknown_length() {
+    #[derive(Debug)]
+    struct Unsized(Vec<u8>);
+
+    impl ReaderAt for Unsized {
+        fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+            self.0.read_at(buf, offset)
+        }
+    }
+
+    impl rawzip::ReaderLen for Unsized {
+        fn len(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let err = rawzip::ZipArchive::from_reader_at(Unsized(data), &mut buffer).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+}
+
+#[test]
+fn test_bounded_reader_at() {
+    let zip = std::fs::read("assets/test.zip").unwrap();
+    let mut data = b"PK\x05\x06 not the archive ".repeat(8);
+    let start = data.len() as u64;
+    data.extend_from_slice(&zip);
+    data.extend_from_slice(&b"PK\x05\x06 trailing garbage ".repeat(8));
+
+    let reader = rawzip::BoundedReaderAt::new(data.as_slice(), start, zip.len() as u64);
+
+    // Reads are relative to the window and cut short at its end
+    let mut buf = [0u8; 4];
+    reader.read_exact_at(&mut buf, 0).unwrap();
+    assert_eq!(&buf, b"PK\x03\x04");
+    let mut buf = [0u8; 16];
+    assert_eq!(reader.read_at(&mut buf, zip.len() as u64 - 4).unwrap(), 4);
+    assert_eq!(&buf[..4], &zip[zip.len() - 4..]);
+    assert_eq!(reader.read_at(&mut buf, zip.len() as u64).unwrap(), 0);
+    let err = reader.read_at(&mut buf, zip.len() as u64 + 1).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_reader_at(reader, &mut buffer).unwrap();
+    assert_eq!(archive.comment().as_bytes(), b"This is a zipfile comment.");
+
+    let expected = rawzip::ZipArchive::from_slice(&zip).unwrap();
+    let mut expected_entries = expected.entries();
+    let mut entries = archive.entries(&mut buffer);
+    while let Some(entry) = entries.next_entry().unwrap() {
+        let expected_entry = expected_entries.next_entry().unwrap().unwrap();
+        assert_eq!(entry.file_path(), expected_entry.file_path());
+
+        let zip_entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let mut actual = Vec::new();
+        zip_entry.reader().read_to_end(&mut actual).unwrap();
+        let expected_data = expected.get_entry(expected_entry.wayfinder()).unwrap();
+        assert_eq!(actual, expected_data.data());
+    }
+    assert!(expected_entries.next_entry().unwrap().is_none());
+}
+
+#[test]
+fn test_probe_base_offset() {
+    let fixtures = [
+        // The understated directory size implies data before the archive
+        "assets/test-baddirsz.zip",
+        // A 16 byte digital signature record between the central directory
+        // and its end record does the same
+        "assets/test-badbase.zip",
+    ];
+
+    let locator = rawzip::ZipLocator::new().probe_base_offset(true);
+    for name in fixtures {
+        let data = std::fs::read(name).unwrap();
+
+        let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+        assert_eq!(archive.base_offset(), 16);
+        assert!(archive.entries().next_entry().is_err());
+
+        let archive = locator.locate_in_slice(&data).map_err(|(_, e)| e).unwrap();
+        assert_eq!(archive.base_offset(), 0);
+        assert_eq!(archive.comment().as_bytes(), b"This is a zipfile comment.");
+        let mut names = Vec::new();
+        for entry in archive.entries() {
+            let entry = entry.unwrap();
+            names.push(entry.file_path().as_ref().to_vec());
+            archive.get_entry(entry.wayfinder()).unwrap();
+        }
+        assert_eq!(names, [&b"test.txt"[..], &b"gophercolor16x16.png"[..]]);
+
+        let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+        let archive = locator
+            .locate_in_reader(&data[..], &mut buffer, data.len() as u64)
+            .map_err(|(_, e)| e)
+            .unwrap();
+        assert_eq!(archive.base_offset(), 0);
+        let mut entries = archive.entries(&mut buffer);
+        let entry = entries.next_entry().unwrap().unwrap();
+        let wayfinder = entry.wayfinder();
+        assert_eq!(entry.file_path().as_ref(), b"test.txt");
+        archive.get_entry(wayfinder).unwrap();
+    }
+
+    // Archives that are actually prefixed keep their base offset
+    for name in ["assets/test-prefix.zip", "assets/test.zip"] {
+        let data = std::fs::read(name).unwrap();
+        let expected = rawzip::ZipArchive::from_slice(&data).unwrap().base_offset();
+        let archive = locator.locate_in_slice(&data).map_err(|(_, e)| e).unwrap();
+        assert_eq!(archive.base_offset(), expected);
+        assert_eq!(archive.entries().count(), 2);
+    }
+}
+
+#[test]
+fn test_entries_seek_to_offset() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    for i in 0..50 {
+        archive
+            .store_reader(&format!("file-{i:02}.txt"), &b"data"[..])
+            .unwrap();
+    }
+    archive.finish().unwrap();
+    let data = output.into_inner();
+
+    let mut buf = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipArchive::from_reader_at(&data[..], &mut buf).unwrap();
+    let (start, end) = archive.central_directory_range();
+
+    // Checkpoint after the first entry and resume with a new iterator
+    let mut entries = archive.entries(&mut buf);
+    assert_eq!(entries.position(), start);
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert_eq!(entry.file_path().as_ref(), b"file-00.txt");
+    let checkpoint = entries.position();
+
+    let mut entries = archive.entries(&mut buf);
+    entries.seek_to_offset(checkpoint).unwrap();
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert_eq!(entry.file_path().as_ref(), b"file-01.txt");
+
+    // Every position resumes at the entry that followed it
+    let mut positions = Vec::new();
+    let mut entries = archive.entries(&mut buf);
+    loop {
+        let position = entries.position();
+        let Some(entry) = entries.next_entry().unwrap() else {
+            assert_eq!(position, end);
+            break;
+        };
+        positions.push((position, entry.file_path().as_ref().to_vec()));
+    }
+    assert_eq!(positions.len(), 50);
+
+    let mut small_buf = vec![0u8; 256];
+    let mut entries = archive.entries(&mut small_buf);
+    for (position, name) in positions.iter().rev() {
+        entries.seek_to_offset(*position).unwrap();
+        let entry = entries.next_entry().unwrap().unwrap();
+        assert_eq!(entry.file_path().as_ref(), name.as_slice());
+    }
+
+    entries.seek_to_offset(end).unwrap();
+    assert!(entries.next_entry().unwrap().is_none());
+
+    for offset in [start - 1, end + 1] {
+        let err = entries.seek_to_offset(offset).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+    }
+
+    entries.seek_to_offset(start + 1).unwrap();
+    assert!(entries.next_entry().is_err());
+}
+
+#[test]
+fn test_comment_display() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.comment().to_string(), "This is a zipfile comment.");
+    assert_eq!(
+        format!("{:?}", archive.comment()),
+        "\"This is a zipfile comment.\""
+    );
+
+    let mut buf = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let file = File::open("assets/test.zip").unwrap();
+    let archive = rawzip::ZipArchive::from_file(file, &mut buf).unwrap();
+    assert_eq!(
+        format!("{}", archive.comment()),
+        "This is a zipfile comment."
+    );
+    assert_eq!(
+        format!("{:?}", archive.comment().into_owned()),
+        "\"This is a zipfile comment.\""
+    );
+}
+
+#[test]
+fn test_caching_reader_at() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let reads = std::cell::Cell::new(0);
+    let inner = rawzip::FnReaderAt::new(|buf: &mut [u8], offset: u64| {
+        reads.set(reads.get() + 1);
+        (&data[..]).read_at(buf, offset)
+    });
+    let reader = rawzip::CachingReaderAt::new(inner)
+        .block_size(512)
+        .capacity(4);
+
+    let mut buf = [0u8; 4];
+    reader.read_exact_at(&mut buf, 0).unwrap();
+    assert_eq!(&buf, b"PK\x03\x04");
+    reader.read_exact_at(&mut buf, 2).unwrap();
+    assert_eq!(&buf, b"\x03\x04\x14\x00");
+    assert_eq!(reads.get(), 1);
+
+    // Reads spanning blocks are stitched together
+    let mut buf = [0u8; 100];
+    reader.read_exact_at(&mut buf, 480).unwrap();
+    assert_eq!(&buf[..], &data[480..580]);
+
+    let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    let archive = rawzip::ZipLocator::new()
+        .locate_in_reader(reader, &mut buffer, data.len() as u64)
+        .map_err(|(_, e)| e)
+        .unwrap();
+    let wayfinders = archive
+        .entries(&mut buffer)
+        .manifest()
+        .unwrap()
+        .into_iter()
+        .map(|(_, wayfinder)| wayfinder)
+        .collect::<Vec<_>>();
+
+    for wayfinder in &wayfinders {
+        archive.get_entry(*wayfinder).unwrap();
+    }
+
+    let before = reads.get();
+    for wayfinder in &wayfinders {
+        archive.get_entry(*wayfinder).unwrap();
+    }
+    assert_eq!(reads.get(), before);
+}
+
+#[test]
+fn test_data_writer_reset() {
+    use flate2::{write::DeflateEncoder, Compression};
+
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    let mut writer =
+        rawzip::ZipDataWriter::new(DeflateEncoder::new(Vec::new(), Compression::fast()));
+    let entries: [(&str, &[u8]); 2] = [("first.txt", b"Hello, world!"), ("second.txt", b"Goodbye")];
+    for (name, contents) in entries {
+        writer.write_all(contents).unwrap();
+        let descriptor = writer.reset();
+        let compressed = writer.get_mut().reset(Vec::new()).unwrap();
+
+        let mut file = archive
+            .new_file(name)
+            .compression_method(rawzip::CompressionMethod::Deflate)
+            .create()
+            .unwrap();
+        file.write_all(&compressed).unwrap();
+        file.finish(descriptor).unwrap();
+    }
+    archive.finish().unwrap();
+
+    let archive = rawzip::ZipArchive::from_slice(output.get_ref()).unwrap();
+    let mut entries_iter = archive.entries();
+    for (name, contents) in entries {
+        let entry = entries_iter.next_entry().unwrap().unwrap();
+        assert_eq!(entry.file_path().as_ref(), name.as_bytes());
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+        let mut actual = Vec::new();
+        entry
+            .verifying_reader(flate2::read::DeflateDecoder::new(entry.data()))
+            .read_to_end(&mut actual)
+            .unwrap();
+        assert_eq!(actual, contents);
+    }
+}
+
+#[test]
+fn test_copy_entry_verified() {
+    fn decompressor<R: ReaderAt>(
+        method: rawzip::CompressionMethod,
+        reader: rawzip::ZipReader<'_, R>,
+    ) -> Result<flate2::read::DeflateDecoder<rawzip::ZipReader<'_, R>>, Error> {
+        assert_eq!(method, rawzip::CompressionMethod::Deflate);
+        Ok(flate2::read::DeflateDecoder::new(reader))
+    }
+
+    fn copy(data: &[u8]) -> (Result<(), Error>, Vec<u8>) {
+        let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+        let source = rawzip::ZipArchive::from_reader_at(data, &mut buffer).unwrap();
+        let wayfinder = source
+            .entries(&mut buffer)
+            .next_entry()
+            .unwrap()
+            .unwrap()
+            .wayfinder();
+        let entry = source.get_entry(wayfinder).unwrap();
+
+        let mut archive = rawzip::ZipArchiveWriter::new(Vec::new());
+        let result = archive.copy_entry_verified(&entry, decompressor, "copy.txt");
+        let result = result.and_then(|()| {
+            archive
+                .new_file("script.sh")
+                .unix_permissions(0o755)
+                .copy_verified(&entry, decompressor)
+        });
+        (result, archive.finish().unwrap())
+    }
+
+    let mut data = std::fs::read("assets/test.zip").unwrap();
+    let (result, output) = copy(&data);
+    result.unwrap();
+
+    let source = rawzip::ZipArchive::from_slice(&data).unwrap();
+    let header = source.entries().next_entry().unwrap().unwrap();
+    let archive = rawzip::ZipArchive::from_slice(&output).unwrap();
+    let mut entries = archive.entries();
+    let record = entries.next_entry().unwrap().unwrap();
+    assert_eq!(record.file_path().as_ref(), b"copy.txt");
+    assert_eq!(record.last_modified_utc(), header.last_modified_utc());
+    assert_eq!(
+        record.compression_method(),
+        rawzip::CompressionMethod::Deflate
+    );
+    let entry = archive.get_entry(record.wayfinder()).unwrap();
+    let mut contents = Vec::new();
+    entry
+        .verifying_reader(flate2::read::DeflateDecoder::new(entry.data()))
+        .read_to_end(&mut contents)
+        .unwrap();
+    assert_eq!(contents, b"This is a test text file.\n");
+
+    // Permissions are only set when asked for through the builder
+    let record = entries.next_entry().unwrap().unwrap();
+    assert_eq!(record.file_path().as_ref(), b"script.sh");
+    assert_eq!(record.mode().permissions(), 0o755);
+    assert_eq!(record.last_modified_utc(), header.last_modified_utc());
+    assert!(entries.next_entry().unwrap().is_none());
+
+    // Flip a bit of the stored CRC in the central directory
+    let (cd_offset, _) = rawzip::ZipArchive::from_slice(&data)
+        .unwrap()
+        .central_directory_range();
+    data[cd_offset as usize + 16] ^= 1;
+    let (result, output) = copy(&data);
+    let err = result.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidChecksum { .. }));
+    let archive = rawzip::ZipArchive::from_slice(&output).unwrap();
+    assert_eq!(archive.entries_hint(), 0);
+}
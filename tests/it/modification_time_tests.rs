@@ -1,7 +1,8 @@
 use rawzip::{
-    time::{LocalDateTime, UtcDateTime, ZipDateTimeKind},
+    time::{ExtendedTimestamps, LocalDateTime, TimestampSource, UtcDateTime, ZipDateTimeKind},
     ZipArchive, ZipArchiveWriter, ZipDataWriter,
 };
+use rstest::rstest;
 use std::io::Write;
 
 /// Test that modification times are preserved in a round-trip for files
@@ -340,3 +341,95 @@ fn test_parsed_datetime_functionality() {
     assert_eq!(parsed_utc.timezone(), rawzip::time::TimeZone::Utc);
     assert_eq!(parsed_local.timezone(), rawzip::time::TimeZone::Local);
 }
+
+#[test]
+fn test_access_and_creation_time_roundtrip() {
+    let modified = UtcDateTime::from_components(2023, 6, 15, 14, 30, 46, 0).unwrap();
+    let accessed = UtcDateTime::from_components(2023, 7, 1, 8, 0, 0, 0).unwrap();
+    let created = UtcDateTime::from_components(2022, 1, 2, 3, 4, 5, 0).unwrap();
+
+    let mut output = Vec::new();
+    let mut archive = ZipArchiveWriter::new(&mut output);
+    let mut file = archive
+        .new_file("test.txt")
+        .last_modified(modified)
+        .access_time(accessed)
+        .creation_time(created)
+        .create()
+        .unwrap();
+    let mut writer = ZipDataWriter::new(&mut file);
+    writer.write_all(b"Hello, world!").unwrap();
+    let (_, descriptor) = writer.finish().unwrap();
+    file.finish(descriptor).unwrap();
+    archive.finish().unwrap();
+
+    let archive = ZipArchive::from_slice(&output).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(entry.last_modified(), ZipDateTimeKind::Utc(modified));
+
+    // The central directory only carries the modification time
+    let central = entry.extended_timestamps().unwrap();
+    assert_eq!(central.modified(), Some(modified));
+    assert_eq!(central.accessed(), None);
+    assert_eq!(central.created(), None);
+
+    // The local header carries all three
+    let local = &output[entry.local_header_offset() as usize..];
+    let name_len = u16::from_le_bytes([local[26], local[27]]) as usize;
+    let extra_len = u16::from_le_bytes([local[28], local[29]]) as usize;
+    let extra = &local[30 + name_len..30 + name_len + extra_len];
+    let local = ExtendedTimestamps::from_extra_field(extra).unwrap();
+    assert_eq!(local.modified(), Some(modified));
+    assert_eq!(local.accessed(), Some(accessed));
+    assert_eq!(local.created(), Some(created));
+}
+
+#[rstest]
+#[case("time-7zip.zip", TimestampSource::Ntfs)]
+#[case("time-go.zip", TimestampSource::ExtendedUnix)]
+#[case("time-infozip.zip", TimestampSource::ExtendedUnix)]
+#[case("time-osx.zip", TimestampSource::OldUnix)]
+#[case("time-win7.zip", TimestampSource::Dos)]
+#[case("time-winrar.zip", TimestampSource::Ntfs)]
+#[case("time-winzip.zip", TimestampSource::Ntfs)]
+fn test_timestamp_source(#[case] name: &str, #[case] expected: TimestampSource) {
+    let data = std::fs::read(format!("assets/{name}")).unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    for entry in archive.entries() {
+        let entry = entry.unwrap();
+        assert_eq!(entry.timestamp_source(), expected);
+        assert_eq!(
+            entry.last_modified().is_ambiguous(),
+            expected == TimestampSource::Dos
+        );
+    }
+}
+
+#[rstest]
+#[case("time-7zip.zip", "2017-11-01T04:11:57.244817900Z")]
+#[case("time-go.zip", "2017-11-01T04:11:57Z")]
+#[case("time-win7.zip", "2017-10-31T21:11:58Z")]
+fn test_last_modified_utc(#[case] name: &str, #[case] expected: &str) {
+    let data = std::fs::read(format!("assets/{name}")).unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    let utc = entry.last_modified_utc();
+    assert_eq!(utc.to_string(), expected);
+
+    // The components agree with the timestamp whatever its time zone
+    match entry.last_modified() {
+        ZipDateTimeKind::Utc(dt) => assert_eq!(utc, dt),
+        ZipDateTimeKind::Local(dt) => {
+            let expected = UtcDateTime::from_components(
+                dt.year(),
+                dt.month(),
+                dt.day(),
+                dt.hour(),
+                dt.minute(),
+                dt.second(),
+                dt.nanosecond(),
+            );
+            assert_eq!(Some(utc), expected);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use rawzip::{ZipArchive, ZipArchiveWriter, ZipDataWriter};
+use rawzip::{EntryMode, ZipArchive, ZipArchiveWriter, ZipDataWriter};
 use std::io::Write;
 
 #[test]
@@ -119,3 +119,78 @@ fn test_permissions_without_unix_permissions() {
         actual_mode
     );
 }
+
+#[test]
+fn test_symlink_roundtrip() {
+    let mut output = Vec::new();
+    {
+        let mut archive = ZipArchiveWriter::new(&mut output);
+        archive.new_symlink("symlink", "../target").unwrap();
+        assert!(archive.new_symlink("dir/", "../target").is_err());
+        archive.finish().unwrap();
+    }
+
+    let archive = ZipArchive::from_slice(&output).unwrap();
+    let mut entries = archive.entries();
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert_eq!(
+        entry.file_path().try_normalize().unwrap().as_ref(),
+        "symlink"
+    );
+    assert!(entry.mode().is_symlink());
+    assert_eq!(entry.mode().value(), 0o120777);
+    assert_eq!(entry.compression_method(), rawzip::CompressionMethod::Store);
+
+    let data = archive.get_entry(entry.wayfinder()).unwrap();
+    assert_eq!(data.data(), b"../target");
+    assert!(entries.next_entry().unwrap().is_none());
+}
+
+#[test]
+fn test_directory_rejects_non_directory_mode() {
+    let mut output = Vec::new();
+    let mut archive = ZipArchiveWriter::new(&mut output);
+    let result = archive.new_dir("dir/").unix_permissions(0o120777).create();
+    assert!(result.is_err());
+
+    let result = archive.new_dir("dir/").unix_permissions(0o040755).create();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_entry_mode_roundtrip() {
+    let file_mode = EntryMode::from_unix(0o104755);
+    let dir_mode = EntryMode::from_unix(0o041775);
+    let symlink_mode = EntryMode::from_unix(0o120777);
+
+    let mut output = Vec::new();
+    {
+        let mut archive = ZipArchiveWriter::new(&mut output);
+        archive
+            .new_file("file")
+            .mode(file_mode)
+            .store_reader(&b"data"[..])
+            .unwrap();
+        archive.new_dir("dir/").mode(dir_mode).create().unwrap();
+        archive
+            .new_file("link")
+            .mode(symlink_mode)
+            .store_reader(&b"file"[..])
+            .unwrap();
+        archive.finish().unwrap();
+    }
+
+    let archive = ZipArchive::from_slice(&output).unwrap();
+    let modes = archive
+        .entries()
+        .map(|entry| entry.unwrap().mode())
+        .collect::<Vec<_>>();
+    assert_eq!(modes, [file_mode, dir_mode, symlink_mode]);
+
+    assert!(modes[0].is_file());
+    assert_eq!(modes[0].permissions(), 0o4755);
+    assert!(modes[1].is_dir());
+    assert_eq!(modes[1].permissions(), 0o1775);
+    assert!(modes[2].is_symlink());
+    assert_eq!(modes[2].permissions(), 0o777);
+}
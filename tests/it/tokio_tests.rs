@@ -0,0 +1,171 @@
+use rawzip::{
+    AsyncZipArchive, CompressionMethod, Error, ErrorKind, TokioReaderAt, ZipArchive,
+    RECOMMENDED_BUFFER_SIZE,
+};
+use std::io::Read;
+
+fn decompressor<'a, R: Read + 'a>(
+    method: CompressionMethod,
+    reader: R,
+) -> Result<Box<dyn Read + 'a>, Error> {
+    match method {
+        CompressionMethod::Store => Ok(Box::new(reader)),
+        CompressionMethod::Deflate => Ok(Box::new(flate2::read::DeflateDecoder::new(reader))),
+        _ => Err(Error::from(ErrorKind::InvalidInput {
+            msg: format!("unsupported compression method: {:?}", method),
+        })),
+    }
+}
+
+#[tokio::test]
+async fn test_async_read_test_zip() {
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let expected = ZipArchive::from_slice(&data).unwrap();
+
+    let reader = TokioReaderAt::open("assets/test.zip").await.unwrap();
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = AsyncZipArchive::from_reader_at(reader, &mut buffer)
+        .await
+        .unwrap();
+    assert_eq!(archive.entries_hint(), expected.entries_hint());
+    assert_eq!(archive.comment().as_bytes(), expected.comment().as_bytes());
+
+    let mut records = Vec::new();
+    let mut entries = archive.entries(&mut buffer);
+    while let Some(entry) = entries.next_entry().await.unwrap() {
+        records.push((entry.file_path().as_ref().to_vec(), entry.wayfinder()));
+    }
+
+    let mut expected_entries = expected.entries();
+    for (name, wayfinder) in records {
+        let expected_entry = expected_entries.next_entry().unwrap().unwrap();
+        assert_eq!(name, expected_entry.file_path().as_ref());
+        assert_eq!(wayfinder, expected_entry.wayfinder());
+
+        let expected_entry = expected.get_entry(wayfinder).unwrap();
+        let entry = archive.get_entry(wayfinder).await.unwrap();
+        assert_eq!(
+            entry.compressed_data_range(),
+            expected_entry.compressed_data_range()
+        );
+
+        let mut expected_contents = Vec::new();
+        decompressor(wayfinder.compression_method(), expected_entry.data())
+            .unwrap()
+            .read_to_end(&mut expected_contents)
+            .unwrap();
+        let contents = entry.read_to_vec(decompressor).await.unwrap();
+        assert_eq!(contents, expected_contents);
+    }
+    assert!(expected_entries.next_entry().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_async_read_corrupt_crc() {
+    let mut data = std::fs::read("assets/test.zip").unwrap();
+    let (cd_offset, _) = ZipArchive::from_slice(&data)
+        .unwrap()
+        .central_directory_range();
+    data[cd_offset as usize + 16] ^= 1;
+
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = AsyncZipArchive::from_reader_at(TokioReaderAt::new(data), &mut buffer)
+        .await
+        .unwrap();
+    let wayfinder = archive
+        .entries(&mut buffer)
+        .next_entry()
+        .await
+        .unwrap()
+        .unwrap()
+        .wayfinder();
+    let entry = archive.get_entry(wayfinder).await.unwrap();
+    let err = entry.read_to_vec(decompressor).await.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidChecksum { .. }));
+}
+
+#[tokio::test]
+async fn test_async_get_entry_checked() {
+    let mut data = std::fs::read("assets/test.zip").unwrap();
+
+    // Corrupt the CRC of the first local file header only
+    data[14] ^= 0xff;
+
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = AsyncZipArchive::from_reader_at(TokioReaderAt::new(data), &mut buffer)
+        .await
+        .unwrap();
+    let wayfinder = archive
+        .entries(&mut buffer)
+        .next_entry()
+        .await
+        .unwrap()
+        .unwrap()
+        .wayfinder();
+    assert!(archive.get_entry(wayfinder).await.is_ok());
+    let err = archive.get_entry_checked(wayfinder).await.unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::LocalHeaderMismatch { field: "crc", .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_async_entries_seek_to_offset() {
+    let reader = TokioReaderAt::open("assets/test.zip").await.unwrap();
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = AsyncZipArchive::from_reader_at(reader, &mut buffer)
+        .await
+        .unwrap();
+
+    let mut entries = archive.entries(&mut buffer);
+    entries.next_entry().await.unwrap();
+    let checkpoint = entries.position();
+    let second = entries.next_entry().await.unwrap().unwrap().wayfinder();
+
+    let mut entries = archive.entries(&mut buffer);
+    entries.seek_to_offset(checkpoint).unwrap();
+    let resumed = entries.next_entry().await.unwrap().unwrap().wayfinder();
+    assert_eq!(resumed, second);
+    assert!(entries.next_entry().await.unwrap().is_none());
+
+    let err = entries.seek_to_offset(0).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+}
+
+#[tokio::test]
+async fn test_async_read_to_vec_limited() {
+    let reader = TokioReaderAt::open("assets/test.zip").await.unwrap();
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = AsyncZipArchive::from_reader_at(reader, &mut buffer)
+        .await
+        .unwrap();
+    let wayfinder = archive
+        .entries(&mut buffer)
+        .next_entry()
+        .await
+        .unwrap()
+        .unwrap()
+        .wayfinder();
+    let entry = archive.get_entry(wayfinder).await.unwrap();
+
+    let size = wayfinder.uncompressed_size_hint();
+    let contents = entry.read_to_vec_limited(size, decompressor).await.unwrap();
+    assert_eq!(contents, b"This is a test text file.\n");
+
+    let err = entry
+        .read_to_vec_limited(size - 1, decompressor)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::LimitExceeded { .. }));
+    assert!(!err.is_corruption());
+
+    let err = entry
+        .read_to_vec_limited(1, decompressor)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::LimitExceeded { limit: 1, .. }
+    ));
+}
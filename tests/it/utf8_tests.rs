@@ -64,6 +64,60 @@ fn test_directory_utf8_flag(#[case] dirname: &str, #[case] should_have_utf8_flag
     );
 }
 
+/// Test names written as CP-437 never have the UTF-8 flag
+#[rstest]
+#[case("file.txt", b"file.txt")]
+#[case("café.txt", b"caf\x82.txt")]
+#[case("file~backup.txt", b"file~backup.txt")]
+#[case("ÄÖÜ/ß", b"\x8e\x99\x9a/\xe1")]
+fn test_filename_cp437(#[case] filename: &str, #[case] expected: &[u8]) {
+    let mut output = Vec::new();
+    {
+        let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+        let mut file = archive
+            .new_file(filename)
+            .encoding(rawzip::Encoding::Cp437)
+            .create()
+            .unwrap();
+        let mut writer = rawzip::ZipDataWriter::new(&mut file);
+        writer.write_all(b"test content").unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        file.finish(descriptor).unwrap();
+        archive.finish().unwrap();
+    }
+
+    let flags = extract_flags_from_zip(&output);
+    assert_eq!(flags & 0x800, 0);
+
+    let archive = rawzip::ZipArchive::from_slice(&output).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(entry.file_path().as_ref(), expected);
+    assert_eq!(&output[30..30 + expected.len()], expected);
+}
+
+#[test]
+fn test_filename_cp437_unrepresentable() {
+    let mut output = Vec::new();
+    let mut archive = rawzip::ZipArchiveWriter::new(&mut output);
+    for name in ["🦀/", "テスト/"] {
+        let err = archive
+            .new_dir(name)
+            .encoding(rawzip::Encoding::Cp437)
+            .create()
+            .unwrap_err();
+        assert!(matches!(err.kind(), rawzip::ErrorKind::InvalidInput { .. }));
+    }
+
+    let err = archive
+        .new_file("🦀.txt")
+        .encoding(rawzip::Encoding::Cp437)
+        .create()
+        .err()
+        .unwrap();
+    assert!(matches!(err.kind(), rawzip::ErrorKind::InvalidInput { .. }));
+    assert_eq!(archive.entry_count(), 0);
+}
+
 /// Test the UTF-8
 /// Helper function to extract the general purpose bit flags from the first local file header
 /// This is a simplified parser just for testing purposes
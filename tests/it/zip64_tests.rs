@@ -1,6 +1,6 @@
 use rawzip::{ZipArchive, ZipArchiveWriter, ZipDataWriter, RECOMMENDED_BUFFER_SIZE};
 use rstest::rstest;
-use std::io::{Cursor, Write};
+use std::io::{Cursor, Read, Write};
 
 // ZIP64 signatures to check for
 const ZIP64_EOCD_SIGNATURE: u32 = 0x06064b50;
@@ -82,3 +82,344 @@ fn test_zip64_threshold_entries(#[case] entry_count: usize, #[case] should_be_zi
 
     verify_expected_entries(&data, entry_count as u64);
 }
+
+#[test]
+fn test_zip64_lowered_file_size_threshold() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = ZipArchiveWriter::builder()
+        .zip64_file_size_threshold(10)
+        .build(&mut output);
+
+    let mut file = archive.new_file("small.txt").create().unwrap();
+    let mut writer = ZipDataWriter::new(&mut file);
+    writer.write_all(b"Hello, world!").unwrap();
+    let (_, descriptor_output) = writer.finish().unwrap();
+    file.finish(descriptor_output).unwrap();
+    archive.finish().unwrap();
+
+    let data = output.into_inner();
+
+    // ZIP64 extra field header with an uncompressed and compressed size
+    let extra_field_header = [0x01, 0x00, 0x10, 0x00];
+    assert!(data.windows(4).any(|w| w == extra_field_header));
+    assert!(contains_zip64_signatures(&data));
+
+    let read_archive = ZipArchive::from_slice(&data).unwrap();
+    let entry = read_archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(entry.uncompressed_size_hint(), 13);
+    assert_eq!(entry.compressed_size_hint(), 13);
+    let zip_entry = read_archive.get_entry(entry.wayfinder()).unwrap();
+    assert_eq!(zip_entry.data(), b"Hello, world!");
+}
+
+#[test]
+fn test_zip64_lowered_offset_and_entries_threshold() {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = ZipArchiveWriter::builder()
+        .zip64_offset_threshold(1)
+        .zip64_entries_threshold(2)
+        .build(&mut output);
+
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        let mut file = archive.new_file(name).create().unwrap();
+        let mut writer = ZipDataWriter::new(&mut file);
+        writer.write_all(name.as_bytes()).unwrap();
+        let (_, descriptor_output) = writer.finish().unwrap();
+        file.finish(descriptor_output).unwrap();
+    }
+    archive.finish().unwrap();
+
+    let data = output.into_inner();
+    assert!(contains_zip64_signatures(&data));
+    verify_expected_entries(&data, 3);
+
+    let read_archive = ZipArchive::from_slice(&data).unwrap();
+    for (entry, name) in read_archive.entries().zip(["a.txt", "b.txt", "c.txt"]) {
+        let entry = entry.unwrap();
+        let zip_entry = read_archive.get_entry(entry.wayfinder()).unwrap();
+        assert_eq!(zip_entry.data(), name.as_bytes());
+    }
+}
+
+/// Rewrites a writer-produced zip64 archive as one a legacy writer would emit
+/// for exactly 65535 entries: no zip64 records and a 0xFFFF entry count.
+fn strip_zip64_records(data: &[u8]) -> Vec<u8> {
+    let eocd_pos = data.len() - 22;
+    let zip64_eocd_pos = eocd_pos - 20 - 56;
+    let zip64_eocd = &data[zip64_eocd_pos..eocd_pos - 20];
+    assert_eq!(&zip64_eocd[..4], &ZIP64_EOCD_SIGNATURE.to_le_bytes());
+
+    let cd_size = u64::from_le_bytes(zip64_eocd[40..48].try_into().unwrap());
+    let cd_offset = u64::from_le_bytes(zip64_eocd[48..56].try_into().unwrap());
+
+    let mut result = data[..zip64_eocd_pos].to_vec();
+    let mut eocd = data[eocd_pos..].to_vec();
+    eocd[8..10].copy_from_slice(&u16::MAX.to_le_bytes());
+    eocd[10..12].copy_from_slice(&u16::MAX.to_le_bytes());
+    eocd[12..16].copy_from_slice(&(cd_size as u32).to_le_bytes());
+    eocd[16..20].copy_from_slice(&(cd_offset as u32).to_le_bytes());
+    result.extend_from_slice(&eocd);
+    result
+}
+
+#[test]
+fn test_65535_entries_without_zip64_locator() {
+    let mut archive = ZipArchiveWriter::new(Cursor::new(Vec::new()));
+    for i in 0..65535 {
+        let filename = format!("{:05}", i);
+        let mut file = archive.new_file(&filename).create().unwrap();
+        let writer = ZipDataWriter::new(&mut file);
+        let (_, descriptor_output) = writer.finish().unwrap();
+        file.finish(descriptor_output).unwrap();
+    }
+    let data = archive.finish().unwrap().into_inner();
+
+    let data = strip_zip64_records(&data);
+    assert!(!contains_zip64_signatures(&data));
+    verify_expected_entries(&data, 65535);
+
+    // Without a central directory where the EOCD points, the missing locator
+    // is still reported as an error.
+    let mut corrupt = data.clone();
+    let eocd_pos = corrupt.len() - 22;
+    let cd_size = u32::from_le_bytes(corrupt[eocd_pos + 12..eocd_pos + 16].try_into().unwrap());
+    corrupt[eocd_pos - cd_size as usize] = 0;
+    assert!(ZipArchive::from_slice(&corrupt).is_err());
+
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    assert!(ZipArchive::from_seekable(Cursor::new(&corrupt), &mut buffer).is_err());
+}
+
+#[test]
+fn test_central_directory_range_zip64() {
+    let data = std::fs::read("assets/zip64.zip").unwrap();
+    let sig = ZIP64_EOCD_SIGNATURE.to_le_bytes();
+    let zip64_eocd_pos = data.windows(4).rposition(|w| w == sig).unwrap();
+    let zip64_eocd = &data[zip64_eocd_pos..];
+    let cd_size = u64::from_le_bytes(zip64_eocd[40..48].try_into().unwrap());
+    let cd_offset = u64::from_le_bytes(zip64_eocd[48..56].try_into().unwrap());
+    let expected = (cd_offset, cd_offset + cd_size);
+
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    assert_eq!(archive.central_directory_range(), expected);
+    assert_eq!(expected.1, zip64_eocd_pos as u64);
+    assert_eq!(&data[cd_offset as usize..][..4], &[0x50, 0x4b, 0x01, 0x02]);
+
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    assert_eq!(archive.central_directory_range(), expected);
+}
+
+/// Builds a single deflated entry archive where the local header defers the
+/// flagged sizes to a zip64 extra field (which always holds both sizes), while
+/// the central directory has plain 32-bit sizes and no extra field.
+fn local_zip64_archive(
+    name: &[u8],
+    content: &[u8],
+    uncompressed: bool,
+    compressed: bool,
+) -> Vec<u8> {
+    let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(content).unwrap();
+    let data = encoder.finish().unwrap();
+
+    let crc = rawzip::crc32(content);
+    let uncompressed_size = content.len() as u32;
+    let compressed_size = data.len() as u32;
+    let sentinel = |flagged: bool, size: u32| if flagged { u32::MAX } else { size };
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&0x04034b50u32.to_le_bytes());
+    out.extend_from_slice(&45u16.to_le_bytes()); // version needed
+    out.extend_from_slice(&0u16.to_le_bytes()); // flags
+    out.extend_from_slice(&8u16.to_le_bytes()); // deflate
+    out.extend_from_slice(&0u32.to_le_bytes()); // time and date
+    out.extend_from_slice(&crc.to_le_bytes());
+    out.extend_from_slice(&sentinel(compressed, compressed_size).to_le_bytes());
+    out.extend_from_slice(&sentinel(uncompressed, uncompressed_size).to_le_bytes());
+    out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    out.extend_from_slice(&20u16.to_le_bytes()); // extra field length
+    out.extend_from_slice(name);
+    out.extend_from_slice(&0x0001u16.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(&u64::from(uncompressed_size).to_le_bytes());
+    out.extend_from_slice(&u64::from(compressed_size).to_le_bytes());
+    out.extend_from_slice(&data);
+
+    let cd_offset = out.len() as u32;
+    out.extend_from_slice(&0x02014b50u32.to_le_bytes());
+    out.extend_from_slice(&45u16.to_le_bytes()); // version made by
+    out.extend_from_slice(&45u16.to_le_bytes()); // version needed
+    out.extend_from_slice(&0u16.to_le_bytes()); // flags
+    out.extend_from_slice(&8u16.to_le_bytes()); // deflate
+    out.extend_from_slice(&0u32.to_le_bytes()); // time and date
+    out.extend_from_slice(&crc.to_le_bytes());
+    out.extend_from_slice(&compressed_size.to_le_bytes());
+    out.extend_from_slice(&uncompressed_size.to_le_bytes());
+    out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    out.extend_from_slice(&[0u8; 12]); // extra, comment, disk, attributes
+    out.extend_from_slice(&0u32.to_le_bytes()); // local header offset
+    out.extend_from_slice(name);
+    let cd_size = out.len() as u32 - cd_offset;
+
+    out.extend_from_slice(&0x06054b50u32.to_le_bytes());
+    out.extend_from_slice(&[0u8; 4]); // disk numbers
+    out.extend_from_slice(&1u16.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes());
+    out.extend_from_slice(&cd_size.to_le_bytes());
+    out.extend_from_slice(&cd_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    out
+}
+
+#[rstest]
+#[case(true, true)]
+#[case(true, false)]
+#[case(false, true)]
+fn test_local_zip64_extra_field(#[case] uncompressed: bool, #[case] compressed: bool) {
+    let content = b"Hello, zip64! Hello, zip64! Hello, zip64!";
+    let data = local_zip64_archive(b"file.txt", content, uncompressed, compressed);
+    let body_start = 30 + 8 + 20;
+    let body_end = data.len() as u64 - (46 + 8) - 22;
+
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next().unwrap().unwrap();
+    assert!(!entry.is_zip64());
+    let zip_entry = archive.get_entry_checked(entry.wayfinder()).unwrap();
+    assert_eq!(zip_entry.compressed_data_range(), (body_start, body_end));
+
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = ZipArchive::from_seekable(Cursor::new(&data), &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let entry = entries.next_entry().unwrap().unwrap();
+    let zip_entry = archive.get_entry_checked(entry.wayfinder()).unwrap();
+    assert_eq!(zip_entry.compressed_data_range(), (body_start, body_end));
+
+    let decoder = flate2::read::DeflateDecoder::new(zip_entry.reader());
+    let mut actual = Vec::new();
+    zip_entry
+        .verifying_reader(decoder)
+        .read_to_end(&mut actual)
+        .unwrap();
+    assert_eq!(actual, content);
+}
+
+/// Strict readers require the disk fields of a single disk archive to point
+/// at disk 0 of 1, with all entries on it, so cross check with another reader.
+#[rstest]
+#[case::regular(u64::MAX, false)]
+#[case::zip64(1, true)]
+fn test_end_of_central_directory_disk_fields(#[case] offset_threshold: u64, #[case] zip64: bool) {
+    let mut output = Cursor::new(Vec::new());
+    let mut archive = ZipArchiveWriter::builder()
+        .zip64_offset_threshold(offset_threshold)
+        .build(&mut output);
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        archive.store_reader(name, name.as_bytes()).unwrap();
+    }
+    archive.finish().unwrap();
+    let data = output.into_inner();
+
+    let eocd = &data[data.len() - 22..];
+    assert_eq!(&eocd[4..6], &0u16.to_le_bytes(), "number of this disk");
+    assert_eq!(&eocd[6..8], &0u16.to_le_bytes(), "central directory disk");
+    assert_eq!(eocd[8..10], eocd[10..12], "entries on this disk");
+
+    assert_eq!(contains_zip64_signatures(&data), zip64);
+    if zip64 {
+        let locator = &data[data.len() - 22 - 20..data.len() - 22];
+        assert_eq!(&locator[..4], &ZIP64_EOCD_LOCATOR_SIGNATURE.to_le_bytes());
+        assert_eq!(&locator[4..8], &0u32.to_le_bytes(), "zip64 eocd disk");
+        assert_eq!(&locator[16..20], &1u32.to_le_bytes(), "total disks");
+
+        let zip64_eocd = &data[data.len() - 22 - 20 - 56..data.len() - 22 - 20];
+        assert_eq!(&zip64_eocd[..4], &ZIP64_EOCD_SIGNATURE.to_le_bytes());
+        assert_eq!(
+            &zip64_eocd[16..20],
+            &0u32.to_le_bytes(),
+            "number of this disk"
+        );
+        assert_eq!(
+            &zip64_eocd[20..24],
+            &0u32.to_le_bytes(),
+            "central directory disk"
+        );
+        assert_eq!(
+            &zip64_eocd[24..32],
+            &3u64.to_le_bytes(),
+            "entries on this disk"
+        );
+        assert_eq!(&zip64_eocd[32..40], &3u64.to_le_bytes(), "total entries");
+    }
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(&data)).unwrap();
+    assert_eq!(archive.len(), 3);
+    for (i, name) in ["a.txt", "b.txt", "c.txt"].into_iter().enumerate() {
+        let mut file = archive.by_index(i).unwrap();
+        assert_eq!(file.name(), name);
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, name);
+    }
+}
+
+/// Entries marked as large announce ZIP64 in their local header, so that
+/// streaming readers expect the 8 byte sizes of the data descriptor.
+#[rstest]
+#[case::streaming(false)]
+#[case::seekable(true)]
+fn test_large_file_local_header(#[case] seekable: bool) {
+    let mut output = Cursor::new(Vec::new());
+    let builder = ZipArchiveWriter::builder().zip64_file_size_threshold(10);
+    let mut archive = if seekable {
+        builder.build_seekable(&mut output)
+    } else {
+        builder.build(&mut output)
+    };
+    archive
+        .new_file("large.txt")
+        .large_file(true)
+        .store_reader(&b"Hello, world!"[..])
+        .unwrap();
+    archive.finish().unwrap();
+    let data = output.into_inner();
+
+    let local_header = &data[..30];
+    assert_eq!(&local_header[4..6], &45u16.to_le_bytes(), "version needed");
+    assert_eq!(&local_header[6..8], &0x08u16.to_le_bytes(), "flags");
+    assert_eq!(&local_header[18..22], &u32::MAX.to_le_bytes());
+    assert_eq!(&local_header[22..26], &u32::MAX.to_le_bytes());
+    assert_eq!(&local_header[28..30], &20u16.to_le_bytes());
+    let extra_field = &data[30 + "large.txt".len()..][..20];
+    assert_eq!(&extra_field[..4], &[0x01, 0x00, 0x10, 0x00]);
+    assert_eq!(&extra_field[4..], &[0u8; 16]);
+
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let read_archive = ZipArchive::from_reader_at(&data[..], &mut buffer).unwrap();
+    let wayfinder = read_archive
+        .entries(&mut buffer)
+        .next_entry()
+        .unwrap()
+        .unwrap()
+        .wayfinder();
+    let zip_entry = read_archive.get_entry(wayfinder).unwrap();
+    let descriptor = zip_entry.data_descriptor().unwrap().unwrap();
+    assert!(descriptor.is_zip64());
+    assert_eq!(descriptor.compressed_size(), 13);
+    assert_eq!(descriptor.uncompressed_size(), 13);
+    let mut actual = Vec::new();
+    zip_entry
+        .verifying_reader(zip_entry.reader())
+        .read_to_end(&mut actual)
+        .unwrap();
+    assert_eq!(actual, b"Hello, world!");
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(&data)).unwrap();
+    let mut contents = String::new();
+    archive
+        .by_index(0)
+        .unwrap()
+        .read_to_string(&mut contents)
+        .unwrap();
+    assert_eq!(contents, "Hello, world!");
+}
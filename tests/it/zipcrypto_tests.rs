@@ -0,0 +1,112 @@
+use rawzip::{CompressionMethod, EncryptionMethod, ErrorKind, ZipArchive, RECOMMENDED_BUFFER_SIZE};
+use std::io::Read;
+
+#[test]
+fn test_zipcrypto_decrypt() {
+    let file = std::fs::File::open("assets/zipcrypto.zip").unwrap();
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = ZipArchive::from_file(file, &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+
+    let mut actual = Vec::new();
+    while let Some(entry) = entries.next_entry().unwrap() {
+        assert!(entry.is_encrypted());
+        let name = entry
+            .file_path()
+            .try_normalize()
+            .unwrap()
+            .as_ref()
+            .to_string();
+        let method = entry.compression_method();
+        let entry = archive.get_entry(entry.wayfinder()).unwrap();
+
+        let err = entry.decrypting_reader(b"wrong").map(|_| ()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidPassword));
+
+        let decrypted = entry.decrypting_reader(b"password").unwrap();
+        let mut contents = String::new();
+        match method {
+            CompressionMethod::Store => entry
+                .verifying_reader(decrypted)
+                .read_to_string(&mut contents)
+                .unwrap(),
+            CompressionMethod::Deflate => entry
+                .verifying_reader(flate2::read::DeflateDecoder::new(decrypted))
+                .read_to_string(&mut contents)
+                .unwrap(),
+            _ => panic!("unexpected compression method {method:?}"),
+        };
+        actual.push((name, contents));
+    }
+
+    assert_eq!(actual.len(), 3);
+    assert_eq!(actual[0], ("hello.txt".into(), "Hello, world!\n".into()));
+    assert_eq!(
+        actual[1].1,
+        "lorem ipsum dolor sit amet ".repeat(200) + "\n"
+    );
+    assert_eq!(actual[2], ("-".into(), "streamed data\n".into()));
+}
+
+#[test]
+fn test_zipcrypto_unencrypted_entry() {
+    let file = std::fs::File::open("assets/test.zip").unwrap();
+    let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+    let archive = ZipArchive::from_file(file, &mut buffer).unwrap();
+    let mut entries = archive.entries(&mut buffer);
+    let entry = entries.next_entry().unwrap().unwrap();
+    assert!(!entry.is_encrypted());
+
+    let entry = archive.get_entry(entry.wayfinder()).unwrap();
+    let err = entry
+        .decrypting_reader(b"password")
+        .map(|_| ())
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidInput { .. }));
+}
+
+#[test]
+fn test_zipcrypto_encryption_method() {
+    let data = std::fs::read("assets/zipcrypto.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    for entry in archive.entries() {
+        let entry = entry.unwrap();
+        assert_eq!(entry.encryption(), Some(EncryptionMethod::ZipCrypto));
+    }
+
+    let data = std::fs::read("assets/test.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    for entry in archive.entries() {
+        assert_eq!(entry.unwrap().encryption(), None);
+    }
+}
+
+#[test]
+fn test_strong_encryption_method() {
+    let mut data = std::fs::read("assets/zipcrypto.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let (offset, _) = archive.central_directory_range();
+
+    // Set the strong encryption bit of the first central directory record
+    let flags = offset as usize + 8;
+    data[flags] |= 0x40;
+
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(entry.encryption(), Some(EncryptionMethod::StrongEncryption));
+}
+
+#[test]
+fn test_aes_method_without_extra_field() {
+    let mut data = std::fs::read("assets/zipcrypto.zip").unwrap();
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let (offset, _) = archive.central_directory_range();
+
+    // Mark the first entry as AES encrypted, which it has no extra field for
+    let method = offset as usize + 10;
+    data[method..method + 2].copy_from_slice(&99u16.to_le_bytes());
+
+    let archive = ZipArchive::from_slice(&data).unwrap();
+    let entry = archive.entries().next_entry().unwrap().unwrap();
+    assert_eq!(entry.encryption(), Some(EncryptionMethod::Unknown));
+}